@@ -5,7 +5,7 @@ use rand::{rngs::StdRng, Rng, SeedableRng};
 use rust_ray_tracer::{
     math::vec3::Vec3,
     raytracing::{
-        camera::Camera,
+        camera::PerspectiveCamera,
         material::{MatDielectric, MatLabmertian, MatMetalic, Material},
         objects::{HittableList, HittableObject, Sphere},
         renderer::Renderer,
@@ -54,18 +54,14 @@ fn scene() -> HittableList {
                     Arc::new(Material::Metalic(MatMetalic { albedo, roughness }))
                 } else {
                     // glass
-                    Arc::new(Material::Dielectric(MatDielectric {
-                        refraction_index: 1.5,
-                    }))
+                    Arc::new(Material::Dielectric(MatDielectric::new(1.5)))
                 };
                 objects.push(Arc::new(Sphere::new(center, 0.2, sphere_material)));
             }
         }
     }
 
-    let material = Arc::new(Material::Dielectric(MatDielectric {
-        refraction_index: 1.5,
-    }));
+    let material = Arc::new(Material::Dielectric(MatDielectric::new(1.5)));
     objects.push(Arc::new(Sphere::new(Vec3::new(0., 1., 0.), 1.0, material)));
 
     let material = Arc::new(Material::Labmertian(MatLabmertian {
@@ -92,7 +88,7 @@ fn setup_render() -> Renderer {
     let dist_to_focus = 5.;
     let aperture = 0.1;
 
-    let camera = Camera::new(
+    let camera = PerspectiveCamera::new(
         lookfrom,
         lookat,
         rotation,
@@ -105,7 +101,7 @@ fn setup_render() -> Renderer {
     );
 
     Renderer::init(
-        camera,
+        Box::new(camera),
         SAMPLES_PER_PIXEL,
         MAX_RAY_BOUNCES,
         Box::new(scene()),
@@ -2,16 +2,32 @@ use std::sync::Arc;
 
 use rand::{rngs::StdRng, Rng, SeedableRng};
 use rust_ray_tracer::{
-    math::vec3::Vec3,
+    math::{quaternion::Quaternion, vec3::Vec3},
     raytracing::{
-        camera::Camera,
-        material::{MatDielectric, MatDiffuseLight, MatLabmertian, MatMetalic, Material},
+        animation::{Interpolation, Keyframe, Track},
+        background::{Background, EnvironmentMap, PhysicalSky},
+        camera::{
+            AnimatedCamera, Aperture, EquirectangularCamera, FisheyeCamera, FisheyeProjection,
+            OrthographicCamera, PerspectiveCamera, RealisticCamera, StereoCamera, StereoLayout,
+            StereoMode, DOUBLE_GAUSS_50MM,
+        },
+        light::{AreaLight, DirectionalLight, Light, PointLight, SpotLight},
+        material::{
+            Dispersion, MatBumpMap, MatCoated, MatConductor, MatDielectric, MatDiffuseLight,
+            MatEmissive, MatLabmertian, MatMetalic, MatMix, MatNormalMap, MatOrenNayar,
+            MatPrincipled, MatRoughDielectric, MatSheen, MatSubsurface, Material, ScalarInput,
+            ThinFilm,
+        },
+        medium::Medium,
         objects::{
-            yaw_rotation::YawRotation, Cube, HittableList, HittableObject, MovingSphere, PlaneX,
-            PlaneY, PlaneZ, Sphere, Translate,
+            yaw_rotation::YawRotation, AnimatedTransform, BvhNode, Cube, HittableList,
+            HittableObject, MovingSphere, PlaneX, PlaneY, PlaneZ, Sphere, Translate,
         },
         renderer::Renderer,
-        texture::{CheckerTexture, ImageTexture, SolidColorTexture, Texture},
+        texture::{
+            CheckerTexture, ColorRamp, ColorSpace, ImageTexture, MarbleTexture, NoiseTexture,
+            SolidColorTexture, Texture, VoronoiMode, VoronoiTexture, WoodTexture,
+        },
     },
 };
 
@@ -26,7 +42,7 @@ pub fn earth_scene(settings: &GlobalSettings) -> Renderer {
     let dist_to_focus = 10.;
     let aperture = 0.1;
 
-    let camera = Camera::new(
+    let camera = PerspectiveCamera::new(
         lookfrom,
         lookat,
         rotation,
@@ -49,12 +65,12 @@ pub fn earth_scene(settings: &GlobalSettings) -> Renderer {
     let world = HittableList::new(vec![globe]);
 
     let mut render = Renderer::init(
-        camera,
+        Box::new(camera),
         settings.samples_per_pixel,
         settings.max_ray_bounces,
         Box::new(world),
     );
-    render.background = Vec3::new(0.70, 0.80, 1.00);
+    render.background = Background::SolidColor(Vec3::new(0.70, 0.80, 1.00));
     render
 }
 
@@ -67,7 +83,7 @@ pub fn test_scene(settings: &GlobalSettings) -> Renderer {
     let dist_to_focus = 10.;
     let aperture = 0.1;
 
-    let camera = Camera::new(
+    let camera = PerspectiveCamera::new(
         lookfrom,
         lookat,
         rotation,
@@ -87,9 +103,7 @@ pub fn test_scene(settings: &GlobalSettings) -> Renderer {
     let material_center = Arc::new(Material::Labmertian(MatLabmertian {
         albedo: Arc::new(Texture::SolidColor(SolidColorTexture::new(0.7, 0.3, 0.3))),
     }));
-    let material_left = Arc::new(Material::Dielectric(MatDielectric {
-        refraction_index: 1.7,
-    }));
+    let material_left = Arc::new(Material::Dielectric(MatDielectric::new(1.7)));
     let material_right = Arc::new(Material::Metalic(MatMetalic::new(
         Vec3::new(0.8, 0.6, 0.2),
         1.0,
@@ -109,12 +123,12 @@ pub fn test_scene(settings: &GlobalSettings) -> Renderer {
     let world = HittableList::new(objects);
     // ---------
     let mut render = Renderer::init(
-        camera,
+        Box::new(camera),
         settings.samples_per_pixel,
         settings.max_ray_bounces,
         Box::new(world),
     );
-    render.background = Vec3::new(0.70, 0.80, 1.00);
+    render.background = Background::SolidColor(Vec3::new(0.70, 0.80, 1.00));
     render
 }
 
@@ -127,7 +141,7 @@ pub fn random_scene(settings: &GlobalSettings) -> Renderer {
     let dist_to_focus = 10.;
     let aperture = 0.1;
 
-    let camera = Camera::new(
+    let camera = PerspectiveCamera::new(
         lookfrom,
         lookat,
         rotation,
@@ -144,8 +158,8 @@ pub fn random_scene(settings: &GlobalSettings) -> Renderer {
 
     let ground_material = Arc::new(Material::Labmertian(MatLabmertian {
         albedo: Arc::new(Texture::Checker(CheckerTexture::new(
-            Arc::new(SolidColorTexture::new(0.2, 0.3, 0.1)),
-            Arc::new(SolidColorTexture::new(0.9, 0.9, 0.9)),
+            Arc::new(Texture::SolidColor(SolidColorTexture::new(0.2, 0.3, 0.1))),
+            Arc::new(Texture::SolidColor(SolidColorTexture::new(0.9, 0.9, 0.9))),
         ))),
     }));
     objects.push(Arc::new(Sphere::new(
@@ -188,18 +202,14 @@ pub fn random_scene(settings: &GlobalSettings) -> Renderer {
                     objects.push(Arc::new(Sphere::new(center, 0.2, sphere_material)));
                 } else {
                     // glass
-                    let sphere_material = Arc::new(Material::Dielectric(MatDielectric {
-                        refraction_index: 1.5,
-                    }));
+                    let sphere_material = Arc::new(Material::Dielectric(MatDielectric::new(1.5)));
                     objects.push(Arc::new(Sphere::new(center, 0.2, sphere_material)));
                 };
             }
         }
     }
 
-    let material = Arc::new(Material::Dielectric(MatDielectric {
-        refraction_index: 1.5,
-    }));
+    let material = Arc::new(Material::Dielectric(MatDielectric::new(1.5)));
     objects.push(Arc::new(Sphere::new(Vec3::new(0., 1., 0.), 1.0, material)));
 
     let material = Arc::new(Material::Labmertian(MatLabmertian {
@@ -216,13 +226,13 @@ pub fn random_scene(settings: &GlobalSettings) -> Renderer {
     // ------------------------
 
     let mut render = Renderer::init(
-        camera,
+        Box::new(camera),
         settings.samples_per_pixel,
         settings.max_ray_bounces,
         Box::new(world),
     );
 
-    render.background = Vec3::new(0.70, 0.80, 1.00);
+    render.background = Background::SolidColor(Vec3::new(0.70, 0.80, 1.00));
     render
 }
 
@@ -235,7 +245,7 @@ pub fn lighting_scene(settings: &GlobalSettings) -> Renderer {
     let dist_to_focus = 10.;
     let aperture = 0.1;
 
-    let camera = Camera::new(
+    let camera = PerspectiveCamera::new(
         lookfrom,
         lookat,
         rotation,
@@ -273,7 +283,7 @@ pub fn lighting_scene(settings: &GlobalSettings) -> Renderer {
     let world = HittableList::new(objects);
     // ---------
     Renderer::init(
-        camera,
+        Box::new(camera),
         settings.samples_per_pixel,
         settings.max_ray_bounces,
         Box::new(world),
@@ -294,7 +304,7 @@ pub fn cornell_box(settings: &mut GlobalSettings) -> Renderer {
     let dist_to_focus = 10.;
     let aperture = 0.1;
 
-    let camera = Camera::new(
+    let camera = PerspectiveCamera::new(
         lookfrom,
         lookat,
         rotation,
@@ -337,10 +347,11 @@ pub fn cornell_box(settings: &mut GlobalSettings) -> Renderer {
     ));
     let cube1 = Box::new(Translate::new(cube1, Vec3::new(100., 200., 0.)));
     let cube1 = Arc::new(YawRotation::new(cube1, 25.));
+    let ceiling_light = Arc::new(PlaneY::new(213., 554., 227., 130., 105., light));
     let objects: Vec<Arc<dyn HittableObject + Send + Sync>> = vec![
         Arc::new(PlaneX::new(555., 0., 0., 555., 555., green)),
         Arc::new(PlaneX::new(0., 0., 0., 555., 555., red)),
-        Arc::new(PlaneY::new(213., 554., 227., 130., 105., light)),
+        ceiling_light.clone(),
         Arc::new(PlaneY::new(0., 0., 0., 555., 555., white.clone())),
         Arc::new(PlaneY::new(0., 555., 0., 555., 555., white.clone())),
         Arc::new(PlaneZ::new(0., 0., 555., 555., 555., white.clone())),
@@ -354,10 +365,1574 @@ pub fn cornell_box(settings: &mut GlobalSettings) -> Renderer {
 
     let world = HittableList::new(objects);
     // ---------
-    Renderer::init(
-        camera,
+    let mut render = Renderer::init(
+        Box::new(camera),
         settings.samples_per_pixel,
         settings.max_ray_bounces,
         Box::new(world),
-    )
+    );
+    render.lights = vec![Light::Area(AreaLight::new(ceiling_light))];
+    render
+}
+
+pub fn noise_scene(settings: &GlobalSettings) -> Renderer {
+    // Camera
+    let lookfrom = Vec3::new(13., 2., 3.);
+    let lookat = Vec3::new(0., 0., 0.);
+    let rotation = Vec3::new(0., 1., 0.);
+    let vfov = 20.0;
+    let dist_to_focus = 10.;
+    let aperture = 0.1;
+
+    let camera = PerspectiveCamera::new(
+        lookfrom,
+        lookat,
+        rotation,
+        vfov,
+        settings.aspect_ratio,
+        aperture,
+        dist_to_focus,
+        settings.animation_start_time,
+        settings.animation_end_time,
+    );
+
+    // --------World---------
+    //Materials
+    let marble = Arc::new(Material::Labmertian(MatLabmertian {
+        albedo: Arc::new(Texture::Marble(MarbleTexture::new(4.))),
+    }));
+    let wood = Arc::new(Material::Labmertian(MatLabmertian {
+        albedo: Arc::new(Texture::Wood(WoodTexture::new(2., 8.))),
+    }));
+    let mut cells = VoronoiTexture::new(3., VoronoiMode::Edges);
+    cells.ramp = ColorRamp::new(vec![
+        (0., Vec3::new(0.05, 0.05, 0.05)),
+        (0.1, Vec3::new(0.9, 0.6, 0.2)),
+        (1., Vec3::new(0.9, 0.3, 0.1)),
+    ]);
+    let cells = Arc::new(Material::Labmertian(MatLabmertian {
+        albedo: Arc::new(Texture::Voronoi(cells)),
+    }));
+
+    // Objects
+    let objects: Vec<Arc<dyn HittableObject + Send + Sync>> = vec![
+        Arc::new(Sphere::new(Vec3::new(0., -1000., 0.), 1000., marble)),
+        Arc::new(Sphere::new(Vec3::new(0., 2., -1.5), 1.5, wood)),
+        Arc::new(Sphere::new(Vec3::new(0., 1., 1.5), 1., cells)),
+    ];
+    let world = HittableList::new(objects);
+    // ---------
+    let mut render = Renderer::init(
+        Box::new(camera),
+        settings.samples_per_pixel,
+        settings.max_ray_bounces,
+        Box::new(world),
+    );
+    render.background = Background::SolidColor(Vec3::new(0.70, 0.80, 1.00));
+    render
+}
+
+pub fn environment_scene(settings: &GlobalSettings) -> Renderer {
+    // Camera
+    let lookfrom = Vec3::new(13., 2., 3.);
+    let lookat = Vec3::new(0., 1., 0.);
+    let rotation = Vec3::new(0., 1., 0.);
+    let vfov = 20.0;
+    let dist_to_focus = 10.;
+    let aperture = 0.1;
+
+    let camera = PerspectiveCamera::new(
+        lookfrom,
+        lookat,
+        rotation,
+        vfov,
+        settings.aspect_ratio,
+        aperture,
+        dist_to_focus,
+        settings.animation_start_time,
+        settings.animation_end_time,
+    );
+
+    // --------World---------
+    //Materials
+    let material_ground = Arc::new(Material::Labmertian(MatLabmertian {
+        albedo: Arc::new(Texture::SolidColor(SolidColorTexture::new(0.5, 0.5, 0.5))),
+    }));
+    let material_diffuse = Arc::new(Material::Labmertian(MatLabmertian {
+        albedo: Arc::new(Texture::SolidColor(SolidColorTexture::new(0.7, 0.3, 0.3))),
+    }));
+    let material_glass = Arc::new(Material::Dielectric(MatDielectric::new(1.5)));
+    let material_metal = Arc::new(Material::Metalic(MatMetalic::new(
+        Vec3::new(0.8, 0.8, 0.8),
+        0.1,
+    )));
+
+    // Objects
+    let objects: Vec<Arc<dyn HittableObject + Send + Sync>> = vec![
+        Arc::new(Sphere::new(
+            Vec3::new(0., -1000., 0.),
+            1000.,
+            material_ground,
+        )),
+        Arc::new(Sphere::new(Vec3::new(0., 1., 0.), 1., material_diffuse)),
+        Arc::new(Sphere::new(Vec3::new(0., 1., 2.2), 1., material_glass)),
+        Arc::new(Sphere::new(Vec3::new(0., 1., -2.2), 1., material_metal)),
+    ];
+    let world = HittableList::new(objects);
+    // ---------
+
+    let mut path = std::env::current_dir().unwrap();
+    path.push("images");
+    path.push("environment.hdr");
+    let mut environment = EnvironmentMap::new(path).unwrap();
+    environment.set_rotation(90.);
+
+    let mut render = Renderer::init(
+        Box::new(camera),
+        settings.samples_per_pixel,
+        settings.max_ray_bounces,
+        Box::new(world),
+    );
+    render.background = Background::EnvironmentMap(environment);
+    render
+}
+
+pub fn sky_scene(settings: &GlobalSettings) -> Renderer {
+    // Camera
+    let lookfrom = Vec3::new(13., 2., 3.);
+    let lookat = Vec3::new(0., 1., 0.);
+    let rotation = Vec3::new(0., 1., 0.);
+    let vfov = 20.0;
+    let dist_to_focus = 10.;
+    let aperture = 0.1;
+
+    let camera = PerspectiveCamera::new(
+        lookfrom,
+        lookat,
+        rotation,
+        vfov,
+        settings.aspect_ratio,
+        aperture,
+        dist_to_focus,
+        settings.animation_start_time,
+        settings.animation_end_time,
+    );
+
+    // --------World---------
+    //Materials
+    let material_ground = Arc::new(Material::Labmertian(MatLabmertian {
+        albedo: Arc::new(Texture::SolidColor(SolidColorTexture::new(0.5, 0.5, 0.5))),
+    }));
+    let material_diffuse = Arc::new(Material::Labmertian(MatLabmertian {
+        albedo: Arc::new(Texture::SolidColor(SolidColorTexture::new(0.7, 0.3, 0.3))),
+    }));
+    let material_metal = Arc::new(Material::Metalic(MatMetalic::new(
+        Vec3::new(0.8, 0.8, 0.8),
+        0.0,
+    )));
+
+    // Objects
+    let objects: Vec<Arc<dyn HittableObject + Send + Sync>> = vec![
+        Arc::new(Sphere::new(
+            Vec3::new(0., -1000., 0.),
+            1000.,
+            material_ground,
+        )),
+        Arc::new(Sphere::new(Vec3::new(0., 1., 1.1), 1., material_diffuse)),
+        Arc::new(Sphere::new(Vec3::new(0., 1., -1.1), 1., material_metal)),
+    ];
+    let world = HittableList::new(objects);
+    // ---------
+
+    let mut render = Renderer::init(
+        Box::new(camera),
+        settings.samples_per_pixel,
+        settings.max_ray_bounces,
+        Box::new(world),
+    );
+    render.set_sky(PhysicalSky::from_angles(20., 60., 3.));
+    render
+}
+
+pub fn punctual_lights_scene(settings: &GlobalSettings) -> Renderer {
+    // Camera
+    let lookfrom = Vec3::new(13., 4., 3.);
+    let lookat = Vec3::new(0., 1., 0.);
+    let rotation = Vec3::new(0., 1., 0.);
+    let vfov = 25.0;
+    let dist_to_focus = 10.;
+    let aperture = 0.0;
+
+    let camera = PerspectiveCamera::new(
+        lookfrom,
+        lookat,
+        rotation,
+        vfov,
+        settings.aspect_ratio,
+        aperture,
+        dist_to_focus,
+        settings.animation_start_time,
+        settings.animation_end_time,
+    );
+
+    // --------World---------
+    //Materials
+    let material_ground = Arc::new(Material::Labmertian(MatLabmertian {
+        albedo: Arc::new(Texture::SolidColor(SolidColorTexture::new(0.8, 0.8, 0.8))),
+    }));
+    let material_center = Arc::new(Material::Labmertian(MatLabmertian {
+        albedo: Arc::new(Texture::SolidColor(SolidColorTexture::new(0.5, 0.2, 0.7))),
+    }));
+
+    // Objects
+    let objects: Vec<Arc<dyn HittableObject + Send + Sync>> = vec![
+        Arc::new(Sphere::new(
+            Vec3::new(0., -1000.0, 0.),
+            1000.0,
+            material_ground,
+        )),
+        Arc::new(Sphere::new(Vec3::new(0., 1., 0.), 1.0, material_center)),
+    ];
+    let world = HittableList::new(objects);
+    // ---------
+    let mut render = Renderer::init(
+        Box::new(camera),
+        settings.samples_per_pixel,
+        settings.max_ray_bounces,
+        Box::new(world),
+    );
+    render.lights = vec![
+        Light::Point(PointLight::new(
+            Vec3::new(2., 3., 3.),
+            Vec3::new(10., 8., 6.),
+        )),
+        Light::Spot(SpotLight::new(
+            Vec3::new(0., 6., -3.),
+            Vec3::new(0., 0., -1.5),
+            Vec3::new(20., 30., 60.),
+            25.,
+            15.,
+        )),
+        Light::Directional(DirectionalLight::new(
+            Vec3::new(-1., -2., 0.5),
+            Vec3::new(0.3, 0.3, 0.3),
+        )),
+    ];
+    render
+}
+
+pub fn microfacet_scene(settings: &GlobalSettings) -> Renderer {
+    // Camera
+    let lookfrom = Vec3::new(13., 3., 3.);
+    let lookat = Vec3::new(0., 1., 0.);
+    let rotation = Vec3::new(0., 1., 0.);
+    let vfov = 30.0;
+    let dist_to_focus = 10.;
+    let aperture = 0.0;
+
+    let camera = PerspectiveCamera::new(
+        lookfrom,
+        lookat,
+        rotation,
+        vfov,
+        settings.aspect_ratio,
+        aperture,
+        dist_to_focus,
+        settings.animation_start_time,
+        settings.animation_end_time,
+    );
+
+    // --------World---------
+    //Materials
+    let material_ground = Arc::new(Material::Labmertian(MatLabmertian {
+        albedo: Arc::new(Texture::Checker(CheckerTexture::new(
+            Arc::new(Texture::SolidColor(SolidColorTexture::new(0.2, 0.2, 0.2))),
+            Arc::new(Texture::SolidColor(SolidColorTexture::new(0.8, 0.8, 0.8))),
+        ))),
+    }));
+    let material_gold = Arc::new(Material::Conductor(MatConductor::gold(0.3)));
+    let material_copper = Arc::new(Material::Conductor(MatConductor::copper(0.05)));
+    let material_aluminium = Arc::new(Material::Conductor(MatConductor::aluminium(0.6)));
+    let material_frosted_glass = Arc::new(Material::RoughDielectric(MatRoughDielectric::new(
+        1.5, 0.25,
+    )));
+
+    // Objects
+    let objects: Vec<Arc<dyn HittableObject + Send + Sync>> = vec![
+        Arc::new(Sphere::new(
+            Vec3::new(0., -1000., 0.),
+            1000.,
+            material_ground,
+        )),
+        Arc::new(Sphere::new(Vec3::new(0., 1., -3.3), 1., material_gold)),
+        Arc::new(Sphere::new(Vec3::new(0., 1., -1.1), 1., material_copper)),
+        Arc::new(Sphere::new(Vec3::new(0., 1., 1.1), 1., material_aluminium)),
+        Arc::new(Sphere::new(
+            Vec3::new(0., 1., 3.3),
+            1.,
+            material_frosted_glass,
+        )),
+    ];
+    let world = HittableList::new(objects);
+    // ---------
+
+    let mut render = Renderer::init(
+        Box::new(camera),
+        settings.samples_per_pixel,
+        settings.max_ray_bounces,
+        Box::new(world),
+    );
+    render.set_sky(PhysicalSky::from_angles(30., 40., 3.));
+    render
+}
+
+pub fn principled_scene(settings: &GlobalSettings) -> Renderer {
+    // Camera
+    let lookfrom = Vec3::new(13., 3., 3.);
+    let lookat = Vec3::new(0., 1., 0.);
+    let rotation = Vec3::new(0., 1., 0.);
+    let vfov = 30.0;
+    let dist_to_focus = 10.;
+    let aperture = 0.0;
+
+    let camera = PerspectiveCamera::new(
+        lookfrom,
+        lookat,
+        rotation,
+        vfov,
+        settings.aspect_ratio,
+        aperture,
+        dist_to_focus,
+        settings.animation_start_time,
+        settings.animation_end_time,
+    );
+
+    // --------World---------
+    //Materials
+    let solid = |r, g, b| Arc::new(Texture::SolidColor(SolidColorTexture::new(r, g, b)));
+    let material_ground = Arc::new(Material::Labmertian(MatLabmertian {
+        albedo: solid(0.5, 0.5, 0.5),
+    }));
+
+    let mut coated_plastic = MatPrincipled::new(solid(0.8, 0.1, 0.1));
+    coated_plastic.roughness = 0.6.into();
+    coated_plastic.clearcoat = 1.0.into();
+
+    let mut brushed_metal = MatPrincipled::new(solid(0.9, 0.6, 0.3));
+    brushed_metal.metallic = 1.0.into();
+    brushed_metal.anisotropy = 0.9.into();
+    // Polished stripes on rough metal
+    brushed_metal.roughness = ScalarInput::Texture(Arc::new(Texture::Checker(CheckerTexture::uv(
+        solid(0.1, 0.1, 0.1),
+        solid(0.5, 0.5, 0.5),
+        8.,
+    ))));
+
+    let mut glass = MatPrincipled::new(solid(0.9, 0.95, 1.0));
+    glass.transmission = 1.0.into();
+    glass.roughness = 0.1.into();
+
+    let mut velvet = MatPrincipled::new(solid(0.2, 0.2, 0.6));
+    velvet.sheen = 1.0.into();
+    velvet.roughness = 1.0.into();
+
+    // Objects
+    let objects: Vec<Arc<dyn HittableObject + Send + Sync>> = vec![
+        Arc::new(Sphere::new(
+            Vec3::new(0., -1000., 0.),
+            1000.,
+            material_ground,
+        )),
+        Arc::new(Sphere::new(
+            Vec3::new(0., 1., -3.3),
+            1.,
+            Arc::new(Material::Principled(coated_plastic)),
+        )),
+        Arc::new(Sphere::new(
+            Vec3::new(0., 1., -1.1),
+            1.,
+            Arc::new(Material::Principled(brushed_metal)),
+        )),
+        Arc::new(Sphere::new(
+            Vec3::new(0., 1., 1.1),
+            1.,
+            Arc::new(Material::Principled(glass)),
+        )),
+        Arc::new(Sphere::new(
+            Vec3::new(0., 1., 3.3),
+            1.,
+            Arc::new(Material::Principled(velvet)),
+        )),
+    ];
+    let world = HittableList::new(objects);
+    // ---------
+
+    let mut render = Renderer::init(
+        Box::new(camera),
+        settings.samples_per_pixel,
+        settings.max_ray_bounces,
+        Box::new(world),
+    );
+    render.set_sky(PhysicalSky::from_angles(30., 40., 3.));
+    render
+}
+
+pub fn layered_materials_scene(settings: &GlobalSettings) -> Renderer {
+    // Camera
+    let lookfrom = Vec3::new(13., 3., 3.);
+    let lookat = Vec3::new(0., 1., 0.);
+    let rotation = Vec3::new(0., 1., 0.);
+    let vfov = 30.0;
+    let dist_to_focus = 10.;
+    let aperture = 0.0;
+
+    let camera = PerspectiveCamera::new(
+        lookfrom,
+        lookat,
+        rotation,
+        vfov,
+        settings.aspect_ratio,
+        aperture,
+        dist_to_focus,
+        settings.animation_start_time,
+        settings.animation_end_time,
+    );
+
+    // --------World---------
+    //Materials
+    let solid = |r, g, b| Arc::new(Texture::SolidColor(SolidColorTexture::new(r, g, b)));
+    let material_ground = Arc::new(Material::Labmertian(MatLabmertian {
+        albedo: solid(0.5, 0.5, 0.5),
+    }));
+    let material_dust = Arc::new(Material::Labmertian(MatLabmertian {
+        albedo: solid(0.6, 0.55, 0.5),
+    }));
+    let material_red = Arc::new(Material::Labmertian(MatLabmertian {
+        albedo: solid(0.7, 0.1, 0.1),
+    }));
+    let material_copper = Arc::new(Material::Conductor(MatConductor::copper(0.2)));
+    let material_gold = Arc::new(Material::Conductor(MatConductor::gold(0.5)));
+
+    let material_dusty_copper = Arc::new(Material::Mix(MatMix::new(
+        material_copper,
+        material_dust,
+        ScalarInput::Texture(Arc::new(Texture::Noise(NoiseTexture::new(4.)))),
+    )));
+    let material_lacquered = Arc::new(Material::Coated(MatCoated::new(material_red, 1.5, 0.)));
+    let material_varnished_gold =
+        Arc::new(Material::Coated(MatCoated::new(material_gold, 1.5, 0.05)));
+
+    // Objects
+    let objects: Vec<Arc<dyn HittableObject + Send + Sync>> = vec![
+        Arc::new(Sphere::new(
+            Vec3::new(0., -1000., 0.),
+            1000.,
+            material_ground,
+        )),
+        Arc::new(Sphere::new(
+            Vec3::new(0., 1., -2.2),
+            1.,
+            material_dusty_copper,
+        )),
+        Arc::new(Sphere::new(Vec3::new(0., 1., 0.), 1., material_lacquered)),
+        Arc::new(Sphere::new(
+            Vec3::new(0., 1., 2.2),
+            1.,
+            material_varnished_gold,
+        )),
+    ];
+    let world = HittableList::new(objects);
+    // ---------
+
+    let mut render = Renderer::init(
+        Box::new(camera),
+        settings.samples_per_pixel,
+        settings.max_ray_bounces,
+        Box::new(world),
+    );
+    render.set_sky(PhysicalSky::from_angles(30., 40., 3.));
+    render
+}
+
+pub fn surface_detail_scene(settings: &GlobalSettings) -> Renderer {
+    // Camera
+    let lookfrom = Vec3::new(13., 3., 3.);
+    let lookat = Vec3::new(0., 1., 0.);
+    let rotation = Vec3::new(0., 1., 0.);
+    let vfov = 25.0;
+    let dist_to_focus = 10.;
+    let aperture = 0.0;
+
+    let camera = PerspectiveCamera::new(
+        lookfrom,
+        lookat,
+        rotation,
+        vfov,
+        settings.aspect_ratio,
+        aperture,
+        dist_to_focus,
+        settings.animation_start_time,
+        settings.animation_end_time,
+    );
+
+    // --------World---------
+    //Materials
+    let material_ground = Arc::new(Material::Labmertian(MatLabmertian {
+        albedo: Arc::new(Texture::SolidColor(SolidColorTexture::new(0.5, 0.5, 0.5))),
+    }));
+
+    // Dents of hammered metal
+    let dents = Arc::new(Texture::Voronoi(VoronoiTexture::new(
+        6.,
+        VoronoiMode::Distance,
+    )));
+    let material_hammered_copper = Arc::new(Material::BumpMap(MatBumpMap::new(
+        Arc::new(Material::Conductor(MatConductor::copper(0.15))),
+        dents,
+        0.03,
+    )));
+
+    let mut path = std::env::current_dir().unwrap();
+    path.push("images");
+    path.push("normal_map.png");
+    let normals = Arc::new(Texture::Image(
+        ImageTexture::load(path, ColorSpace::Linear).unwrap(),
+    ));
+    let material_embossed = Arc::new(Material::NormalMap(MatNormalMap::new(
+        Arc::new(Material::Labmertian(MatLabmertian {
+            albedo: Arc::new(Texture::SolidColor(SolidColorTexture::new(0.7, 0.6, 0.5))),
+        })),
+        normals,
+    )));
+
+    // Objects
+    let objects: Vec<Arc<dyn HittableObject + Send + Sync>> = vec![
+        Arc::new(Sphere::new(
+            Vec3::new(0., -1000., 0.),
+            1000.,
+            material_ground,
+        )),
+        Arc::new(Sphere::new(
+            Vec3::new(0., 1., -1.1),
+            1.,
+            material_hammered_copper,
+        )),
+        Arc::new(Sphere::new(Vec3::new(0., 1., 1.1), 1., material_embossed)),
+    ];
+    let world = HittableList::new(objects);
+    // ---------
+
+    let mut render = Renderer::init(
+        Box::new(camera),
+        settings.samples_per_pixel,
+        settings.max_ray_bounces,
+        Box::new(world),
+    );
+    render.set_sky(PhysicalSky::from_angles(30., 40., 3.));
+    render
+}
+
+pub fn emissive_scene(settings: &GlobalSettings) -> Renderer {
+    // Camera
+    let lookfrom = Vec3::new(13., 3., 3.);
+    let lookat = Vec3::new(0., 1., 0.);
+    let rotation = Vec3::new(0., 1., 0.);
+    let vfov = 25.0;
+    let dist_to_focus = 10.;
+    let aperture = 0.0;
+
+    let camera = PerspectiveCamera::new(
+        lookfrom,
+        lookat,
+        rotation,
+        vfov,
+        settings.aspect_ratio,
+        aperture,
+        dist_to_focus,
+        settings.animation_start_time,
+        settings.animation_end_time,
+    );
+
+    // --------World---------
+    //Materials
+    let material_ground = Arc::new(Material::Labmertian(MatLabmertian {
+        albedo: Arc::new(Texture::SolidColor(SolidColorTexture::new(0.5, 0.5, 0.5))),
+    }));
+    let dark_plastic = Arc::new(Material::Labmertian(MatLabmertian {
+        albedo: Arc::new(Texture::SolidColor(SolidColorTexture::new(
+            0.05, 0.05, 0.05,
+        ))),
+    }));
+
+    // Screen glows only from the front, back side stays dark plastic
+    let mut screen = MatEmissive::new(
+        dark_plastic,
+        Arc::new(Texture::Checker(CheckerTexture::new(
+            Arc::new(Texture::SolidColor(SolidColorTexture::new(0.2, 0.4, 1.))),
+            Arc::new(Texture::SolidColor(SolidColorTexture::new(1., 0.9, 0.7))),
+        ))),
+        1.,
+    );
+    screen.one_sided = true;
+
+    let lamp = MatEmissive::new(
+        Arc::new(Material::Labmertian(MatLabmertian {
+            albedo: Arc::new(Texture::SolidColor(SolidColorTexture::new(0.8, 0.8, 0.8))),
+        })),
+        Arc::new(Texture::SolidColor(SolidColorTexture::new(1., 0.6, 0.3))),
+        20.,
+    );
+
+    // Objects
+    let screen = Arc::new(PlaneX::new(
+        0.,
+        0.2,
+        -1.5,
+        2.,
+        3.,
+        Arc::new(Material::Emissive(screen)),
+    ));
+    let lamp = Arc::new(Sphere::new(
+        Vec3::new(3., 0.3, -1.5),
+        0.3,
+        Arc::new(Material::Emissive(lamp)),
+    ));
+    let objects: Vec<Arc<dyn HittableObject + Send + Sync>> = vec![
+        Arc::new(Sphere::new(
+            Vec3::new(0., -1000., 0.),
+            1000.,
+            material_ground,
+        )),
+        Arc::new(Sphere::new(
+            Vec3::new(2., 0.7, 1.5),
+            0.7,
+            Arc::new(Material::Conductor(MatConductor::silver(0.2))),
+        )),
+        screen.clone(),
+        lamp.clone(),
+    ];
+    let world = HittableList::new(objects);
+    // ---------
+
+    let mut render = Renderer::init(
+        Box::new(camera),
+        settings.samples_per_pixel,
+        settings.max_ray_bounces,
+        Box::new(world),
+    );
+    render.lights = vec![
+        Light::Area(AreaLight::new(screen)),
+        Light::Area(AreaLight::new(lamp)),
+    ];
+    render
+}
+
+pub fn absorption_scene(settings: &GlobalSettings) -> Renderer {
+    // Camera
+    let lookfrom = Vec3::new(13., 3., 3.);
+    let lookat = Vec3::new(0., 1., 0.);
+    let rotation = Vec3::new(0., 1., 0.);
+    let vfov = 25.0;
+    let dist_to_focus = 10.;
+    let aperture = 0.0;
+
+    let camera = PerspectiveCamera::new(
+        lookfrom,
+        lookat,
+        rotation,
+        vfov,
+        settings.aspect_ratio,
+        aperture,
+        dist_to_focus,
+        settings.animation_start_time,
+        settings.animation_end_time,
+    );
+
+    // --------World---------
+    //Materials
+    let material_ground = Arc::new(Material::Labmertian(MatLabmertian {
+        albedo: Arc::new(Texture::Checker(CheckerTexture::new(
+            Arc::new(Texture::SolidColor(SolidColorTexture::new(0.2, 0.3, 0.1))),
+            Arc::new(Texture::SolidColor(SolidColorTexture::new(0.9, 0.9, 0.9))),
+        ))),
+    }));
+
+    // Thicker parts of colored glass are darker
+    let mut green_glass = MatDielectric::new(1.5);
+    green_glass.absorption = Medium::absorption_from_color(&Vec3::new(0.3, 0.8, 0.4), 1.);
+    let mut tinted_glass = MatDielectric::new(1.5);
+    tinted_glass.tint = Vec3::new(0.9, 0.6, 0.5);
+
+    // Ice floating in water fills the overlapping volume
+    let mut water = MatDielectric::new(1.33);
+    water.absorption = Medium::absorption_from_color(&Vec3::new(0.7, 0.9, 0.95), 1.);
+    let mut ice = MatDielectric::new(1.31);
+    ice.priority = 1;
+
+    // Objects
+    let objects: Vec<Arc<dyn HittableObject + Send + Sync>> = vec![
+        Arc::new(Sphere::new(
+            Vec3::new(0., -1000., 0.),
+            1000.,
+            material_ground,
+        )),
+        Arc::new(Sphere::new(
+            Vec3::new(0., 1., -2.2),
+            1.,
+            Arc::new(Material::Dielectric(green_glass)),
+        )),
+        Arc::new(Sphere::new(
+            Vec3::new(0., 1., 0.),
+            1.,
+            Arc::new(Material::Dielectric(tinted_glass)),
+        )),
+        Arc::new(Sphere::new(
+            Vec3::new(0., 1., 2.2),
+            1.,
+            Arc::new(Material::Dielectric(water)),
+        )),
+        Arc::new(Sphere::new(
+            Vec3::new(0.3, 1.6, 2.2),
+            0.5,
+            Arc::new(Material::Dielectric(ice)),
+        )),
+    ];
+    let world = HittableList::new(objects);
+    // ---------
+
+    let mut render = Renderer::init(
+        Box::new(camera),
+        settings.samples_per_pixel,
+        settings.max_ray_bounces,
+        Box::new(world),
+    );
+    render.set_sky(PhysicalSky::from_angles(30., 40., 3.));
+    render
+}
+
+pub fn dispersion_scene(settings: &GlobalSettings) -> Renderer {
+    // Camera
+    let lookfrom = Vec3::new(13., 3., 3.);
+    let lookat = Vec3::new(0., 1., 0.);
+    let rotation = Vec3::new(0., 1., 0.);
+    let vfov = 25.0;
+    let dist_to_focus = 10.;
+    let aperture = 0.0;
+
+    let camera = PerspectiveCamera::new(
+        lookfrom,
+        lookat,
+        rotation,
+        vfov,
+        settings.aspect_ratio,
+        aperture,
+        dist_to_focus,
+        settings.animation_start_time,
+        settings.animation_end_time,
+    );
+
+    // --------World---------
+    //Materials
+    // Sharp edges seen through the glass show colored fringes
+    let material_ground = Arc::new(Material::Labmertian(MatLabmertian {
+        albedo: Arc::new(Texture::Checker(CheckerTexture::new(
+            Arc::new(Texture::SolidColor(SolidColorTexture::new(
+                0.05, 0.05, 0.05,
+            ))),
+            Arc::new(Texture::SolidColor(SolidColorTexture::new(0.9, 0.9, 0.9))),
+        ))),
+    }));
+    let lamp = Arc::new(Material::DiffuseLight(MatDiffuseLight {
+        emit: Arc::new(Texture::SolidColor(SolidColorTexture::new(10., 10., 10.))),
+    }));
+
+    let mut diamond = MatDielectric::new(2.42);
+    diamond.dispersion = Some(Dispersion::DIAMOND);
+    // Glass with exaggerated dispersion
+    let mut flint = MatDielectric::new(1.6);
+    flint.dispersion = Some(Dispersion::Cauchy { a: 1.5, b: 0.04 });
+
+    // Objects
+    let lamp = Arc::new(Sphere::new(Vec3::new(-3., 7., 0.), 1.5, lamp));
+    let objects: Vec<Arc<dyn HittableObject + Send + Sync>> = vec![
+        Arc::new(Sphere::new(
+            Vec3::new(0., -1000., 0.),
+            1000.,
+            material_ground,
+        )),
+        Arc::new(Sphere::new(
+            Vec3::new(0., 1., -1.1),
+            1.,
+            Arc::new(Material::Dielectric(diamond)),
+        )),
+        Arc::new(Sphere::new(
+            Vec3::new(0., 1., 1.1),
+            1.,
+            Arc::new(Material::Dielectric(flint)),
+        )),
+        lamp.clone(),
+    ];
+    let world = HittableList::new(objects);
+    // ---------
+
+    let mut render = Renderer::init(
+        Box::new(camera),
+        settings.samples_per_pixel,
+        settings.max_ray_bounces,
+        Box::new(world),
+    );
+    render.background = Background::SolidColor(Vec3::new(0.1, 0.1, 0.12));
+    render.lights = vec![Light::Area(AreaLight::new(lamp))];
+    render.spectral = true;
+    render
+}
+
+pub fn thin_film_scene(settings: &GlobalSettings) -> Renderer {
+    // Camera
+    let lookfrom = Vec3::new(13., 3., 3.);
+    let lookat = Vec3::new(0., 1., 0.);
+    let rotation = Vec3::new(0., 1., 0.);
+    let vfov = 25.0;
+    let dist_to_focus = 10.;
+    let aperture = 0.0;
+
+    let camera = PerspectiveCamera::new(
+        lookfrom,
+        lookat,
+        rotation,
+        vfov,
+        settings.aspect_ratio,
+        aperture,
+        dist_to_focus,
+        settings.animation_start_time,
+        settings.animation_end_time,
+    );
+
+    // --------World---------
+    //Materials
+    let material_ground = Arc::new(Material::Labmertian(MatLabmertian {
+        albedo: Arc::new(Texture::SolidColor(SolidColorTexture::new(0.3, 0.3, 0.3))),
+    }));
+
+    // Soap film of varying thickness around air
+    let mut thickness = NoiseTexture::new(2.);
+    thickness.ramp = ColorRamp::linear(Vec3::new(200., 200., 200.), Vec3::new(800., 800., 800.));
+    let mut bubble = MatDielectric::new(1.);
+    bubble.thin_film = Some(ThinFilm {
+        thickness: ScalarInput::Texture(Arc::new(Texture::Noise(thickness))),
+        refraction_index: 1.33,
+    });
+
+    // Oxide layer of heated metal
+    let mut tempered_steel = MatConductor::new(
+        Vec3::new(2.87, 2.92, 2.54),
+        Vec3::new(3.18, 2.93, 2.75),
+        0.2,
+    );
+    tempered_steel.thin_film = Some(ThinFilm::new(300., 2.4));
+
+    // Objects
+    let objects: Vec<Arc<dyn HittableObject + Send + Sync>> = vec![
+        Arc::new(Sphere::new(
+            Vec3::new(0., -1000., 0.),
+            1000.,
+            material_ground,
+        )),
+        Arc::new(Sphere::new(
+            Vec3::new(0., 1., -1.1),
+            1.,
+            Arc::new(Material::Conductor(tempered_steel)),
+        )),
+        Arc::new(Sphere::new(
+            Vec3::new(0., 1., 1.1),
+            1.,
+            Arc::new(Material::Dielectric(bubble)),
+        )),
+    ];
+    let world = HittableList::new(objects);
+    // ---------
+
+    let mut render = Renderer::init(
+        Box::new(camera),
+        settings.samples_per_pixel,
+        settings.max_ray_bounces,
+        Box::new(world),
+    );
+    render.set_sky(PhysicalSky::from_angles(30., 40., 3.));
+    render
+}
+
+pub fn subsurface_scene(settings: &GlobalSettings) -> Renderer {
+    // Camera
+    let lookfrom = Vec3::new(13., 3., 3.);
+    let lookat = Vec3::new(0., 1., 0.);
+    let rotation = Vec3::new(0., 1., 0.);
+    let vfov = 25.0;
+    let dist_to_focus = 10.;
+    let aperture = 0.0;
+
+    let camera = PerspectiveCamera::new(
+        lookfrom,
+        lookat,
+        rotation,
+        vfov,
+        settings.aspect_ratio,
+        aperture,
+        dist_to_focus,
+        settings.animation_start_time,
+        settings.animation_end_time,
+    );
+
+    // --------World---------
+    //Materials
+    let material_ground = Arc::new(Material::Labmertian(MatLabmertian {
+        albedo: Arc::new(Texture::SolidColor(SolidColorTexture::new(0.5, 0.5, 0.5))),
+    }));
+    let lamp = Arc::new(Material::DiffuseLight(MatDiffuseLight {
+        emit: Arc::new(Texture::SolidColor(SolidColorTexture::new(8., 7., 6.))),
+    }));
+
+    // Red light travels deeper into the skin
+    let skin = MatSubsurface::new(Vec3::new(0.85, 0.6, 0.5), Vec3::new(0.2, 0.07, 0.04));
+    let wax = MatSubsurface::new(Vec3::new(0.9, 0.8, 0.5), Vec3::new(0.3, 0.25, 0.15));
+    let marble = MatSubsurface::new(Vec3::new(0.95, 0.95, 0.93), Vec3::new(0.05, 0.05, 0.04));
+
+    // Objects
+    let lamp = Arc::new(Sphere::new(Vec3::new(-1., 4., -4.), 1., lamp));
+    let objects: Vec<Arc<dyn HittableObject + Send + Sync>> = vec![
+        Arc::new(Sphere::new(
+            Vec3::new(0., -1000., 0.),
+            1000.,
+            material_ground,
+        )),
+        Arc::new(Sphere::new(
+            Vec3::new(0., 0.8, -2.),
+            0.8,
+            Arc::new(Material::Subsurface(skin)),
+        )),
+        Arc::new(Sphere::new(
+            Vec3::new(0., 0.8, 0.),
+            0.8,
+            Arc::new(Material::Subsurface(wax)),
+        )),
+        Arc::new(Sphere::new(
+            Vec3::new(0., 0.8, 2.),
+            0.8,
+            Arc::new(Material::Subsurface(marble)),
+        )),
+        lamp.clone(),
+    ];
+    let world = HittableList::new(objects);
+    // ---------
+
+    let mut render = Renderer::init(
+        Box::new(camera),
+        settings.samples_per_pixel,
+        settings.max_ray_bounces,
+        Box::new(world),
+    );
+    render.background = Background::SolidColor(Vec3::new(0.05, 0.06, 0.08));
+    render.lights = vec![Light::Area(AreaLight::new(lamp))];
+    render
+}
+
+pub fn rough_diffuse_scene(settings: &GlobalSettings) -> Renderer {
+    // Camera
+    let lookfrom = Vec3::new(13., 3., 3.);
+    let lookat = Vec3::new(0., 1., 0.);
+    let rotation = Vec3::new(0., 1., 0.);
+    let vfov = 25.0;
+    let dist_to_focus = 10.;
+    let aperture = 0.0;
+
+    let camera = PerspectiveCamera::new(
+        lookfrom,
+        lookat,
+        rotation,
+        vfov,
+        settings.aspect_ratio,
+        aperture,
+        dist_to_focus,
+        settings.animation_start_time,
+        settings.animation_end_time,
+    );
+
+    // --------World---------
+    //Materials
+    let clay_color = Arc::new(Texture::SolidColor(SolidColorTexture::new(0.7, 0.4, 0.3)));
+    let lambertian = Arc::new(Material::Labmertian(MatLabmertian {
+        albedo: clay_color.clone(),
+    }));
+    let clay = Arc::new(Material::OrenNayar(MatOrenNayar::new(clay_color, 0.8)));
+    // Weathered concrete with patches of different roughness
+    let mut concrete = MatOrenNayar::new(
+        Arc::new(Texture::SolidColor(SolidColorTexture::new(0.5, 0.5, 0.48))),
+        0.,
+    );
+    concrete.roughness = ScalarInput::Texture(Arc::new(Texture::Noise(NoiseTexture::new(4.))));
+    let velvet = Arc::new(Material::Sheen(MatSheen::new(
+        Arc::new(Texture::SolidColor(SolidColorTexture::new(0.3, 0.02, 0.05))),
+        Arc::new(Texture::SolidColor(SolidColorTexture::new(1., 0.6, 0.7))),
+        0.3,
+    )));
+
+    // Objects
+    let objects: Vec<Arc<dyn HittableObject + Send + Sync>> = vec![
+        Arc::new(Sphere::new(
+            Vec3::new(0., -1000., 0.),
+            1000.,
+            Arc::new(Material::OrenNayar(concrete)),
+        )),
+        Arc::new(Sphere::new(Vec3::new(0., 0.8, -2.), 0.8, lambertian)),
+        Arc::new(Sphere::new(Vec3::new(0., 0.8, 0.), 0.8, clay)),
+        Arc::new(Sphere::new(Vec3::new(0., 0.8, 2.), 0.8, velvet)),
+    ];
+    let world = HittableList::new(objects);
+    // ---------
+
+    let mut render = Renderer::init(
+        Box::new(camera),
+        settings.samples_per_pixel,
+        settings.max_ray_bounces,
+        Box::new(world),
+    );
+    render.background = Background::SolidColor(Vec3::new(0.1, 0.1, 0.12));
+    // Light from behind the camera shows flattening of rough surfaces
+    render.lights = vec![Light::Directional(DirectionalLight::new(
+        Vec3::new(-1., -0.5, -0.2),
+        Vec3::new(3., 3., 3.),
+    ))];
+    render
+}
+
+pub fn brushed_metal_scene(settings: &GlobalSettings) -> Renderer {
+    // Camera
+    let lookfrom = Vec3::new(13., 3., 3.);
+    let lookat = Vec3::new(0., 1., 0.);
+    let rotation = Vec3::new(0., 1., 0.);
+    let vfov = 25.0;
+    let dist_to_focus = 10.;
+    let aperture = 0.0;
+
+    let camera = PerspectiveCamera::new(
+        lookfrom,
+        lookat,
+        rotation,
+        vfov,
+        settings.aspect_ratio,
+        aperture,
+        dist_to_focus,
+        settings.animation_start_time,
+        settings.animation_end_time,
+    );
+
+    // --------World---------
+    //Materials
+    let material_ground = Arc::new(Material::Labmertian(MatLabmertian {
+        albedo: Arc::new(Texture::Checker(CheckerTexture::new(
+            Arc::new(Texture::SolidColor(SolidColorTexture::new(0.2, 0.3, 0.1))),
+            Arc::new(Texture::SolidColor(SolidColorTexture::new(0.9, 0.9, 0.9))),
+        ))),
+    }));
+    let lamp = Arc::new(Material::DiffuseLight(MatDiffuseLight {
+        emit: Arc::new(Texture::SolidColor(SolidColorTexture::new(8., 8., 8.))),
+    }));
+
+    // Brushed around the sphere, along its parallels
+    let lathe = MatConductor::aluminium(0.).anisotropic(0.5, 0.1);
+    // Brushed from pole to pole
+    let mut meridian = MatConductor::aluminium(0.).anisotropic(0.5, 0.1);
+    meridian.flow_map = Some(Arc::new(Texture::SolidColor(SolidColorTexture::new(
+        0.5, 1., 0.5,
+    ))));
+    // Copper brushed diagonally
+    let mut diagonal = MatConductor::copper(0.).anisotropic(0.4, 0.05);
+    diagonal.flow_map = Some(Arc::new(Texture::SolidColor(SolidColorTexture::new(
+        1., 1., 0.5,
+    ))));
+
+    // Objects
+    let lamp = Arc::new(Sphere::new(Vec3::new(2., 5., -3.), 1., lamp));
+    let objects: Vec<Arc<dyn HittableObject + Send + Sync>> = vec![
+        Arc::new(Sphere::new(
+            Vec3::new(0., -1000., 0.),
+            1000.,
+            material_ground,
+        )),
+        Arc::new(Sphere::new(
+            Vec3::new(0., 0.8, -2.),
+            0.8,
+            Arc::new(Material::Conductor(lathe)),
+        )),
+        Arc::new(Sphere::new(
+            Vec3::new(0., 0.8, 0.),
+            0.8,
+            Arc::new(Material::Conductor(meridian)),
+        )),
+        Arc::new(Sphere::new(
+            Vec3::new(0., 0.8, 2.),
+            0.8,
+            Arc::new(Material::Conductor(diagonal)),
+        )),
+        lamp.clone(),
+    ];
+    let world = HittableList::new(objects);
+    // ---------
+
+    let mut render = Renderer::init(
+        Box::new(camera),
+        settings.samples_per_pixel,
+        settings.max_ray_bounces,
+        Box::new(world),
+    );
+    render.background = Background::SolidColor(Vec3::new(0.3, 0.35, 0.4));
+    render.lights = vec![Light::Area(AreaLight::new(lamp))];
+    render
+}
+
+pub fn orthographic_scene(settings: &GlobalSettings) -> Renderer {
+    // Isometric camera
+    let lookfrom = Vec3::new(20., 20., 20.);
+    let lookat = Vec3::new(0., 0., 0.);
+    let rotation = Vec3::new(0., 1., 0.);
+    let view_height = 12.;
+
+    let camera = OrthographicCamera::new(
+        lookfrom,
+        lookat,
+        rotation,
+        view_height,
+        settings.aspect_ratio,
+        settings.animation_start_time,
+        settings.animation_end_time,
+    );
+
+    // --------World---------
+    //Materials
+    let solid = |r, g, b| Arc::new(Texture::SolidColor(SolidColorTexture::new(r, g, b)));
+    let material_ground = Arc::new(Material::Labmertian(MatLabmertian {
+        albedo: solid(0.6, 0.6, 0.6),
+    }));
+    let concrete = Arc::new(Material::OrenNayar(MatOrenNayar::new(
+        solid(0.75, 0.73, 0.7),
+        0.5,
+    )));
+    let glass = Arc::new(Material::Dielectric(MatDielectric::new(1.5)));
+
+    // Objects, blocks of buildings along a street
+    let mut objects: Vec<Arc<dyn HittableObject + Send + Sync>> = vec![Arc::new(Sphere::new(
+        Vec3::new(0., -1000., 0.),
+        1000.,
+        material_ground,
+    ))];
+    let heights = [3., 1.5, 4.5, 2., 1., 3.5];
+    for (i, height) in heights.iter().enumerate() {
+        let x = -5. + 2. * i as f32;
+        objects.push(Arc::new(Cube::new(
+            Vec3::new(x, 0., -3.),
+            Vec3::new(x + 1.5, *height, -1.),
+            concrete.clone(),
+        )));
+        objects.push(Arc::new(Cube::new(
+            Vec3::new(x, 0., 1.),
+            Vec3::new(x + 1.5, 5.5 - height, 3.),
+            concrete.clone(),
+        )));
+    }
+    objects.push(Arc::new(Sphere::new(Vec3::new(0., 0.5, 0.), 0.5, glass)));
+    let world = HittableList::new(objects);
+    // ---------
+
+    let mut render = Renderer::init(
+        Box::new(camera),
+        settings.samples_per_pixel,
+        settings.max_ray_bounces,
+        Box::new(world),
+    );
+    render.set_sky(PhysicalSky::from_angles(50., 60., 3.));
+    render
+}
+
+/// Spheres around the origin under the sky, for cameras seeing in all directions
+fn panorama_world() -> HittableList {
+    let solid = |r, g, b| Arc::new(Texture::SolidColor(SolidColorTexture::new(r, g, b)));
+    let material_ground = Arc::new(Material::Labmertian(MatLabmertian {
+        albedo: Arc::new(Texture::Checker(CheckerTexture::new(
+            solid(0.2, 0.3, 0.1),
+            solid(0.9, 0.9, 0.9),
+        ))),
+    }));
+
+    let mut objects: Vec<Arc<dyn HittableObject + Send + Sync>> = vec![Arc::new(Sphere::new(
+        Vec3::new(0., -1000., 0.),
+        1000.,
+        material_ground,
+    ))];
+    // Ring of spheres with hue changing around the viewer
+    let count = 12;
+    for i in 0..count {
+        let angle = 2. * std::f32::consts::PI * i as f32 / count as f32;
+        let color = Vec3::new(
+            0.5 + 0.4 * angle.cos(),
+            0.5 + 0.4 * (angle + 2.1).cos(),
+            0.5 + 0.4 * (angle + 4.2).cos(),
+        );
+        let material = if i % 3 == 0 {
+            Arc::new(Material::Conductor(MatConductor::silver(0.1)))
+        } else {
+            Arc::new(Material::Labmertian(MatLabmertian {
+                albedo: solid(color.x(), color.y(), color.z()),
+            }))
+        };
+        objects.push(Arc::new(Sphere::new(
+            Vec3::new(5. * angle.cos(), 1., 5. * angle.sin()),
+            1.,
+            material,
+        )));
+    }
+    HittableList::new(objects)
+}
+
+pub fn panorama_scene(settings: &mut GlobalSettings) -> Renderer {
+    // Latitude-longitude image for VR viewers
+    settings.aspect_ratio = 2.;
+    settings.height = settings.width / 2;
+
+    let camera = EquirectangularCamera::new(
+        Vec3::new(0., 1.5, 0.),
+        Vec3::new(1., 1.5, 0.),
+        Vec3::new(0., 1., 0.),
+        settings.animation_start_time,
+        settings.animation_end_time,
+    );
+
+    let mut render = Renderer::init(
+        Box::new(camera),
+        settings.samples_per_pixel,
+        settings.max_ray_bounces,
+        Box::new(panorama_world()),
+    );
+    render.set_sky(PhysicalSky::from_angles(40., 30., 3.));
+    render
+}
+
+pub fn fisheye_scene(settings: &GlobalSettings) -> Renderer {
+    // Lens looking up, sees the whole ring of spheres at the image circle
+    let camera = FisheyeCamera::new(
+        Vec3::new(0., 0.5, 0.),
+        Vec3::new(0., 10., 0.),
+        Vec3::new(1., 0., 0.),
+        FisheyeProjection::Equisolid,
+        200.,
+        settings.aspect_ratio,
+        settings.animation_start_time,
+        settings.animation_end_time,
+    );
+
+    let mut render = Renderer::init(
+        Box::new(camera),
+        settings.samples_per_pixel,
+        settings.max_ray_bounces,
+        Box::new(panorama_world()),
+    );
+    render.set_sky(PhysicalSky::from_angles(40., 30., 3.));
+    render
+}
+
+pub fn stereo_scene(settings: &mut GlobalSettings) -> Renderer {
+    // Eye images side by side, each with the original aspect ratio
+    let eye_aspect_ratio = settings.aspect_ratio;
+    settings.width *= 2;
+    settings.aspect_ratio *= 2.;
+
+    let camera = StereoCamera::perspective(
+        Vec3::new(0., 2., 12.),
+        Vec3::new(0., 1., 0.),
+        Vec3::new(0., 1., 0.),
+        40.,
+        eye_aspect_ratio,
+        0.3,
+        12.,
+        StereoMode::OffAxis,
+        StereoLayout::SideBySide,
+        settings.animation_start_time,
+        settings.animation_end_time,
+    );
+
+    let mut render = Renderer::init(
+        Box::new(camera),
+        settings.samples_per_pixel,
+        settings.max_ray_bounces,
+        Box::new(panorama_world()),
+    );
+    render.set_sky(PhysicalSky::from_angles(40., 30., 3.));
+    render
+}
+
+pub fn omnidirectional_stereo_scene(settings: &mut GlobalSettings) -> Renderer {
+    // Two latitude-longitude panoramas on top of each other
+    settings.aspect_ratio = 1.;
+    settings.height = settings.width;
+
+    let camera = StereoCamera::omnidirectional(
+        Vec3::new(0., 1.5, 0.),
+        Vec3::new(1., 1.5, 0.),
+        Vec3::new(0., 1., 0.),
+        0.065,
+        StereoLayout::TopBottom,
+        settings.animation_start_time,
+        settings.animation_end_time,
+    );
+
+    let mut render = Renderer::init(
+        Box::new(camera),
+        settings.samples_per_pixel,
+        settings.max_ray_bounces,
+        Box::new(panorama_world()),
+    );
+    render.set_sky(PhysicalSky::from_angles(40., 30., 3.));
+    render
+}
+
+pub fn lens_scene(settings: &GlobalSettings) -> Renderer {
+    // 50mm lens wide open on full frame film, scene units are meters
+    let camera = RealisticCamera::new(
+        Vec3::new(0., 1.5, 15.),
+        Vec3::new(0., 1., 0.),
+        Vec3::new(0., 1., 0.),
+        &DOUBLE_GAUSS_50MM,
+        0.001,
+        43.3,
+        settings.aspect_ratio,
+        17.1,
+        10.,
+        settings.animation_start_time,
+        settings.animation_end_time,
+    )
+    .unwrap();
+
+    let mut render = Renderer::init(
+        Box::new(camera),
+        settings.samples_per_pixel,
+        settings.max_ray_bounces,
+        Box::new(panorama_world()),
+    );
+    render.set_sky(PhysicalSky::from_angles(40., 30., 3.));
+    render
+}
+
+pub fn bokeh_scene(settings: &GlobalSettings) -> Renderer {
+    // Camera focused on the close sphere, lights in the background are out of focus
+    let lookfrom = Vec3::new(0., 1., 6.);
+    let lookat = Vec3::new(0., 1., 0.);
+    let rotation = Vec3::new(0., 1., 0.);
+    let vfov = 30.0;
+    let dist_to_focus = 6.;
+    let aperture = 0.8;
+
+    let camera = PerspectiveCamera::new(
+        lookfrom,
+        lookat,
+        rotation,
+        vfov,
+        settings.aspect_ratio,
+        aperture,
+        dist_to_focus,
+        settings.animation_start_time,
+        settings.animation_end_time,
+    )
+    .with_aperture(Aperture::Polygonal {
+        blades: 6,
+        rotation: 15.,
+    })
+    .with_anamorphic_squeeze(1.5)
+    .with_cats_eye(0.4);
+
+    // --------World---------
+    //Materials
+    let material_ground = Arc::new(Material::Labmertian(MatLabmertian {
+        albedo: Arc::new(Texture::SolidColor(SolidColorTexture::new(0.2, 0.2, 0.2))),
+    }));
+    let material_subject = Arc::new(Material::Conductor(MatConductor::gold(0.2)));
+
+    // Objects
+    let mut objects: Vec<Arc<dyn HittableObject + Send + Sync>> = vec![
+        Arc::new(Sphere::new(
+            Vec3::new(0., -1000., 0.),
+            1000.,
+            material_ground,
+        )),
+        Arc::new(Sphere::new(Vec3::new(0., 1., 0.), 1., material_subject)),
+    ];
+    // Garland of small lamps far behind
+    let mut rng = StdRng::seed_from_u64(11);
+    let mut lights = Vec::new();
+    for i in 0..16 {
+        let color = Vec3::new(rng.gen_range(0.5..1.), rng.gen_range(0.3..0.8), 0.2);
+        let lamp = Arc::new(Material::DiffuseLight(MatDiffuseLight {
+            emit: Arc::new(Texture::SolidColor(SolidColorTexture::new(
+                20. * color.x(),
+                20. * color.y(),
+                20. * color.z(),
+            ))),
+        }));
+        let x = -12. + 1.6 * i as f32;
+        let y = 3. + 1.5 * f32::sin(0.8 * i as f32);
+        let lamp = Arc::new(Sphere::new(Vec3::new(x, y, -20.), 0.1, lamp));
+        lights.push(Light::Area(AreaLight::new(lamp.clone())));
+        objects.push(lamp);
+    }
+    let world = HittableList::new(objects);
+    // ---------
+
+    let mut render = Renderer::init(
+        Box::new(camera),
+        settings.samples_per_pixel,
+        settings.max_ray_bounces,
+        Box::new(world),
+    );
+    render.background = Background::SolidColor(Vec3::new(0.02, 0.02, 0.04));
+    lights.push(Light::Point(PointLight::new(
+        Vec3::new(3., 4., 4.),
+        Vec3::new(30., 30., 30.),
+    )));
+    render.lights = lights;
+    render
+}
+
+pub fn animated_camera_scene(settings: &GlobalSettings) -> Renderer {
+    // Camera circling the ring of spheres for four seconds, zooming in at the end
+    let orbit = (0..=8)
+        .map(|i| {
+            let angle = std::f32::consts::FRAC_PI_4 * i as f32;
+            let height = 3. - 0.25 * i as f32;
+            Keyframe::new(
+                0.5 * i as f32,
+                Vec3::new(10. * angle.sin(), height, 10. * angle.cos()),
+                Interpolation::CatmullRom,
+            )
+        })
+        .collect();
+    let lookat = Track::new(vec![
+        Keyframe::new(0., Vec3::new(0., 0., 0.), Interpolation::Linear),
+        Keyframe::new(4., Vec3::new(0., 1., 0.), Interpolation::Linear),
+    ]);
+    let vfov = Track::new(vec![
+        Keyframe::new(0., 40., Interpolation::Constant),
+        Keyframe::new(3., 40., Interpolation::Bezier),
+        Keyframe::new(4., 25., Interpolation::Bezier),
+    ]);
+
+    let camera = AnimatedCamera::new(
+        Track::new(orbit),
+        lookat,
+        vfov,
+        settings.aspect_ratio,
+        settings.animation_start_time,
+        settings.animation_end_time,
+    )
+    .with_focus(0.1, Track::constant(10.));
+
+    let mut render = Renderer::init(
+        Box::new(camera),
+        settings.samples_per_pixel,
+        settings.max_ray_bounces,
+        Box::new(panorama_world()),
+    );
+    render.set_sky(PhysicalSky::from_angles(40., 30., 3.));
+    render
+}
+
+pub fn transform_motion_scene(settings: &GlobalSettings) -> Renderer {
+    // Camera
+    let lookfrom = Vec3::new(0., 3., 12.);
+    let lookat = Vec3::new(0., 1., 0.);
+    let rotation = Vec3::new(0., 1., 0.);
+    let vfov = 30.0;
+
+    let camera = PerspectiveCamera::new(
+        lookfrom,
+        lookat,
+        rotation,
+        vfov,
+        settings.aspect_ratio,
+        0.,
+        10.,
+        settings.animation_start_time,
+        settings.animation_end_time,
+    );
+
+    // --------World---------
+    let solid = |r, g, b| Arc::new(Texture::SolidColor(SolidColorTexture::new(r, g, b)));
+    let material_ground = Arc::new(Material::Labmertian(MatLabmertian {
+        albedo: Arc::new(Texture::Checker(CheckerTexture::new(
+            solid(0.2, 0.3, 0.1),
+            solid(0.9, 0.9, 0.9),
+        ))),
+    }));
+    // Motion over the first second, still image shows it during the whole shutter
+    let (start, middle, end) = (0., 0.5, 1.);
+    let turn = |time, value| Keyframe::new(time, value, Interpolation::Linear);
+    let unit_cube = |material| {
+        Box::new(Cube::new(
+            Vec3::new(-0.5, -0.5, -0.5),
+            Vec3::new(0.5, 0.5, 0.5),
+            material,
+        ))
+    };
+
+    let mut objects: Vec<Arc<dyn HittableObject + Send + Sync>> = vec![Arc::new(Sphere::new(
+        Vec3::new(0., -1000., 0.),
+        1000.,
+        material_ground,
+    ))];
+    // Cubes spinning faster from left to right, each around a tilted axis
+    for i in 0..4 {
+        let axis = Vec3::new(0.3 * i as f32, 1., 0.2);
+        let angle = 30. * (i + 1) as f32;
+        let material = Arc::new(Material::Labmertian(MatLabmertian {
+            albedo: solid(0.8, 0.3 + 0.15 * i as f32, 0.2),
+        }));
+        objects.push(Arc::new(AnimatedTransform::new(
+            unit_cube(material),
+            Track::constant(Vec3::new(-3. + 2. * i as f32, 0.8, 0.)),
+            Track::new(vec![
+                turn(start, Quaternion::identity()),
+                turn(end, Quaternion::from_axis_angle(&axis, angle)),
+            ]),
+            Track::constant(Vec3::new(1., 1., 1.)),
+        )));
+    }
+    // Ball bouncing on an arc in the back, squashed at the impact
+    let ball = Arc::new(Material::Conductor(MatConductor::gold(0.2)));
+    objects.push(Arc::new(AnimatedTransform::new(
+        Box::new(Sphere::new(Vec3::zero(), 0.6, ball)),
+        Track::new(vec![
+            Keyframe::new(start, Vec3::new(-3., 2.5, -3.), Interpolation::CatmullRom),
+            Keyframe::new(middle, Vec3::new(0., 0.4, -3.), Interpolation::CatmullRom),
+            Keyframe::new(end, Vec3::new(3., 2.5, -3.), Interpolation::CatmullRom),
+        ]),
+        Track::constant(Quaternion::identity()),
+        Track::new(vec![
+            Keyframe::new(start, Vec3::new(1., 1., 1.), Interpolation::Linear),
+            Keyframe::new(middle, Vec3::new(1.3, 0.6, 1.3), Interpolation::Linear),
+            Keyframe::new(end, Vec3::new(1., 1., 1.), Interpolation::Linear),
+        ]),
+    )));
+    let world = BvhNode::new(
+        &objects,
+        settings.animation_start_time,
+        settings.animation_end_time,
+    )
+    .unwrap();
+    // ---------
+
+    let mut render = Renderer::init(
+        Box::new(camera),
+        settings.samples_per_pixel,
+        settings.max_ray_bounces,
+        Box::new(world),
+    );
+    render.set_sky(PhysicalSky::from_angles(40., 30., 3.));
+    render
 }
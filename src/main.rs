@@ -3,6 +3,7 @@ pub mod example_scenes;
 use rust_ray_tracer::{
     math::vec3::Vec3,
     ppm::{color::Color, image::PpmImage},
+    raytracing::renderer::Renderer,
 };
 
 pub struct GlobalSettings {
@@ -11,8 +12,13 @@ pub struct GlobalSettings {
     pub height: usize,
     pub samples_per_pixel: usize,
     pub max_ray_bounces: usize,
+    /// Shutter interval of a single image, frame sequences start at `animation_start_time`
     pub animation_start_time: f32,
     pub animation_end_time: f32,
+    /// Frame rate of rendered frame sequences
+    pub frames_per_second: f32,
+    /// Part of the frame duration the shutter is open
+    pub shutter_fraction: f32,
 }
 
 fn main() {
@@ -27,27 +33,91 @@ fn main() {
         max_ray_bounces: 50,
         animation_start_time: 0.0,
         animation_end_time: 1.0,
+        frames_per_second: 24.0,
+        shutter_fraction: 0.5,
     };
 
-    let renderer = match std::env::args().nth(1).unwrap().as_str() {
-        "1" => example_scenes::test_scene(&settings),
-        "2" => example_scenes::random_scene(&settings),
-        "3" => example_scenes::earth_scene(&settings),
-        "4" => example_scenes::lighting_scene(&settings),
-        "5" => example_scenes::cornell_box(&mut settings),
-        _ => panic!("Unknown scene number"),
+    let scene = std::env::args().nth(1).unwrap();
+
+    // Optional frame range renders a numbered sequence instead of a single image
+    let frame_arg = |i: usize| std::env::args().nth(i).map(|a| a.parse::<usize>().unwrap());
+    let (first, last) = match (frame_arg(2), frame_arg(3)) {
+        (Some(first), Some(last)) => (first, last),
+        (Some(frame), None) => (frame, frame),
+        _ => {
+            let renderer = build_scene(&scene, &mut settings);
+            let image = renderer.render(settings.width, settings.height, true);
+            save_to_ppm("box.ppm", settings.width, settings.height, image);
+            return;
+        }
     };
 
-    let scene = renderer.render(settings.width, settings.height, true);
+    for frame in first..=last {
+        // Scene is built for the shutter interval of each frame, so moving objects
+        // are bounded where they are during it
+        let open = settings.animation_start_time + frame as f32 / settings.frames_per_second;
+        let close = open + settings.shutter_fraction / settings.frames_per_second;
+        let mut frame_settings = GlobalSettings {
+            animation_start_time: open,
+            animation_end_time: close,
+            ..settings
+        };
+        let renderer = build_scene(&scene, &mut frame_settings);
 
-    save_to_ppm("box.ppm", settings.width, settings.height, scene);
+        let image = renderer.render(frame_settings.width, frame_settings.height, true);
+        save_to_ppm(
+            &format!("frame_{frame:04}.ppm"),
+            frame_settings.width,
+            frame_settings.height,
+            image,
+        );
+    }
+}
+
+/// Example scene by its number, scenes may change the image `settings`
+fn build_scene(scene: &str, settings: &mut GlobalSettings) -> Renderer {
+    match scene {
+        "1" => example_scenes::test_scene(settings),
+        "2" => example_scenes::random_scene(settings),
+        "3" => example_scenes::earth_scene(settings),
+        "4" => example_scenes::lighting_scene(settings),
+        "5" => example_scenes::cornell_box(settings),
+        "6" => example_scenes::noise_scene(settings),
+        "7" => example_scenes::environment_scene(settings),
+        "8" => example_scenes::sky_scene(settings),
+        "9" => example_scenes::punctual_lights_scene(settings),
+        "10" => example_scenes::microfacet_scene(settings),
+        "11" => example_scenes::principled_scene(settings),
+        "12" => example_scenes::layered_materials_scene(settings),
+        "13" => example_scenes::surface_detail_scene(settings),
+        "14" => example_scenes::emissive_scene(settings),
+        "15" => example_scenes::absorption_scene(settings),
+        "16" => example_scenes::dispersion_scene(settings),
+        "17" => example_scenes::thin_film_scene(settings),
+        "18" => example_scenes::subsurface_scene(settings),
+        "19" => example_scenes::rough_diffuse_scene(settings),
+        "20" => example_scenes::brushed_metal_scene(settings),
+        "21" => example_scenes::orthographic_scene(settings),
+        "22" => example_scenes::panorama_scene(settings),
+        "23" => example_scenes::fisheye_scene(settings),
+        "24" => example_scenes::stereo_scene(settings),
+        "25" => example_scenes::omnidirectional_stereo_scene(settings),
+        "26" => example_scenes::lens_scene(settings),
+        "27" => example_scenes::bokeh_scene(settings),
+        "28" => example_scenes::animated_camera_scene(settings),
+        "29" => example_scenes::transform_motion_scene(settings),
+        _ => panic!("Unknown scene number"),
+    }
 }
 
 fn save_to_ppm(filename: &str, width: usize, height: usize, scene: Vec<Vec3>) {
     let mut ppm = PpmImage::new(width, height);
     ppm.pixels = scene
         .into_iter()
-        .map(|p| Color::try_from(p).unwrap())
+        .map(|p| {
+            // Clamp HDR values, since PPM stores only [0, 1] range
+            Color::try_from(Vec3::new(p.x().min(1.), p.y().min(1.), p.z().min(1.))).unwrap()
+        })
         .collect();
 
     let mut path = std::env::current_dir().unwrap();
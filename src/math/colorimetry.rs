@@ -0,0 +1,43 @@
+use super::vec3::Vec3;
+
+/// Relative luminance of linear sRGB color
+pub fn luminance(color: &Vec3) -> f32 {
+    0.2126 * color.x() + 0.7152 * color.y() + 0.0722 * color.z()
+}
+
+/// Convert CIE XYZ color to linear sRGB
+pub fn xyz_to_linear_srgb(xyz: &Vec3) -> Vec3 {
+    Vec3::new(
+        3.240_6 * xyz.x() - 1.537_2 * xyz.y() - 0.498_6 * xyz.z(),
+        -0.968_9 * xyz.x() + 1.875_8 * xyz.y() + 0.041_5 * xyz.z(),
+        0.055_7 * xyz.x() - 0.204 * xyz.y() + 1.057 * xyz.z(),
+    )
+}
+
+/// Convert CIE xyY chromaticity and luminance to XYZ
+pub fn xyy_to_xyz(x: f32, y: f32, luminance: f32) -> Vec3 {
+    if y <= 0. {
+        return Vec3::zero();
+    }
+    Vec3::new(x / y * luminance, luminance, (1. - x - y) / y * luminance)
+}
+
+/// CIE 1931 color matching functions, multi-lobe Gaussian fit from Wyman et al.
+/// "Simple Analytic Approximations to the CIE XYZ Color Matching Functions"
+pub fn cie_xyz(wavelength: f32) -> Vec3 {
+    let gaussian = |mean: f32, sigma_low: f32, sigma_high: f32| {
+        let sigma = if wavelength < mean {
+            sigma_low
+        } else {
+            sigma_high
+        };
+        let t = (wavelength - mean) / sigma;
+        f32::exp(-0.5 * t * t)
+    };
+    Vec3::new(
+        1.056 * gaussian(599.8, 37.9, 31.0) + 0.362 * gaussian(442.0, 16.0, 26.7)
+            - 0.065 * gaussian(501.1, 20.4, 26.2),
+        0.821 * gaussian(568.8, 46.9, 40.5) + 0.286 * gaussian(530.9, 16.3, 31.1),
+        1.217 * gaussian(437.0, 11.8, 36.0) + 0.681 * gaussian(459.0, 26.0, 13.8),
+    )
+}
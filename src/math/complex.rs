@@ -0,0 +1,104 @@
+use std::ops::{Add, Div, Mul, Sub};
+
+/// Complex number
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Complex {
+    pub re: f32,
+    pub im: f32,
+}
+
+impl Complex {
+    pub const fn new(re: f32, im: f32) -> Self {
+        Self { re, im }
+    }
+
+    /// Squared magnitude
+    pub fn norm(&self) -> f32 {
+        self.re * self.re + self.im * self.im
+    }
+
+    /// Principal square root
+    pub fn sqrt(&self) -> Self {
+        let magnitude = self.norm().sqrt();
+        if magnitude == 0. {
+            return Self::default();
+        }
+        let re = f32::sqrt(0.5 * (magnitude + self.re));
+        let im = f32::sqrt(0.5 * (magnitude - self.re)).copysign(self.im);
+        Self { re, im }
+    }
+
+    /// Complex exponent of purely imaginary number, i.e. `e^(i*phase)`
+    pub fn from_phase(phase: f32) -> Self {
+        let (sin, cos) = phase.sin_cos();
+        Self { re: cos, im: sin }
+    }
+}
+
+impl From<f32> for Complex {
+    fn from(value: f32) -> Self {
+        Self { re: value, im: 0. }
+    }
+}
+
+impl Add for Complex {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.re + rhs.re, self.im + rhs.im)
+    }
+}
+
+impl Sub for Complex {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.re - rhs.re, self.im - rhs.im)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::new(
+            self.re * rhs.re - self.im * rhs.im,
+            self.re * rhs.im + self.im * rhs.re,
+        )
+    }
+}
+
+impl Div for Complex {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self::Output {
+        let scale = 1. / rhs.norm();
+        Self::new(
+            scale * (self.re * rhs.re + self.im * rhs.im),
+            scale * (self.im * rhs.re - self.re * rhs.im),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Complex;
+
+    #[test]
+    fn arithmetic_test() {
+        let a = Complex::new(1., 2.);
+        let b = Complex::new(3., -1.);
+        let product = a * b;
+        assert_eq!(product.re, 5.);
+        assert_eq!(product.im, 5.);
+
+        let quotient = product / b;
+        assert!((quotient.re - 1.).abs() < 1e-6);
+        assert!((quotient.im - 2.).abs() < 1e-6);
+
+        let root = Complex::new(-4., 0.).sqrt();
+        assert!(root.re.abs() < 1e-6);
+        assert!((root.im - 2.).abs() < 1e-6);
+
+        let root = a.sqrt();
+        let square = root * root;
+        assert!((square.re - a.re).abs() < 1e-5);
+        assert!((square.im - a.im).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,152 @@
+/// Piecewise constant 1D distribution over [0, 1) range
+pub struct Distribution1D {
+    func: Vec<f32>,
+    cdf: Vec<f32>,
+    integral: f32,
+}
+
+/// Piecewise constant 2D distribution over [0, 1) x [0, 1) range.
+///
+/// Row is selected with marginal density first, then column with density of the row
+pub struct Distribution2D {
+    conditional: Vec<Distribution1D>,
+    marginal: Distribution1D,
+}
+
+impl Distribution1D {
+    /// Create distribution proportional to non negative `func` values
+    pub fn new(func: &[f32]) -> Self {
+        let count = func.len() as f32;
+        let mut cdf = Vec::with_capacity(func.len() + 1);
+        cdf.push(0.);
+        for value in func {
+            cdf.push(cdf[cdf.len() - 1] + value / count);
+        }
+        let integral = cdf[cdf.len() - 1];
+
+        // Fallback to uniform distribution if there is nothing to sample
+        let (func, integral) = if integral > 0. {
+            cdf.iter_mut().for_each(|value| *value /= integral);
+            (func.to_vec(), integral)
+        } else {
+            cdf.iter_mut()
+                .enumerate()
+                .for_each(|(i, value)| *value = i as f32 / count);
+            (vec![1.; func.len()], 1.)
+        };
+
+        Self {
+            func,
+            cdf,
+            integral,
+        }
+    }
+
+    /// Integral of the function over [0, 1)
+    pub fn integral(&self) -> f32 {
+        self.integral
+    }
+
+    /// Map uniform `sample` to distribution.
+    ///
+    /// ## Returns
+    ///
+    /// Sampled value, its probability density and index of the piece
+    pub fn sample(&self, sample: f32) -> (f32, f32, usize) {
+        let offset = self
+            .cdf
+            .partition_point(|value| *value <= sample)
+            .saturating_sub(1)
+            .min(self.func.len() - 1);
+
+        let mut delta = sample - self.cdf[offset];
+        let width = self.cdf[offset + 1] - self.cdf[offset];
+        if width > 0. {
+            delta /= width;
+        }
+        let value = (offset as f32 + delta) / self.func.len() as f32;
+        (
+            value.min(1. - f32::EPSILON),
+            self.func[offset] / self.integral,
+            offset,
+        )
+    }
+
+    /// Probability density of the `value`
+    pub fn pdf(&self, value: f32) -> f32 {
+        self.func[self.offset(value)] / self.integral
+    }
+
+    fn offset(&self, value: f32) -> usize {
+        ((value * self.func.len() as f32) as usize).min(self.func.len() - 1)
+    }
+}
+
+impl Distribution2D {
+    /// Create distribution from row major `func` values
+    pub fn new(func: &[f32], width: usize, height: usize) -> Self {
+        let conditional: Vec<Distribution1D> = func
+            .chunks(width)
+            .take(height)
+            .map(Distribution1D::new)
+            .collect();
+        let marginal: Vec<f32> = conditional.iter().map(Distribution1D::integral).collect();
+        Self {
+            conditional,
+            marginal: Distribution1D::new(&marginal),
+        }
+    }
+
+    /// Map pair of uniform samples to distribution.
+    ///
+    /// ## Returns
+    ///
+    /// Sampled `(u, v)` coordinates and their probability density
+    pub fn sample(&self, sample_u: f32, sample_v: f32) -> ((f32, f32), f32) {
+        let (v, pdf_v, row) = self.marginal.sample(sample_v);
+        let (u, pdf_u, _) = self.conditional[row].sample(sample_u);
+        ((u, v), pdf_u * pdf_v)
+    }
+
+    /// Probability density of `(u, v)` coordinates
+    pub fn pdf(&self, u: f32, v: f32) -> f32 {
+        let row = self.marginal.offset(v);
+        self.marginal.pdf(v) * self.conditional[row].pdf(u)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Distribution1D, Distribution2D};
+
+    #[test]
+    fn distribution_1d_test() {
+        let distribution = Distribution1D::new(&[0., 1., 3.]);
+        assert!((distribution.integral() - 4. / 3.).abs() < 1e-6);
+
+        let (value, pdf, offset) = distribution.sample(0.5);
+        assert_eq!(offset, 2);
+        assert!((pdf - 2.25).abs() < 1e-6);
+        assert!(value >= 2. / 3.);
+        assert_eq!(distribution.pdf(0.1), 0.);
+        assert!((distribution.pdf(value) - pdf).abs() < 1e-6);
+    }
+
+    #[test]
+    fn empty_distribution_test() {
+        let distribution = Distribution1D::new(&[0., 0.]);
+        let (value, pdf, _) = distribution.sample(0.25);
+        assert!((value - 0.25).abs() < 1e-6);
+        assert_eq!(pdf, 1.);
+    }
+
+    #[test]
+    fn distribution_2d_test() {
+        let distribution = Distribution2D::new(&[1., 0., 0., 3.], 2, 2);
+        let ((u, v), pdf) = distribution.sample(0.9, 0.9);
+        assert!(u >= 0.5 && v >= 0.5);
+        assert!((pdf - 3.).abs() < 1e-6);
+        assert!((distribution.pdf(u, v) - pdf).abs() < 1e-6);
+        assert_eq!(distribution.pdf(0.75, 0.25), 0.);
+    }
+}
@@ -1,9 +1,27 @@
 use std::f32::consts::PI;
 
+use rand::{thread_rng, Rng};
+
 use self::vec3::Vec3;
 
+/// Color spaces conversion
+pub mod colorimetry;
+/// Complex numbers
+pub mod complex;
+/// Piecewise constant distributions for importance sampling
+pub mod distribution;
+/// Orthonormal basis
+pub mod onb;
+/// Perlin gradient noise
+pub mod perlin;
+/// Quaternions for rotations
+pub mod quaternion;
+/// Spectral sampling of light
+pub mod spectrum;
 /// Math primitives and oparations with them
 pub mod vec3;
+/// Worley cellular noise
+pub mod worley;
 
 pub fn degrees_to_radians(degrees: f32) -> f32 {
     degrees * PI / 180.0
@@ -27,3 +45,32 @@ pub fn random_in_unit_disk() -> Vec3 {
         }
     }
 }
+
+/// Multiple importance sampling weight of the first strategy
+pub fn power_heuristic(pdf: f32, other_pdf: f32) -> f32 {
+    let (pdf, other_pdf) = (pdf * pdf, other_pdf * other_pdf);
+    if pdf + other_pdf == 0. {
+        0.
+    } else {
+        pdf / (pdf + other_pdf)
+    }
+}
+
+/// Cosine weighted direction on hemisphere around `z` axis
+pub fn random_cosine_direction() -> Vec3 {
+    let mut rng = thread_rng();
+    let (r1, r2): (f32, f32) = (rng.gen(), rng.gen());
+    let phi = 2. * PI * r1;
+    let r = r2.sqrt();
+    Vec3::new(r * phi.cos(), r * phi.sin(), f32::sqrt(1. - r2))
+}
+
+/// Uniform direction inside cone around `z` axis
+pub fn random_in_cone(cos_theta_max: f32) -> Vec3 {
+    let mut rng = thread_rng();
+    let (r1, r2): (f32, f32) = (rng.gen(), rng.gen());
+    let cos_theta = 1. - r2 * (1. - cos_theta_max);
+    let sin_theta = f32::sqrt(f32::max(0., 1. - cos_theta * cos_theta));
+    let phi = 2. * PI * r1;
+    Vec3::new(sin_theta * phi.cos(), sin_theta * phi.sin(), cos_theta)
+}
@@ -0,0 +1,72 @@
+use super::vec3::Vec3;
+
+/// Orthonormal basis
+pub struct Onb {
+    pub u: Vec3,
+    pub v: Vec3,
+    pub w: Vec3,
+}
+
+impl Onb {
+    /// Basis with `w` axis pointing to the given direction
+    pub fn from_w(direction: &Vec3) -> Self {
+        let w = direction.norm();
+        // Branchless construction from "Building an Orthonormal Basis, Revisited"
+        let sign = 1f32.copysign(w.z());
+        let a = -1. / (sign + w.z());
+        let b = w.x() * w.y() * a;
+        let u = Vec3::new(1. + sign * w.x() * w.x() * a, sign * b, -sign * w.x());
+        let v = Vec3::new(b, sign + w.y() * w.y() * a, -w.y());
+        Self { u, v, w }
+    }
+
+    /// Basis with `w` axis along `normal` and `u` axis along projection of `tangent`
+    pub fn from_wu(normal: &Vec3, tangent: &Vec3) -> Self {
+        let w = normal.norm();
+        let projected = *tangent - tangent.dot(&w) * w;
+        if projected.length_squared() < 1e-12 {
+            return Self::from_w(&w);
+        }
+        let u = projected.norm();
+        let v = w.cross(&u);
+        Self { u, v, w }
+    }
+
+    /// Convert vector from basis coordinates to world
+    pub fn to_world(&self, local: &Vec3) -> Vec3 {
+        local.x() * self.u + local.y() * self.v + local.z() * self.w
+    }
+
+    /// Convert world vector to basis coordinates
+    pub fn to_local(&self, world: &Vec3) -> Vec3 {
+        Vec3::new(world.dot(&self.u), world.dot(&self.v), world.dot(&self.w))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::math::vec3::Vec3;
+
+    use super::Onb;
+
+    #[test]
+    fn orthonormal_test() {
+        for direction in [
+            Vec3::new(0., 0., 1.),
+            Vec3::new(0., 0., -1.),
+            Vec3::new(1., 2., 3.),
+            Vec3::new(-0.3, 0.1, -0.9),
+        ] {
+            let onb = Onb::from_w(&direction);
+            assert!((onb.u.length() - 1.).abs() < 1e-5);
+            assert!((onb.v.length() - 1.).abs() < 1e-5);
+            assert!(onb.u.dot(&onb.v).abs() < 1e-5);
+            assert!(onb.u.dot(&onb.w).abs() < 1e-5);
+            assert!((onb.u.cross(&onb.v).dot(&onb.w) - 1.).abs() < 1e-5);
+
+            let world = Vec3::new(0.5, -0.25, 2.);
+            let back = onb.to_world(&onb.to_local(&world));
+            assert!((back - world).length() < 1e-5);
+        }
+    }
+}
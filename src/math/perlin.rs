@@ -0,0 +1,162 @@
+use rand::{rngs::StdRng, seq::SliceRandom, thread_rng, Rng, SeedableRng};
+
+use super::vec3::Vec3;
+
+/// Perlin gradient noise generator
+pub struct Perlin {
+    gradients: Vec<Vec3>,
+    perm_x: Vec<usize>,
+    perm_y: Vec<usize>,
+    perm_z: Vec<usize>,
+}
+
+impl Perlin {
+    const POINT_COUNT: usize = 256;
+
+    pub fn new() -> Self {
+        Self::from_rng(&mut thread_rng())
+    }
+
+    /// Generator with reproducible pattern
+    pub fn with_seed(seed: u64) -> Self {
+        Self::from_rng(&mut StdRng::seed_from_u64(seed))
+    }
+
+    fn from_rng<R: Rng>(rng: &mut R) -> Self {
+        let gradients = (0..Self::POINT_COUNT)
+            .map(|_| {
+                Vec3::new(
+                    rng.gen_range(-1.0..1.0),
+                    rng.gen_range(-1.0..1.0),
+                    rng.gen_range(-1.0..1.0),
+                )
+                .norm()
+            })
+            .collect();
+
+        Self {
+            gradients,
+            perm_x: Self::generate_perm(rng),
+            perm_y: Self::generate_perm(rng),
+            perm_z: Self::generate_perm(rng),
+        }
+    }
+
+    /// Noise value in range [-1, 1]. Equals to 0 on integer lattice points
+    pub fn noise(&self, point: &Vec3) -> f32 {
+        let (i, u) = Self::split(point.x());
+        let (j, v) = Self::split(point.y());
+        let (k, w) = Self::split(point.z());
+
+        let mut corners = [[[Vec3::zero(); 2]; 2]; 2];
+        for (di, plane) in corners.iter_mut().enumerate() {
+            for (dj, row) in plane.iter_mut().enumerate() {
+                for (dk, corner) in row.iter_mut().enumerate() {
+                    let hash = self.perm_x[(i + di as i32) as usize & 255]
+                        ^ self.perm_y[(j + dj as i32) as usize & 255]
+                        ^ self.perm_z[(k + dk as i32) as usize & 255];
+                    *corner = self.gradients[hash];
+                }
+            }
+        }
+
+        Self::interpolate(&corners, u, v, w)
+    }
+
+    /// Sum of absolute noise values over several octaves
+    pub fn turbulence(&self, point: &Vec3, octaves: usize) -> f32 {
+        let mut accum = 0.0;
+        let mut temp_point = *point;
+        let mut weight = 1.0;
+
+        for _ in 0..octaves {
+            accum += weight * self.noise(&temp_point).abs();
+            weight *= 0.5;
+            temp_point *= 2.;
+        }
+        accum
+    }
+
+    /// Fractal brownian motion: sum of signed noise values over several octaves
+    pub fn fbm(&self, point: &Vec3, octaves: usize) -> f32 {
+        let mut accum = 0.0;
+        let mut temp_point = *point;
+        let mut weight = 1.0;
+
+        for _ in 0..octaves {
+            accum += weight * self.noise(&temp_point);
+            weight *= 0.5;
+            temp_point *= 2.;
+        }
+        accum
+    }
+
+    /// Integer lattice cell and fractional offset inside it
+    fn split(value: f32) -> (i32, f32) {
+        let floor = value.floor();
+        (floor as i32, value - floor)
+    }
+
+    fn generate_perm<R: Rng>(rng: &mut R) -> Vec<usize> {
+        let mut perm: Vec<usize> = (0..Self::POINT_COUNT).collect();
+        perm.shuffle(rng);
+        perm
+    }
+
+    fn interpolate(corners: &[[[Vec3; 2]; 2]; 2], u: f32, v: f32, w: f32) -> f32 {
+        // Hermitian smoothing
+        let uu = u * u * (3. - 2. * u);
+        let vv = v * v * (3. - 2. * v);
+        let ww = w * w * (3. - 2. * w);
+
+        let mut accum = 0.0;
+        for (i, plane) in corners.iter().enumerate() {
+            let fi = i as f32;
+            for (j, row) in plane.iter().enumerate() {
+                let fj = j as f32;
+                for (k, gradient) in row.iter().enumerate() {
+                    let fk = k as f32;
+                    let weight = Vec3::new(u - fi, v - fj, w - fk);
+                    accum += (fi * uu + (1. - fi) * (1. - uu))
+                        * (fj * vv + (1. - fj) * (1. - vv))
+                        * (fk * ww + (1. - fk) * (1. - ww))
+                        * gradient.dot(&weight);
+                }
+            }
+        }
+        accum
+    }
+}
+
+impl Default for Perlin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::math::vec3::Vec3;
+
+    use super::Perlin;
+
+    #[test]
+    fn lattice_points_test() {
+        let perlin = Perlin::with_seed(7);
+        assert_eq!(perlin.noise(&Vec3::new(0., 0., 0.)), 0.);
+        assert_eq!(perlin.noise(&Vec3::new(3., -2., 11.)), 0.);
+    }
+
+    #[test]
+    fn range_and_seed_test() {
+        let first = Perlin::with_seed(42);
+        let second = Perlin::with_seed(42);
+        for i in 0..100 {
+            let point = Vec3::new(i as f32 * 0.37, i as f32 * -0.71, i as f32 * 1.13);
+            let value = first.noise(&point);
+            assert!((-1.0..=1.0).contains(&value));
+            assert_eq!(value, second.noise(&point));
+            assert!(first.turbulence(&point, 7) >= 0.);
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use std::ops::Mul;
+
+use super::{degrees_to_radians, vec3::Vec3};
+
+/// Rotation quaternion `w + xi + yj + zk`, stored as scalar and vector parts
+#[derive(Debug, Clone, Copy)]
+pub struct Quaternion {
+    pub w: f32,
+    pub v: Vec3,
+}
+
+impl Default for Quaternion {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl Quaternion {
+    pub const fn new(w: f32, v: Vec3) -> Self {
+        Self { w, v }
+    }
+
+    pub const fn identity() -> Self {
+        Self::new(1., Vec3::zero())
+    }
+
+    /// Rotation counterclockwise by `angle` in degrees around `axis`
+    pub fn from_axis_angle(axis: &Vec3, angle: f32) -> Self {
+        let (sin, cos) = f32::sin_cos(0.5 * degrees_to_radians(angle));
+        Self::new(cos, sin * axis.norm())
+    }
+
+    pub fn dot(&self, rhs: &Self) -> f32 {
+        self.w * rhs.w + self.v.dot(&rhs.v)
+    }
+
+    /// Same rotation with unit length
+    pub fn norm(&self) -> Self {
+        let length = self.dot(self).sqrt();
+        Self::new(self.w / length, &self.v / length)
+    }
+
+    /// Inverse rotation of unit quaternion
+    pub fn conjugate(&self) -> Self {
+        Self::new(self.w, -self.v)
+    }
+
+    /// Rotates vector by unit quaternion
+    pub fn rotate(&self, vector: &Vec3) -> Vec3 {
+        let t = 2. * self.v.cross(vector);
+        *vector + self.w * t + self.v.cross(&t)
+    }
+
+    /// Spherical interpolation with constant angular velocity along the shortest arc
+    pub fn slerp(&self, other: &Self, t: f32) -> Self {
+        let mut cos_theta = self.dot(other);
+        let mut other = *other;
+        // Opposite quaternions are the same rotation, take the closer one
+        if cos_theta < 0. {
+            cos_theta = -cos_theta;
+            other = Self::new(-other.w, -other.v);
+        }
+        let (a, b) = if cos_theta > 0.9995 {
+            // Nearly parallel, linear interpolation avoids division by zero
+            (1. - t, t)
+        } else {
+            let theta = cos_theta.acos();
+            let sin_theta = theta.sin();
+            (
+                f32::sin((1. - t) * theta) / sin_theta,
+                f32::sin(t * theta) / sin_theta,
+            )
+        };
+        Self::new(a * self.w + b * other.w, a * self.v + b * other.v).norm()
+    }
+}
+
+impl Mul for Quaternion {
+    type Output = Self;
+    /// Composition, `rhs` rotation is applied first
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::new(
+            self.w * rhs.w - self.v.dot(&rhs.v),
+            self.w * rhs.v + rhs.w * self.v + self.v.cross(&rhs.v),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::f32::consts::FRAC_PI_6;
+
+    use crate::math::vec3::Vec3;
+
+    use super::Quaternion;
+
+    fn assert_close(a: Vec3, b: Vec3) {
+        assert!((a - b).length() < 1e-5, "{a:?} != {b:?}");
+    }
+
+    #[test]
+    fn rotation_test() {
+        let up = Vec3::new(0., 1., 0.);
+        let quarter = Quaternion::from_axis_angle(&up, 90.);
+        assert_close(
+            quarter.rotate(&Vec3::new(1., 0., 0.)),
+            Vec3::new(0., 0., -1.),
+        );
+        assert_close(
+            quarter.conjugate().rotate(&Vec3::new(0., 0., -1.)),
+            Vec3::new(1., 0., 0.),
+        );
+        assert_close(
+            (quarter * quarter).rotate(&Vec3::new(1., 0., 0.)),
+            Vec3::new(-1., 0., 0.),
+        );
+
+        // Halfway rotation has half the angle, also for the opposite representation
+        // of the end rotation
+        let end = Quaternion::from_axis_angle(&up, 120.);
+        let middle = Quaternion::from_axis_angle(&up, 60.);
+        for end in [end, Quaternion::new(-end.w, -end.v)] {
+            let halfway = Quaternion::identity().slerp(&end, 0.5);
+            assert!(halfway.dot(&middle).abs() > 1. - 1e-5);
+        }
+        let third = Quaternion::identity().slerp(&quarter, 1. / 3.);
+        assert_close(
+            third.rotate(&Vec3::new(1., 0., 0.)),
+            Vec3::new(FRAC_PI_6.cos(), 0., -FRAC_PI_6.sin()),
+        );
+    }
+}
@@ -0,0 +1,245 @@
+use std::sync::OnceLock;
+
+use super::{
+    colorimetry::{cie_xyz, xyz_to_linear_srgb},
+    vec3::Vec3,
+};
+
+/// Shortest visible wavelength in nanometers
+pub const WAVELENGTH_MIN: f32 = 380.;
+/// Longest visible wavelength in nanometers
+pub const WAVELENGTH_MAX: f32 = 720.;
+/// Number of wavelengths carried by single path, spectrum values at them are stored in
+/// components of [`Vec3`]
+pub const WAVELENGTH_SAMPLES: usize = 3;
+
+/// Wavelengths where blue, green and red parts of the spectrum end
+const BAND_EDGES: [f32; 3] = [490., 590., WAVELENGTH_MAX];
+
+/// Wavelengths traced along one path, hero wavelength with others evenly rotated across
+/// the visible range (Wilkie et al. "Hero Wavelength Spectral Sampling").
+///
+/// Throughput and radiance along the path are values of spectra at the path wavelengths.
+/// RGB colors of materials and lights are [uplifted](Self::uplift) where they are used,
+/// and the path is converted through XYZ to RGB only at the film
+#[derive(Clone, Copy)]
+pub struct SampledWavelengths {
+    wavelengths: [f32; WAVELENGTH_SAMPLES],
+    pdf: [f32; WAVELENGTH_SAMPLES],
+}
+
+impl SampledWavelengths {
+    /// Uniformly distributed wavelengths for the random number `u` in range [0, 1)
+    pub fn sample_visible(u: f32) -> Self {
+        let range = WAVELENGTH_MAX - WAVELENGTH_MIN;
+        let mut wavelengths = [0.; WAVELENGTH_SAMPLES];
+        for (i, wavelength) in wavelengths.iter_mut().enumerate() {
+            let offset = (u + i as f32 / WAVELENGTH_SAMPLES as f32).fract();
+            *wavelength = WAVELENGTH_MIN + offset * range;
+        }
+        Self {
+            wavelengths,
+            pdf: [1. / range; WAVELENGTH_SAMPLES],
+        }
+    }
+
+    /// Wavelength defining the path after [termination](Self::terminate_secondary)
+    pub fn hero(&self) -> f32 {
+        self.wavelengths[0]
+    }
+
+    /// Values of `spectrum` given as a function of wavelength at the path wavelengths
+    pub fn sample<F: Fn(f32) -> f32>(&self, spectrum: F) -> Vec3 {
+        let [a, b, c] = self.wavelengths.map(spectrum);
+        Vec3::new(a, b, c)
+    }
+
+    /// Values of the spectrum of `rgb` color at the path wavelengths.
+    ///
+    /// The spectrum is made of three non-overlapping boxes, weighted so that it has the same
+    /// RGB. Colors outside of the gamut of the boxes would need negative weights, which are
+    /// clamped to zero
+    pub fn uplift(&self, rgb: &Vec3) -> Vec3 {
+        let [r, g, b] = &film().to_box;
+        let boxes = Vec3::new(r.dot(rgb).max(0.), g.dot(rgb).max(0.), b.dot(rgb).max(0.));
+        self.sample(|wavelength| spectrum_value(&boxes, wavelength))
+    }
+
+    /// Keep only the hero wavelength, e.g. after refraction in dispersive material
+    /// which sends every wavelength in a different direction
+    pub fn terminate_secondary(&mut self) {
+        if self.is_secondary_terminated() {
+            return;
+        }
+        self.pdf[0] /= WAVELENGTH_SAMPLES as f32;
+        self.pdf[1..].fill(0.);
+    }
+
+    pub fn is_secondary_terminated(&self) -> bool {
+        self.pdf[1..].iter().all(|pdf| *pdf == 0.)
+    }
+
+    /// Estimate XYZ of the spectral `radiance` traced along the path
+    pub fn to_xyz(&self, radiance: &Vec3) -> Vec3 {
+        let values = [radiance.x(), radiance.y(), radiance.z()];
+        let mut xyz = Vec3::zero();
+        for ((wavelength, pdf), value) in self.wavelengths.iter().zip(self.pdf).zip(values) {
+            if pdf > 0. {
+                xyz += (value / pdf) * cie_xyz(*wavelength);
+            }
+        }
+        &xyz / WAVELENGTH_SAMPLES as f32
+    }
+
+    /// Convert spectral `radiance` traced along the path to linear RGB of the film
+    pub fn to_rgb(&self, radiance: &Vec3) -> Vec3 {
+        xyz_to_film(&self.to_xyz(radiance))
+    }
+}
+
+/// Color `rgb` as carried along a path, [uplifted](SampledWavelengths::uplift) to
+/// the path `wavelengths` in spectral rendering
+pub fn path_color(wavelengths: Option<&SampledWavelengths>, rgb: &Vec3) -> Vec3 {
+    match wavelengths {
+        Some(wavelengths) => wavelengths.uplift(rgb),
+        None => *rgb,
+    }
+}
+
+/// RGB color of `spectrum` given as a function of wavelength, e.g. reflectance
+pub fn spectrum_to_rgb<F: Fn(f32) -> f32>(spectrum: F) -> Vec3 {
+    const STEPS: usize = 32;
+    let step = (WAVELENGTH_MAX - WAVELENGTH_MIN) / STEPS as f32;
+    let xyz = (0..STEPS).fold(Vec3::zero(), |xyz, i| {
+        let wavelength = WAVELENGTH_MIN + (i as f32 + 0.5) * step;
+        xyz + (spectrum(wavelength) * step) * cie_xyz(wavelength)
+    });
+    xyz_to_film(&xyz)
+}
+
+/// Value of spectrum with `rgb` weights of the red, green and blue boxes at `wavelength`
+pub fn spectrum_value(rgb: &Vec3, wavelength: f32) -> f32 {
+    if wavelength < BAND_EDGES[0] {
+        rgb.z()
+    } else if wavelength < BAND_EDGES[1] {
+        rgb.y()
+    } else {
+        rgb.x()
+    }
+}
+
+/// Linear sRGB of the film, white balanced so that the equal energy spectrum of white
+/// lights and surfaces is (1, 1, 1)
+fn xyz_to_film(xyz: &Vec3) -> Vec3 {
+    let rgb = xyz_to_linear_srgb(xyz);
+    let white = &film().white;
+    Vec3::new(
+        rgb.x() / white.x(),
+        rgb.y() / white.y(),
+        rgb.z() / white.z(),
+    )
+}
+
+/// Film conversions, computed once
+struct Film {
+    /// Linear sRGB of the equal energy spectrum over the visible range
+    white: Vec3,
+    /// Rows of the inverse of the matrix converting weights of the red, green and blue
+    /// boxes to film RGB. The boxes overlap in RGB, so the weights of a saturated color
+    /// can be negative
+    to_box: [Vec3; 3],
+}
+
+fn film() -> &'static Film {
+    static FILM: OnceLock<Film> = OnceLock::new();
+    FILM.get_or_init(|| {
+        // XYZ of the unit red, green and blue boxes
+        let box_xyz = |channel: &Vec3| {
+            let steps = 1000;
+            let step = (WAVELENGTH_MAX - WAVELENGTH_MIN) / steps as f32;
+            (0..steps).fold(Vec3::zero(), |xyz, i| {
+                let wavelength = WAVELENGTH_MIN + (i as f32 + 0.5) * step;
+                xyz + (spectrum_value(channel, wavelength) * step) * cie_xyz(wavelength)
+            })
+        };
+        let r = box_xyz(&Vec3::new(1., 0., 0.));
+        let g = box_xyz(&Vec3::new(0., 1., 0.));
+        let b = box_xyz(&Vec3::new(0., 0., 1.));
+        // Boxes together cover the visible range with the equal energy spectrum
+        let white = xyz_to_linear_srgb(&(r + g + b));
+        let to_film = |xyz: &Vec3| {
+            let rgb = xyz_to_linear_srgb(xyz);
+            Vec3::new(
+                rgb.x() / white.x(),
+                rgb.y() / white.y(),
+                rgb.z() / white.z(),
+            )
+        };
+        let (r, g, b) = (to_film(&r), to_film(&g), to_film(&b));
+
+        // Inverse of matrix with columns r, g, b, rows are the cross products over the
+        // determinant
+        let determinant = r.dot(&g.cross(&b));
+        Film {
+            white,
+            to_box: [
+                &g.cross(&b) / determinant,
+                &b.cross(&r) / determinant,
+                &r.cross(&g) / determinant,
+            ],
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use crate::math::vec3::Vec3;
+
+    use super::{spectrum_to_rgb, SampledWavelengths, WAVELENGTH_MAX, WAVELENGTH_MIN};
+
+    #[test]
+    fn round_trip_test() {
+        let color = Vec3::new(0.8, 0.3, 0.1);
+        let mut rng = StdRng::seed_from_u64(3);
+        let samples = 20000;
+        let mut rgb = Vec3::zero();
+        let mut hero_rgb = Vec3::zero();
+        for _ in 0..samples {
+            let mut wavelengths = SampledWavelengths::sample_visible(rng.gen());
+            for wavelength in wavelengths.wavelengths {
+                assert!((WAVELENGTH_MIN..WAVELENGTH_MAX).contains(&wavelength));
+            }
+            rgb += wavelengths.to_rgb(&wavelengths.uplift(&color));
+            wavelengths.terminate_secondary();
+            hero_rgb += wavelengths.to_rgb(&wavelengths.uplift(&color));
+        }
+        rgb /= samples as f32;
+        hero_rgb /= samples as f32;
+        assert!((rgb - color).length() < 0.01, "{rgb}");
+        assert!((hero_rgb - color).length() < 0.02, "{hero_rgb}");
+    }
+
+    #[test]
+    fn spectrum_to_rgb_test() {
+        let white = spectrum_to_rgb(|_| 1.);
+        assert!((white - Vec3::new(1., 1., 1.)).length() < 0.01, "{white}");
+        // Long wavelengths are red
+        let red = spectrum_to_rgb(|wavelength| if wavelength > 600. { 1. } else { 0. });
+        assert!(
+            red.x() > 0.5 && red.y().abs() < 0.1 && red.z().abs() < 0.1,
+            "{red}"
+        );
+
+        // Spectral cyan is outside of the sRGB gamut, with negative red
+        let cyan = spectrum_to_rgb(|wavelength| {
+            if (495. ..505.).contains(&wavelength) {
+                1.
+            } else {
+                0.
+            }
+        });
+        assert!(cyan.x() < 0. && cyan.y() > 0. && cyan.z() > 0., "{cyan}");
+    }
+}
@@ -152,7 +152,7 @@ impl Add for Vec3 {
     }
 }
 
-impl<'a, 'b> Add<&'b Vec3> for &'a Vec3 {
+impl<'b> Add<&'b Vec3> for &Vec3 {
     type Output = Vec3;
     fn add(self, rhs: &'b Vec3) -> Self::Output {
         Vec3 {
@@ -168,7 +168,7 @@ impl Sub for Vec3 {
     }
 }
 
-impl<'a, 'b> Sub<&'b Vec3> for &'a Vec3 {
+impl<'b> Sub<&'b Vec3> for &Vec3 {
     type Output = Vec3;
     fn sub(self, rhs: &'b Vec3) -> Self::Output {
         Vec3 {
@@ -0,0 +1,117 @@
+use rand::{thread_rng, Rng};
+
+use super::vec3::Vec3;
+
+/// Worley (cellular) noise generator.
+///
+/// Space is split into unit cells, each containing single pseudo-random feature point
+pub struct Worley {
+    seed: u32,
+}
+
+/// Distances to the closest feature points
+pub struct WorleySample {
+    /// Distance to the closest feature point
+    pub f1: f32,
+    /// Distance to the second closest feature point
+    pub f2: f32,
+    /// Random value in range [0, 1) unique for the closest cell
+    pub cell_value: f32,
+}
+
+impl Worley {
+    pub fn new() -> Self {
+        Self {
+            seed: thread_rng().gen(),
+        }
+    }
+
+    /// Generator with reproducible pattern
+    pub fn with_seed(seed: u32) -> Self {
+        Self { seed }
+    }
+
+    pub fn sample(&self, point: &Vec3) -> WorleySample {
+        let cell = [
+            point.x().floor() as i32,
+            point.y().floor() as i32,
+            point.z().floor() as i32,
+        ];
+
+        let mut sample = WorleySample {
+            f1: f32::INFINITY,
+            f2: f32::INFINITY,
+            cell_value: 0.,
+        };
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                for dz in -1..=1 {
+                    let neighbour = [cell[0] + dx, cell[1] + dy, cell[2] + dz];
+                    let hash = self.hash(neighbour);
+                    let feature = Vec3::new(
+                        neighbour[0] as f32 + Self::to_unit(hash),
+                        neighbour[1] as f32 + Self::to_unit(Self::mix(hash ^ 0x68e3_1da4)),
+                        neighbour[2] as f32 + Self::to_unit(Self::mix(hash ^ 0xb529_7a4d)),
+                    );
+                    let distance = (feature - *point).length();
+                    if distance < sample.f1 {
+                        sample.f2 = sample.f1;
+                        sample.f1 = distance;
+                        sample.cell_value = Self::to_unit(Self::mix(hash ^ 0x1b56_c4e9));
+                    } else if distance < sample.f2 {
+                        sample.f2 = distance;
+                    }
+                }
+            }
+        }
+        sample
+    }
+
+    fn hash(&self, cell: [i32; 3]) -> u32 {
+        let mut hash = self.seed;
+        for coord in cell {
+            hash = Self::mix(hash ^ coord as u32);
+        }
+        hash
+    }
+
+    /// Integer avalanche function
+    fn mix(mut value: u32) -> u32 {
+        value ^= value >> 16;
+        value = value.wrapping_mul(0x7feb_352d);
+        value ^= value >> 15;
+        value = value.wrapping_mul(0x846c_a68b);
+        value ^= value >> 16;
+        value
+    }
+
+    fn to_unit(value: u32) -> f32 {
+        (value >> 8) as f32 / (1 << 24) as f32
+    }
+}
+
+impl Default for Worley {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::math::vec3::Vec3;
+
+    use super::Worley;
+
+    #[test]
+    fn distances_test() {
+        let worley = Worley::with_seed(3);
+        for i in 0..100 {
+            let point = Vec3::new(i as f32 * 0.53, i as f32 * -0.29, i as f32 * 0.91);
+            let sample = worley.sample(&point);
+            assert!(sample.f1 <= sample.f2);
+            // Own cell always contains feature point
+            assert!(sample.f1 <= f32::sqrt(3.));
+            assert!((0.0..1.0).contains(&sample.cell_value));
+        }
+    }
+}
@@ -41,6 +41,9 @@ impl TryFrom<Vec3> for Color {
         if !value.x().is_sign_positive()
             || !value.y().is_sign_positive()
             || !value.z().is_sign_positive()
+            || value.x() > 1.
+            || value.y() > 1.
+            || value.z() > 1.
         {
             return Err(OutOfBoundsError(format!("{:?}", value)));
         }
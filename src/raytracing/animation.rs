@@ -0,0 +1,311 @@
+use crate::math::{quaternion::Quaternion, vec3::Vec3};
+
+/// Values which can be keyframed
+pub trait Animatable: Copy {
+    fn add(&self, other: &Self) -> Self;
+    fn scale(&self, factor: f32) -> Self;
+
+    /// Linear interpolation towards `other`
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        self.scale(1. - t).add(&other.scale(t))
+    }
+
+    /// Representation of the same value closest to `previous`, for values with more
+    /// than one of them
+    fn align_to(&self, _previous: &Self) -> Self {
+        *self
+    }
+}
+
+impl Animatable for f32 {
+    fn add(&self, other: &Self) -> Self {
+        self + other
+    }
+
+    fn scale(&self, factor: f32) -> Self {
+        self * factor
+    }
+}
+
+impl Animatable for Vec3 {
+    fn add(&self, other: &Self) -> Self {
+        *self + *other
+    }
+
+    fn scale(&self, factor: f32) -> Self {
+        factor * self
+    }
+}
+
+/// Rotations are interpolated linearly by [slerp](Quaternion::slerp), curves blend
+/// the components and have to be normalized. Keys of a track are kept in the same
+/// hemisphere, so curves take the shortest path too
+impl Animatable for Quaternion {
+    fn add(&self, other: &Self) -> Self {
+        Quaternion::new(self.w + other.w, self.v + other.v)
+    }
+
+    fn scale(&self, factor: f32) -> Self {
+        Quaternion::new(factor * self.w, factor * self.v)
+    }
+
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        self.slerp(other, t)
+    }
+
+    fn align_to(&self, previous: &Self) -> Self {
+        // Opposite quaternions are the same rotation
+        if self.dot(previous) < 0. {
+            self.scale(-1.)
+        } else {
+            *self
+        }
+    }
+}
+
+/// How the value changes from a keyframe to the next one
+#[derive(Clone, Copy)]
+pub enum Interpolation {
+    /// Value jumps at the next keyframe
+    Constant,
+    Linear,
+    /// Smooth curve through the neighbouring keyframes
+    CatmullRom,
+    /// Cubic curve shaped by handles of the keyframes
+    Bezier,
+}
+
+pub struct Keyframe<T: Animatable> {
+    pub time: f32,
+    pub value: T,
+    /// Interpolation towards the next keyframe
+    pub interpolation: Interpolation,
+    /// Bezier control values before and after the keyframe. Handles equal to the value
+    /// ease in and out of the keyframe
+    pub in_handle: T,
+    pub out_handle: T,
+}
+
+impl<T: Animatable> Keyframe<T> {
+    pub fn new(time: f32, value: T, interpolation: Interpolation) -> Self {
+        Self {
+            time,
+            value,
+            interpolation,
+            in_handle: value,
+            out_handle: value,
+        }
+    }
+
+    pub fn with_handles(mut self, in_handle: T, out_handle: T) -> Self {
+        self.in_handle = in_handle;
+        self.out_handle = out_handle;
+        self
+    }
+}
+
+/// Value changing over time, held constant before the first and after the last keyframe
+pub struct Track<T: Animatable> {
+    keyframes: Vec<Keyframe<T>>,
+}
+
+impl<T: Animatable> Track<T> {
+    pub fn new(mut keyframes: Vec<Keyframe<T>>) -> Self {
+        assert!(!keyframes.is_empty(), "Track needs at least one keyframe");
+        keyframes.sort_by(|a, b| a.time.total_cmp(&b.time));
+        for i in 0..keyframes.len() {
+            if i > 0 {
+                let previous = keyframes[i - 1].value;
+                keyframes[i].value = keyframes[i].value.align_to(&previous);
+            }
+            let key = &mut keyframes[i];
+            key.in_handle = key.in_handle.align_to(&key.value);
+            key.out_handle = key.out_handle.align_to(&key.value);
+        }
+        Self { keyframes }
+    }
+
+    pub fn constant(value: T) -> Self {
+        Self::new(vec![Keyframe::new(0., value, Interpolation::Constant)])
+    }
+
+    /// Keyframes ordered by time
+    pub fn keyframes(&self) -> &[Keyframe<T>] {
+        &self.keyframes
+    }
+
+    pub fn value(&self, time: f32) -> T {
+        let keys = &self.keyframes;
+        let next = keys.partition_point(|key| key.time <= time);
+        if next == 0 {
+            return keys[0].value;
+        }
+        if next == keys.len() {
+            return keys[next - 1].value;
+        }
+
+        let (start, end) = (&keys[next - 1], &keys[next]);
+        let duration = end.time - start.time;
+        let t = (time - start.time) / duration;
+        match start.interpolation {
+            Interpolation::Constant => start.value,
+            Interpolation::Linear => start.value.lerp(&end.value, t),
+            Interpolation::CatmullRom => {
+                // Hermite curve with tangents through the neighbours
+                let (t2, t3) = (t * t, t * t * t);
+                start
+                    .value
+                    .scale(2. * t3 - 3. * t2 + 1.)
+                    .add(&self.tangent(next - 1, duration).scale(t3 - 2. * t2 + t))
+                    .add(&end.value.scale(-2. * t3 + 3. * t2))
+                    .add(&self.tangent(next, duration).scale(t3 - t2))
+            }
+            Interpolation::Bezier => {
+                let s = 1. - t;
+                start
+                    .value
+                    .scale(s * s * s)
+                    .add(&start.out_handle.scale(3. * s * s * t))
+                    .add(&end.in_handle.scale(3. * s * t * t))
+                    .add(&end.value.scale(t * t * t))
+            }
+        }
+    }
+
+    /// Values whose convex hull contains the track between `start_time` and `end_time`.
+    /// Curves contribute their Bezier control points, so the hull is conservative
+    pub fn control_points(&self, start_time: f32, end_time: f32) -> Vec<T> {
+        // Values held outside of the keyframes
+        let mut points = vec![self.value(start_time), self.value(end_time)];
+        for (i, pair) in self.keyframes.windows(2).enumerate() {
+            let (start, end) = (&pair[0], &pair[1]);
+            if end.time <= start_time || end_time <= start.time {
+                continue;
+            }
+            points.push(start.value);
+            match start.interpolation {
+                Interpolation::Constant => {}
+                Interpolation::Linear => points.push(end.value),
+                Interpolation::CatmullRom => {
+                    // Hermite tangents are three times the distance to the inner points
+                    let duration = end.time - start.time;
+                    let out_tangent = self.tangent(i, duration).scale(1. / 3.);
+                    let in_tangent = self.tangent(i + 1, duration).scale(-1. / 3.);
+                    points.push(start.value.add(&out_tangent));
+                    points.push(end.value.add(&in_tangent));
+                    points.push(end.value);
+                }
+                Interpolation::Bezier => {
+                    points.push(start.out_handle);
+                    points.push(end.in_handle);
+                    points.push(end.value);
+                }
+            }
+        }
+        points
+    }
+
+    /// Catmull-Rom tangent at keyframe `i` through its neighbours, scaled to a segment
+    /// of `duration`
+    fn tangent(&self, i: usize, duration: f32) -> T {
+        let keys = &self.keyframes;
+        let before = &keys[i.saturating_sub(1)];
+        let after = &keys[(i + 1).min(keys.len() - 1)];
+        let span = after.time - before.time;
+        after
+            .value
+            .add(&before.value.scale(-1.))
+            .scale(duration / span)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::math::{quaternion::Quaternion, vec3::Vec3};
+
+    use super::{Animatable, Interpolation, Keyframe, Track};
+
+    #[test]
+    fn interpolation_test() {
+        let track = |interpolation| {
+            Track::new(vec![
+                Keyframe::new(2., 4., interpolation),
+                Keyframe::new(0., 0., interpolation),
+                Keyframe::new(1., 1., interpolation),
+            ])
+        };
+
+        let linear = track(Interpolation::Linear);
+        assert_eq!(linear.value(-1.), 0.);
+        assert_eq!(linear.value(0.5), 0.5);
+        assert_eq!(linear.value(1.5), 2.5);
+        assert_eq!(linear.value(3.), 4.);
+        assert_eq!(track(Interpolation::Constant).value(1.9), 1.);
+
+        // Curves pass through keyframes, Catmull-Rom follows the acceleration
+        let catmull_rom = track(Interpolation::CatmullRom);
+        assert!((catmull_rom.value(1.) - 1.).abs() < 1e-6);
+        assert!(catmull_rom.value(1.5) < 2.5);
+
+        // Default handles ease in and out
+        let bezier = track(Interpolation::Bezier);
+        assert!((bezier.value(1.) - 1.).abs() < 1e-6);
+        assert!(bezier.value(0.1) < 0.1 && bezier.value(0.9) > 0.9);
+        let bezier = Track::new(vec![
+            Keyframe::new(0., 0., Interpolation::Bezier).with_handles(0., 2.),
+            Keyframe::new(1., 1., Interpolation::Bezier),
+        ]);
+        assert!(bezier.value(0.5) > 1.);
+    }
+    #[test]
+    fn rotation_test() {
+        let up = Vec3::new(0., 1., 0.);
+        let turn = |angle: f32| Quaternion::from_axis_angle(&up, angle);
+        let flipped = |angle: f32| turn(angle).scale(-1.);
+        let halfway = turn(45.).rotate(&Vec3::new(1., 0., 0.));
+
+        // Keys given on opposite hemispheres still turn along the shortest arc
+        for interpolation in [Interpolation::CatmullRom, Interpolation::Bezier] {
+            let track = Track::new(vec![
+                Keyframe::new(0., turn(0.), interpolation),
+                Keyframe::new(1., flipped(90.), interpolation),
+                Keyframe::new(2., turn(180.), interpolation),
+            ]);
+            let rotation = track.value(0.5).norm();
+            let direction = rotation.rotate(&Vec3::new(1., 0., 0.));
+            // Curves through components only approximate constant angular velocity
+            assert!((direction - halfway).length() < 0.05, "{direction:?}");
+        }
+    }
+    #[test]
+    fn control_points_test() {
+        let track = |interpolation| {
+            Track::new(vec![
+                Keyframe::new(0., 0., interpolation),
+                Keyframe::new(1., 1., interpolation),
+                Keyframe::new(2., -1., interpolation),
+                Keyframe::new(3., 0., interpolation),
+            ])
+        };
+        let bezier = Track::new(vec![
+            Keyframe::new(0., 0., Interpolation::Bezier).with_handles(0., 3.),
+            Keyframe::new(1., 1., Interpolation::Bezier).with_handles(-2., 1.),
+        ]);
+
+        // Curves overshoot the keyframes, but stay within the control points
+        for (track, start, end) in [
+            (track(Interpolation::CatmullRom), 0.2, 2.7),
+            (track(Interpolation::Linear), 1.5, 1.7),
+            (track(Interpolation::Constant), 0.5, 3.5),
+            (bezier, -1., 0.6),
+        ] {
+            let points = track.control_points(start, end);
+            let min = points.iter().copied().fold(f32::INFINITY, f32::min);
+            let max = points.iter().copied().fold(-f32::INFINITY, f32::max);
+            for i in 0..=1000 {
+                let value = track.value(start + (end - start) * i as f32 / 1000.);
+                assert!(min <= value && value <= max, "{min} {value} {max}");
+            }
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use std::{
+    f32::consts::{PI, TAU},
+    path::Path,
+};
+
+use image::DynamicImage;
+use rand::{thread_rng, Rng};
+
+use crate::{
+    math::{colorimetry::luminance, degrees_to_radians, distribution::Distribution2D, vec3::Vec3},
+    raytracing::texture::ImageTextureError,
+};
+
+use super::BackgroundSample;
+
+/// Environment light from equirectangular (latitude-longitude) image.
+///
+/// Top row of the image is `+y` direction, center of the image is `-x` direction
+pub struct EnvironmentMap {
+    width: usize,
+    height: usize,
+    pixels: Vec<Vec3>,
+    /// Luminance based distribution over the image
+    distribution: Distribution2D,
+    sin_theta: f32,
+    cos_theta: f32,
+    /// Radiance multiplier
+    pub intensity: f32,
+    pub visible_to_camera: bool,
+}
+
+impl EnvironmentMap {
+    /// Load linear HDR image, e.g. `.hdr` or `.exr`
+    pub fn new<P: AsRef<Path>>(filepath: P) -> Result<Self, ImageTextureError> {
+        Self::from_image(image::open(filepath)?)
+    }
+
+    pub fn from_image(image: DynamicImage) -> Result<Self, ImageTextureError> {
+        let image = image.into_rgb32f();
+        if image.width() == 0 || image.height() == 0 {
+            return Err(ImageTextureError::Empty);
+        }
+        let (width, height) = (image.width() as usize, image.height() as usize);
+        let pixels: Vec<Vec3> = image
+            .pixels()
+            .map(|pixel| Vec3::new(pixel[0], pixel[1], pixel[2]))
+            .collect();
+
+        // Rows near the poles cover smaller solid angle
+        let weights: Vec<f32> = pixels
+            .iter()
+            .enumerate()
+            .map(|(i, pixel)| {
+                let sin_theta = f32::sin(PI * ((i / width) as f32 + 0.5) / height as f32);
+                luminance(pixel) * sin_theta
+            })
+            .collect();
+
+        Ok(Self {
+            width,
+            height,
+            distribution: Distribution2D::new(&weights, width, height),
+            pixels,
+            sin_theta: 0.,
+            cos_theta: 1.,
+            intensity: 1.,
+            visible_to_camera: true,
+        })
+    }
+
+    /// Rotate environment around `y` axis by `angle` degrees
+    pub fn set_rotation(&mut self, angle: f32) {
+        (self.sin_theta, self.cos_theta) = f32::sin_cos(degrees_to_radians(angle));
+    }
+
+    pub fn radiance(&self, direction: &Vec3) -> Vec3 {
+        let (u, v) = self.to_uv(&self.to_local(direction));
+        self.intensity * self.lookup(u, v)
+    }
+
+    pub fn sample(&self) -> Option<BackgroundSample> {
+        let mut rng = thread_rng();
+        let ((u, v), pdf) = self.distribution.sample(rng.gen(), rng.gen());
+        let sin_theta = f32::sin(v * PI);
+        if pdf == 0. || sin_theta == 0. {
+            return None;
+        }
+
+        Some(BackgroundSample {
+            direction: self.to_world(&Self::from_uv(u, v)),
+            radiance: self.intensity * self.lookup(u, v),
+            pdf: pdf / (2. * PI * PI * sin_theta),
+        })
+    }
+
+    pub fn pdf(&self, direction: &Vec3) -> f32 {
+        let (u, v) = self.to_uv(&self.to_local(direction));
+        let sin_theta = f32::sin(v * PI);
+        if sin_theta == 0. {
+            return 0.;
+        }
+        self.distribution.pdf(u, v) / (2. * PI * PI * sin_theta)
+    }
+
+    fn lookup(&self, u: f32, v: f32) -> Vec3 {
+        let x = ((u * self.width as f32) as usize).min(self.width - 1);
+        let y = ((v * self.height as f32) as usize).min(self.height - 1);
+        self.pixels[y * self.width + x]
+    }
+
+    fn to_uv(&self, direction: &Vec3) -> (f32, f32) {
+        let direction = direction.norm();
+        let theta = f32::acos(direction.y().clamp(-1., 1.));
+        let phi = f32::atan2(-direction.z(), direction.x()) + PI;
+        (phi / TAU, theta / PI)
+    }
+
+    fn from_uv(u: f32, v: f32) -> Vec3 {
+        let (sin_theta, cos_theta) = f32::sin_cos(v * PI);
+        let (sin_phi, cos_phi) = f32::sin_cos(u * TAU - PI);
+        Vec3::new(sin_theta * cos_phi, cos_theta, -sin_theta * sin_phi)
+    }
+
+    fn to_local(&self, direction: &Vec3) -> Vec3 {
+        Vec3::new(
+            self.cos_theta * direction.x() - self.sin_theta * direction.z(),
+            direction.y(),
+            self.sin_theta * direction.x() + self.cos_theta * direction.z(),
+        )
+    }
+
+    fn to_world(&self, direction: &Vec3) -> Vec3 {
+        Vec3::new(
+            self.cos_theta * direction.x() + self.sin_theta * direction.z(),
+            direction.y(),
+            -self.sin_theta * direction.x() + self.cos_theta * direction.z(),
+        )
+    }
+}
@@ -0,0 +1,65 @@
+pub mod environment_map;
+pub mod sky;
+
+use crate::math::vec3::Vec3;
+
+pub use self::{environment_map::EnvironmentMap, sky::PhysicalSky};
+
+/// Radiance coming from directions where rays don't hit any object
+pub enum Background {
+    SolidColor(Vec3),
+    EnvironmentMap(EnvironmentMap),
+    Sky(PhysicalSky),
+}
+
+/// Direction towards background picked for explicit lighting
+pub struct BackgroundSample {
+    /// Normalized direction
+    pub direction: Vec3,
+    pub radiance: Vec3,
+    /// Probability density of the direction over solid angle
+    pub pdf: f32,
+}
+
+impl Background {
+    pub fn radiance(&self, direction: &Vec3) -> Vec3 {
+        match self {
+            Background::SolidColor(color) => *color,
+            Background::EnvironmentMap(map) => map.radiance(direction),
+            Background::Sky(sky) => sky.radiance(direction),
+        }
+    }
+
+    /// Whether camera rays see the background directly
+    pub fn visible_to_camera(&self) -> bool {
+        match self {
+            Background::SolidColor(_) | Background::Sky(_) => true,
+            Background::EnvironmentMap(map) => map.visible_to_camera,
+        }
+    }
+
+    /// Pick direction towards bright areas of the background.
+    /// Returns `None` if background doesn't support importance sampling
+    pub fn sample(&self) -> Option<BackgroundSample> {
+        match self {
+            Background::SolidColor(_) => None,
+            Background::EnvironmentMap(map) => map.sample(),
+            Background::Sky(sky) => sky.sample(),
+        }
+    }
+
+    /// Probability density of [sampling](Background::sample) the `direction`
+    pub fn pdf(&self, direction: &Vec3) -> f32 {
+        match self {
+            Background::SolidColor(_) => 0.,
+            Background::EnvironmentMap(map) => map.pdf(direction),
+            Background::Sky(sky) => sky.pdf(direction),
+        }
+    }
+}
+
+impl From<Vec3> for Background {
+    fn from(value: Vec3) -> Self {
+        Background::SolidColor(value)
+    }
+}
@@ -0,0 +1,274 @@
+use std::f32::consts::{FRAC_PI_2, PI};
+
+use crate::math::{
+    colorimetry::{xyy_to_xyz, xyz_to_linear_srgb},
+    degrees_to_radians, random_cosine_direction,
+    vec3::Vec3,
+};
+
+use super::BackgroundSample;
+
+/// Analytic daylight sky (Preetham et al. "A Practical Analytic Model for Daylight").
+///
+/// The sun disk is not part of the sky radiance, it's added to the scene as
+/// [`SunLight`](crate::raytracing::light::SunLight).
+/// `y` axis points to zenith
+pub struct PhysicalSky {
+    sun_direction: Vec3,
+    turbidity: f32,
+    /// Perez coefficients for luminance and chromaticity
+    perez_luminance: [f32; 5],
+    perez_x: [f32; 5],
+    perez_y: [f32; 5],
+    /// Zenith values divided by Perez function at zenith
+    zenith_luminance: f32,
+    zenith_x: f32,
+    zenith_y: f32,
+    sun_radiance: Vec3,
+    /// Sky radiance multiplier. Sky luminance is computed in kcd/m²
+    pub intensity: f32,
+    /// Sun radiance multiplier, see [`PhysicalSky::sun_radiance`]
+    pub sun_intensity: f32,
+    /// Angular radius of sun disk in degrees
+    pub sun_radius: f32,
+    /// Radiance below horizon
+    pub ground: Vec3,
+}
+
+impl PhysicalSky {
+    /// `turbidity` - haziness of the atmosphere, from 2 (clear) to 10 (hazy)
+    pub fn new(sun_direction: Vec3, turbidity: f32) -> Self {
+        let mut sky = Self {
+            sun_direction: Vec3::new(0., 1., 0.),
+            turbidity,
+            perez_luminance: [0.; 5],
+            perez_x: [0.; 5],
+            perez_y: [0.; 5],
+            zenith_luminance: 0.,
+            zenith_x: 0.,
+            zenith_y: 0.,
+            sun_radiance: Vec3::zero(),
+            intensity: 0.05,
+            sun_intensity: 60000.,
+            sun_radius: 0.27,
+            ground: Vec3::new(0.05, 0.05, 0.05),
+        };
+        sky.set_sun(sun_direction, turbidity);
+        sky
+    }
+
+    /// Sky with sun at `elevation` degrees above horizon and `azimuth` degrees from `+x`
+    /// towards `+z` axis
+    pub fn from_angles(elevation: f32, azimuth: f32, turbidity: f32) -> Self {
+        let (sin_elevation, cos_elevation) = f32::sin_cos(degrees_to_radians(elevation));
+        let (sin_azimuth, cos_azimuth) = f32::sin_cos(degrees_to_radians(azimuth));
+        Self::new(
+            Vec3::new(
+                cos_elevation * cos_azimuth,
+                sin_elevation,
+                cos_elevation * sin_azimuth,
+            ),
+            turbidity,
+        )
+    }
+
+    pub fn sun_direction(&self) -> &Vec3 {
+        &self.sun_direction
+    }
+
+    pub fn turbidity(&self) -> f32 {
+        self.turbidity
+    }
+
+    pub fn set_sun(&mut self, sun_direction: Vec3, turbidity: f32) {
+        let t = turbidity;
+        self.sun_direction = sun_direction.norm();
+        self.turbidity = t;
+
+        self.perez_luminance = [
+            0.1787 * t - 1.4630,
+            -0.3554 * t + 0.4275,
+            -0.0227 * t + 5.3251,
+            0.1206 * t - 2.5771,
+            -0.0670 * t + 0.3703,
+        ];
+        self.perez_x = [
+            -0.0193 * t - 0.2592,
+            -0.0665 * t + 0.0008,
+            -0.0004 * t + 0.2125,
+            -0.0641 * t - 0.8989,
+            -0.0033 * t + 0.0452,
+        ];
+        self.perez_y = [
+            -0.0167 * t - 0.2608,
+            -0.0950 * t + 0.0092,
+            -0.0079 * t + 0.2102,
+            -0.0441 * t - 1.6537,
+            -0.0109 * t + 0.0529,
+        ];
+
+        // Sun below horizon is handled as sunset
+        let theta_sun = f32::acos(self.sun_direction.y().clamp(0., 1.));
+        let chi = (4. / 9. - t / 120.) * (PI - 2. * theta_sun);
+        let zenith_luminance = (4.0453 * t - 4.9710) * chi.tan() - 0.2155 * t + 2.4192;
+
+        let theta = [theta_sun.powi(3), theta_sun.powi(2), theta_sun, 1.];
+        let zenith_x = Self::zenith_chromaticity(
+            t,
+            &theta,
+            [
+                [0.00166, -0.00375, 0.00209, 0.],
+                [-0.02903, 0.06377, -0.03202, 0.00394],
+                [0.11693, -0.21196, 0.06052, 0.25886],
+            ],
+        );
+        let zenith_y = Self::zenith_chromaticity(
+            t,
+            &theta,
+            [
+                [0.00275, -0.00610, 0.00317, 0.],
+                [-0.04214, 0.08970, -0.04153, 0.00516],
+                [0.15346, -0.26756, 0.06670, 0.26688],
+            ],
+        );
+
+        self.zenith_luminance =
+            zenith_luminance.max(0.) / Self::perez(&self.perez_luminance, 0., theta_sun);
+        self.zenith_x = zenith_x / Self::perez(&self.perez_x, 0., theta_sun);
+        self.zenith_y = zenith_y / Self::perez(&self.perez_y, 0., theta_sun);
+        self.sun_radiance = Self::sun_transmittance(theta_sun, t);
+    }
+
+    /// Radiance of the sun disk attenuated by the atmosphere.
+    /// Sun set below the horizon is hidden by the ground
+    pub fn sun_radiance(&self) -> Vec3 {
+        if self.sun_direction.y() > 0. {
+            self.sun_intensity * self.sun_radiance
+        } else {
+            Vec3::zero()
+        }
+    }
+
+    pub fn radiance(&self, direction: &Vec3) -> Vec3 {
+        self.sky_radiance(&direction.norm())
+    }
+
+    /// Cosine weighted direction over the sky dome
+    pub fn sample(&self) -> Option<BackgroundSample> {
+        let local = random_cosine_direction();
+        let direction = Vec3::new(local.x(), local.z(), local.y());
+
+        let pdf = self.pdf(&direction);
+        if pdf == 0. {
+            return None;
+        }
+        Some(BackgroundSample {
+            radiance: self.radiance(&direction),
+            direction,
+            pdf,
+        })
+    }
+
+    pub fn pdf(&self, direction: &Vec3) -> f32 {
+        direction.norm().y().max(0.) / PI
+    }
+
+    fn sky_radiance(&self, direction: &Vec3) -> Vec3 {
+        if direction.y() < 0. {
+            return self.ground;
+        }
+
+        // Avoid singularity of Perez function at horizon
+        let theta = f32::min(f32::acos(direction.y()), FRAC_PI_2 - 0.001);
+        let gamma = f32::acos(direction.dot(&self.sun_direction).clamp(-1., 1.));
+
+        let luminance = self.zenith_luminance * Self::perez(&self.perez_luminance, theta, gamma);
+        let x = self.zenith_x * Self::perez(&self.perez_x, theta, gamma);
+        let y = self.zenith_y * Self::perez(&self.perez_y, theta, gamma);
+
+        let rgb = xyz_to_linear_srgb(&xyy_to_xyz(x, y, luminance));
+        self.intensity * Vec3::new(rgb.x().max(0.), rgb.y().max(0.), rgb.z().max(0.))
+    }
+
+    /// Perez sky luminance distribution function
+    fn perez(coefficients: &[f32; 5], theta: f32, gamma: f32) -> f32 {
+        let [a, b, c, d, e] = *coefficients;
+        let cos_gamma = gamma.cos();
+        (1. + a * f32::exp(b / theta.cos()))
+            * (1. + c * f32::exp(d * gamma) + e * cos_gamma * cos_gamma)
+    }
+
+    fn zenith_chromaticity(turbidity: f32, theta: &[f32; 4], matrix: [[f32; 4]; 3]) -> f32 {
+        let t = [turbidity * turbidity, turbidity, 1.];
+        matrix
+            .iter()
+            .zip(t)
+            .map(|(row, t)| t * row.iter().zip(theta).map(|(m, th)| m * th).sum::<f32>())
+            .sum()
+    }
+
+    /// Sunlight attenuation by Rayleigh and aerosol scattering for red, green and blue
+    /// wavelengths
+    fn sun_transmittance(theta_sun: f32, turbidity: f32) -> Vec3 {
+        // Relative optical mass (Kasten & Young)
+        let zenith_degrees = theta_sun.to_degrees().min(93.885);
+        let air_mass = 1. / (theta_sun.cos() + 0.15 * f32::powf(93.885 - zenith_degrees, -1.253));
+        let beta = 0.04608 * turbidity - 0.04586;
+
+        let transmittance = |wavelength: f32| {
+            // `wavelength` in micrometers
+            let rayleigh = 0.008735 * wavelength.powf(-4.08);
+            let aerosol = beta * wavelength.powf(-1.3);
+            f32::exp(-(rayleigh + aerosol) * air_mass)
+        };
+        Vec3::new(
+            transmittance(0.68),
+            transmittance(0.55),
+            transmittance(0.44),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::math::vec3::Vec3;
+
+    use super::PhysicalSky;
+
+    #[test]
+    fn radiance_test() {
+        let sky = PhysicalSky::from_angles(30., 0., 3.);
+        let zenith = sky.radiance(&Vec3::new(0., 1., 0.));
+        let near_sun = sky.radiance(&Vec3::new(0.85, 0.5, 0.1));
+        assert!(zenith.x() > 0. && zenith.y() > 0. && zenith.z() > 0.);
+        // Clear sky is blue
+        assert!(zenith.z() > zenith.x());
+        assert!(near_sun.y() > zenith.y());
+    }
+
+    #[test]
+    fn sun_below_horizon_test() {
+        let sky = PhysicalSky::from_angles(-5., 0., 3.);
+        // No sun disk drawn on the ground
+        let below = sky.radiance(sky.sun_direction());
+        assert!((below - sky.ground).length() < 1e-6);
+        assert_eq!(sky.sun_radiance().length(), 0.);
+
+        // Samples go only to the visible sky
+        assert_eq!(sky.pdf(sky.sun_direction()), 0.);
+        for _ in 0..100 {
+            let sample = sky.sample().unwrap();
+            assert!(sample.direction.y() >= 0.);
+            assert!((sky.pdf(&sample.direction) - sample.pdf).abs() < 1e-3 * sample.pdf);
+        }
+    }
+
+    #[test]
+    fn sample_pdf_test() {
+        let sky = PhysicalSky::from_angles(45., 90., 4.);
+        for _ in 0..100 {
+            let sample = sky.sample().unwrap();
+            assert!((sky.pdf(&sample.direction) - sample.pdf).abs() < 1e-3 * sample.pdf);
+        }
+    }
+}
@@ -0,0 +1,167 @@
+use crate::{
+    math::vec3::Vec3,
+    raytracing::{animation::Track, ray::Ray},
+};
+
+use super::{shutter_time, Camera, PerspectiveCamera};
+
+/// Perspective camera following keyframed tracks, moving during the exposure.
+///
+/// Tracks are evaluated at a few times over the shutter interval, rays are interpolated
+/// between these still cameras
+pub struct AnimatedCamera {
+    lookfrom: Track<Vec3>,
+    lookat: Track<Vec3>,
+    /// Vertical field of view in degrees
+    vfov: Track<f32>,
+    focus_distance: Track<f32>,
+    rotation: Vec3,
+    aspect_ratio: f32,
+    aperture: f32,
+    /// Still cameras evenly spaced over the shutter interval
+    shutter_cameras: Vec<PerspectiveCamera>,
+    time0: f32, // shutter open time
+    time1: f32, // shutter close time
+}
+
+impl AnimatedCamera {
+    const SHUTTER_STEPS: usize = 8;
+
+    /// Pinhole camera without depth of field
+    pub fn new(
+        lookfrom: Track<Vec3>,
+        lookat: Track<Vec3>,
+        vfov: Track<f32>,
+        aspect_ratio: f32,
+        time0: f32,
+        time1: f32,
+    ) -> Self {
+        let mut camera = Self {
+            lookfrom,
+            lookat,
+            vfov,
+            focus_distance: Track::constant(10.),
+            rotation: Vec3::new(0., 1., 0.),
+            aspect_ratio,
+            aperture: 0.,
+            shutter_cameras: Vec::new(),
+            time0,
+            time1,
+        };
+        camera.update_shutter_cameras();
+        camera
+    }
+
+    pub fn with_focus(mut self, aperture: f32, focus_distance: Track<f32>) -> Self {
+        self.aperture = aperture;
+        self.focus_distance = focus_distance;
+        self.update_shutter_cameras();
+        self
+    }
+
+    pub fn with_rotation(mut self, rotation: Vec3) -> Self {
+        self.rotation = rotation;
+        self.update_shutter_cameras();
+        self
+    }
+
+    /// Still camera at `time`
+    pub fn at(&self, time: f32) -> PerspectiveCamera {
+        PerspectiveCamera::new(
+            self.lookfrom.value(time),
+            self.lookat.value(time),
+            self.rotation,
+            self.vfov.value(time),
+            self.aspect_ratio,
+            self.aperture,
+            self.focus_distance.value(time),
+            time,
+            time,
+        )
+    }
+
+    fn update_shutter_cameras(&mut self) {
+        let steps = if self.time1 > self.time0 {
+            Self::SHUTTER_STEPS
+        } else {
+            0
+        };
+        self.shutter_cameras = (0..=steps)
+            .map(|i| {
+                let t = if steps == 0 {
+                    0.
+                } else {
+                    i as f32 / steps as f32
+                };
+                self.at((1. - t) * self.time0 + t * self.time1)
+            })
+            .collect();
+    }
+}
+
+impl Camera for AnimatedCamera {
+    fn get_ray(&self, x: f32, y: f32) -> Option<Ray> {
+        let time = shutter_time(self.time0, self.time1);
+        let cameras = &self.shutter_cameras;
+        // Aperture doesn't change over time
+        let lens = cameras[0].sample_lens(x, y)?;
+        if cameras.len() == 1 {
+            return Some(cameras[0].lens_ray(x, y, &lens, time));
+        }
+
+        let position = (time - self.time0) / (self.time1 - self.time0) * (cameras.len() - 1) as f32;
+        let i = (position as usize).min(cameras.len() - 2);
+        let t = position - i as f32;
+        let before = cameras[i].lens_ray(x, y, &lens, time);
+        let after = cameras[i + 1].lens_ray(x, y, &lens, time);
+        Some(Ray::new(
+            (1. - t) * before.origin + t * after.origin,
+            (1. - t) * before.direction + t * after.direction,
+            time,
+        ))
+    }
+
+    fn pixel_spread(&self, height: usize) -> f32 {
+        self.shutter_cameras[0].pixel_spread(height)
+    }
+
+    fn set_shutter(&mut self, open: f32, close: f32) {
+        self.time0 = open;
+        self.time1 = close;
+        self.update_shutter_cameras();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        math::vec3::Vec3,
+        raytracing::animation::{Interpolation, Keyframe, Track},
+    };
+
+    use super::{AnimatedCamera, Camera};
+
+    #[test]
+    fn shutter_test() {
+        let lookfrom = Track::new(vec![
+            Keyframe::new(0., Vec3::new(0., 0., 0.), Interpolation::Linear),
+            Keyframe::new(1., Vec3::new(10., 0., 0.), Interpolation::Linear),
+        ]);
+        let lookat = Track::constant(Vec3::new(0., 0., -10.));
+        let mut camera = AnimatedCamera::new(lookfrom, lookat, Track::constant(40.), 1., 0., 1.);
+
+        // Rays of the frame start within its shutter interval at the camera position
+        camera.set_shutter(0.5, 0.6);
+        for _ in 0..100 {
+            let ray = camera.get_ray(0.5, 0.5).unwrap();
+            assert!(ray.time >= 0.5 && ray.time < 0.6);
+            assert!((ray.origin.x() - 10. * ray.time).abs() < 1e-4);
+        }
+
+        // Still shutter uses a single camera
+        camera.set_shutter(0.25, 0.25);
+        let ray = camera.get_ray(0.5, 0.5).unwrap();
+        assert_eq!(ray.time, 0.25);
+        assert!((ray.origin.x() - 2.5).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,109 @@
+use std::{f32::consts::PI, sync::Arc};
+
+use rand::{thread_rng, Rng};
+
+use crate::{
+    math::{colorimetry::luminance, degrees_to_radians, random_in_unit_disk, vec3::Vec3},
+    raytracing::texture::{Texture, TextureFunc, UvCoords},
+};
+
+/// Shape of the lens opening, defocused highlights (bokeh) take this shape
+#[derive(Clone)]
+pub enum Aperture {
+    Circular,
+    /// Regular polygon formed by straight diaphragm blades
+    Polygonal {
+        blades: usize,
+        /// Rotation of the polygon in degrees
+        rotation: f32,
+    },
+    /// Opening is transparent where luminance of the mask is high, the mask covers
+    /// the square around the unit circle
+    Mask(Arc<Texture>),
+}
+
+impl Aperture {
+    /// Rejection sampling attempts for masks which are mostly dark
+    const MAX_MASK_ATTEMPTS: usize = 256;
+
+    /// Uniformly distributed point on the opening within the unit square, `z` is zero
+    pub fn sample(&self) -> Vec3 {
+        match self {
+            Aperture::Circular => random_in_unit_disk(),
+            Aperture::Polygonal { blades, rotation } => {
+                Self::sample_polygon((*blades).max(3), degrees_to_radians(*rotation))
+            }
+            Aperture::Mask(mask) => Self::sample_mask(mask),
+        }
+    }
+
+    /// Polygon inscribed in the unit circle is made of equal triangles around the center
+    fn sample_polygon(blades: usize, rotation: f32) -> Vec3 {
+        let mut rng = thread_rng();
+        let step = 2. * PI / blades as f32;
+        let angle = rotation + rng.gen_range(0..blades) as f32 * step;
+        let first = Vec3::new(angle.cos(), angle.sin(), 0.);
+        let second = Vec3::new((angle + step).cos(), (angle + step).sin(), 0.);
+
+        let (mut u, mut v) = (rng.gen::<f32>(), rng.gen::<f32>());
+        if u + v > 1. {
+            (u, v) = (1. - u, 1. - v);
+        }
+        u * first + v * second
+    }
+
+    fn sample_mask(mask: &Texture) -> Vec3 {
+        let mut rng = thread_rng();
+        for _ in 0..Self::MAX_MASK_ATTEMPTS {
+            let (u, v) = (rng.gen::<f32>(), rng.gen::<f32>());
+            let transparency = luminance(&mask.value(&UvCoords::new(u, v), &Vec3::zero()));
+            if rng.gen::<f32>() < transparency {
+                return Vec3::new(2. * u - 1., 2. * v - 1., 0.);
+            }
+        }
+        Vec3::zero()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use crate::raytracing::texture::{CheckerTexture, SolidColorTexture, Texture};
+
+    use super::Aperture;
+
+    #[test]
+    fn polygon_test() {
+        let aperture = Aperture::Polygonal {
+            blades: 4,
+            rotation: 45.,
+        };
+        // Square with corners on the unit circle
+        let half_side = f32::sqrt(0.5);
+        let mut max = 0f32;
+        for _ in 0..10000 {
+            let point = aperture.sample();
+            assert!(point.x().abs() <= half_side + 1e-5 && point.y().abs() <= half_side + 1e-5);
+            max = max.max(point.x().abs().min(point.y().abs()));
+        }
+        // Corners are reached
+        assert!(max > 0.95 * half_side);
+    }
+
+    #[test]
+    fn mask_test() {
+        let solid = |value| {
+            Arc::new(Texture::SolidColor(SolidColorTexture::new(
+                value, value, value,
+            )))
+        };
+        // Opening only in the top left and bottom right quadrants
+        let mask = Texture::Checker(CheckerTexture::uv(solid(1.), solid(0.), 2.));
+        let aperture = Aperture::Mask(Arc::new(mask));
+        for _ in 0..1000 {
+            let point = aperture.sample();
+            assert!(point.x() * point.y() < 0., "{point}");
+        }
+    }
+}
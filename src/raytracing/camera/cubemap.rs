@@ -0,0 +1,120 @@
+use crate::{
+    math::{onb::Onb, vec3::Vec3},
+    raytracing::ray::Ray,
+};
+
+use super::{shutter_time, view_frame, Camera};
+
+/// Six 90° views in a horizontal strip, the image should have 6:1 aspect ratio.
+///
+/// Faces are in OpenGL order `+x`, `-x`, `+y`, `-y`, `+z`, `-z` of the camera frame,
+/// where `x` is right, `y` is up and the camera looks along `-z`. Unlike OpenGL each face
+/// is an unmirrored view along its axis, side faces keep `+y` up
+pub struct CubemapCamera {
+    pub origin: Vec3,
+    frame: Onb,
+    time0: f32, // shutter open time
+    time1: f32, // shutter close time
+}
+
+impl CubemapCamera {
+    /// View direction, right and up directions of the faces in the camera frame
+    const FACES: [[Vec3; 3]; 6] = [
+        [
+            Vec3::new(1., 0., 0.),
+            Vec3::new(0., 0., 1.),
+            Vec3::new(0., 1., 0.),
+        ],
+        [
+            Vec3::new(-1., 0., 0.),
+            Vec3::new(0., 0., -1.),
+            Vec3::new(0., 1., 0.),
+        ],
+        [
+            Vec3::new(0., 1., 0.),
+            Vec3::new(1., 0., 0.),
+            Vec3::new(0., 0., 1.),
+        ],
+        [
+            Vec3::new(0., -1., 0.),
+            Vec3::new(1., 0., 0.),
+            Vec3::new(0., 0., -1.),
+        ],
+        [
+            Vec3::new(0., 0., 1.),
+            Vec3::new(-1., 0., 0.),
+            Vec3::new(0., 1., 0.),
+        ],
+        [
+            Vec3::new(0., 0., -1.),
+            Vec3::new(1., 0., 0.),
+            Vec3::new(0., 1., 0.),
+        ],
+    ];
+
+    pub fn new(lookfrom: Vec3, lookat: Vec3, rotation: Vec3, time0: f32, time1: f32) -> Self {
+        Self {
+            origin: lookfrom,
+            frame: view_frame(&lookfrom, &lookat, &rotation),
+            time0,
+            time1,
+        }
+    }
+}
+
+impl Camera for CubemapCamera {
+    fn get_ray(&self, x: f32, y: f32) -> Option<Ray> {
+        let strip = x * 6.;
+        let face = (strip as usize).min(5);
+        let [axis, right, up] = Self::FACES[face];
+        let s = 2. * (strip - face as f32) - 1.;
+        let t = 2. * y - 1.;
+        let direction = self.frame.to_world(&(axis + s * right + t * up));
+        Some(Ray::new(
+            self.origin,
+            direction,
+            shutter_time(self.time0, self.time1),
+        ))
+    }
+
+    /// Faces span two units at unit distance
+    fn pixel_spread(&self, height: usize) -> f32 {
+        2. / height as f32
+    }
+
+    fn set_shutter(&mut self, open: f32, close: f32) {
+        self.time0 = open;
+        self.time1 = close;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::math::vec3::Vec3;
+
+    use super::{Camera, CubemapCamera};
+
+    #[test]
+    fn faces_test() {
+        let camera = CubemapCamera::new(
+            Vec3::zero(),
+            Vec3::new(0., 0., -1.),
+            Vec3::new(0., 1., 0.),
+            0.,
+            1.,
+        );
+        let direction = |x, y| camera.get_ray(x, y).unwrap().direction.norm();
+
+        // Centers of the faces look along the axes
+        for (face, [axis, _, _]) in CubemapCamera::FACES.iter().enumerate() {
+            let x = (face as f32 + 0.5) / 6.;
+            assert!((direction(x, 0.5) - *axis).length() < 1e-5);
+        }
+        // Faces are seen from inside of the cube
+        for [axis, right, up] in CubemapCamera::FACES {
+            assert!((axis.cross(&up) - right).length() < 1e-5);
+        }
+        let front_right = direction(1. - 1e-6, 0.5);
+        assert!((front_right - Vec3::new(1., 0., -1.).norm()).length() < 1e-4);
+    }
+}
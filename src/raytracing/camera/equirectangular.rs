@@ -0,0 +1,116 @@
+use std::f32::consts::PI;
+
+use crate::{
+    math::{onb::Onb, vec3::Vec3},
+    raytracing::ray::Ray,
+};
+
+use super::{shutter_time, view_frame, Camera};
+
+/// Full 360° panorama in latitude-longitude layout, the image should have 2:1 aspect ratio.
+/// View direction is in the center of the image
+pub struct EquirectangularCamera {
+    pub origin: Vec3,
+    /// Distance of the eye from `origin` for omni-directional stereo, positive for the right
+    /// eye. Each ray starts on a circle around `origin`, tangent to it
+    pub eye_offset: f32,
+    frame: Onb,
+    time0: f32, // shutter open time
+    time1: f32, // shutter close time
+}
+
+impl EquirectangularCamera {
+    pub fn new(lookfrom: Vec3, lookat: Vec3, rotation: Vec3, time0: f32, time1: f32) -> Self {
+        Self {
+            origin: lookfrom,
+            eye_offset: 0.,
+            frame: view_frame(&lookfrom, &lookat, &rotation),
+            time0,
+            time1,
+        }
+    }
+
+    /// Direction in the camera frame for point of the image
+    pub(super) fn local_direction(x: f32, y: f32) -> Vec3 {
+        let longitude = (x - 0.5) * 2. * PI;
+        let latitude = (y - 0.5) * PI;
+        let (sin_longitude, cos_longitude) = longitude.sin_cos();
+        let (sin_latitude, cos_latitude) = latitude.sin_cos();
+        Vec3::new(
+            cos_latitude * sin_longitude,
+            sin_latitude,
+            -cos_latitude * cos_longitude,
+        )
+    }
+}
+
+impl Camera for EquirectangularCamera {
+    fn get_ray(&self, x: f32, y: f32) -> Option<Ray> {
+        let local = Self::local_direction(x, y);
+        let direction = self.frame.to_world(&local);
+        // Eyes on the circle are turned towards the horizontal part of the direction.
+        // Offset fades out towards poles, where eyes of all directions would mix
+        let right = Vec3::new(-local.z(), 0., local.x());
+        let origin = self.origin + self.eye_offset * self.frame.to_world(&right);
+        Some(Ray::new(
+            origin,
+            direction,
+            shutter_time(self.time0, self.time1),
+        ))
+    }
+
+    fn pixel_spread(&self, height: usize) -> f32 {
+        PI / height as f32
+    }
+
+    fn set_shutter(&mut self, open: f32, close: f32) {
+        self.time0 = open;
+        self.time1 = close;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::math::vec3::Vec3;
+
+    use super::{Camera, EquirectangularCamera};
+
+    #[test]
+    fn directions_test() {
+        let camera = EquirectangularCamera::new(
+            Vec3::zero(),
+            Vec3::new(0., 0., -1.),
+            Vec3::new(0., 1., 0.),
+            0.,
+            1.,
+        );
+        let direction = |x, y| camera.get_ray(x, y).unwrap().direction;
+
+        assert!((direction(0.5, 0.5) - Vec3::new(0., 0., -1.)).length() < 1e-5);
+        assert!((direction(0.75, 0.5) - Vec3::new(1., 0., 0.)).length() < 1e-5);
+        assert!((direction(0., 0.5) - Vec3::new(0., 0., 1.)).length() < 1e-5);
+        assert!((direction(0.3, 1.) - Vec3::new(0., 1., 0.)).length() < 1e-5);
+    }
+
+    #[test]
+    fn omnidirectional_stereo_test() {
+        let mut camera = EquirectangularCamera::new(
+            Vec3::zero(),
+            Vec3::new(0., 0., -1.),
+            Vec3::new(0., 1., 0.),
+            0.,
+            1.,
+        );
+        camera.eye_offset = 0.03;
+
+        for x in [0.1, 0.5, 0.75] {
+            let ray = camera.get_ray(x, 0.5).unwrap();
+            // Ray is tangent to the circle of eyes, right eye is on the right side
+            assert!((ray.origin.length() - 0.03).abs() < 1e-5);
+            assert!(ray.origin.dot(&ray.direction).abs() < 1e-5);
+            assert!(ray.direction.cross(&Vec3::new(0., 1., 0.)).dot(&ray.origin) > 0.);
+        }
+        // Eyes meet at the poles
+        assert!(camera.get_ray(0.2, 1.).unwrap().origin.length() < 1e-5);
+    }
+}
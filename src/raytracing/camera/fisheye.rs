@@ -0,0 +1,129 @@
+use std::f32::consts::PI;
+
+use crate::{
+    math::{degrees_to_radians, onb::Onb, vec3::Vec3},
+    raytracing::ray::Ray,
+};
+
+use super::{shutter_time, view_frame, Camera};
+
+/// Mapping of the angle from the view direction to the distance from the image center
+#[derive(Clone, Copy)]
+pub enum FisheyeProjection {
+    /// Distance is proportional to the angle, e.g. for dome projection
+    Equidistant,
+    /// Equal areas on the image cover equal solid angles, like most real fisheye lenses
+    Equisolid,
+}
+
+/// Circular fisheye image inscribed in the image height
+pub struct FisheyeCamera {
+    pub origin: Vec3,
+    pub projection: FisheyeProjection,
+    /// Angle covered by the image circle in degrees, up to 360
+    pub fov: f32,
+    pub aspect_ratio: f32,
+    frame: Onb,
+    time0: f32, // shutter open time
+    time1: f32, // shutter close time
+}
+
+impl FisheyeCamera {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        lookfrom: Vec3,
+        lookat: Vec3,
+        rotation: Vec3,
+        projection: FisheyeProjection,
+        fov: f32,
+        aspect_ratio: f32,
+        time0: f32,
+        time1: f32,
+    ) -> Self {
+        Self {
+            origin: lookfrom,
+            projection,
+            fov: fov.clamp(0., 360.),
+            aspect_ratio,
+            frame: view_frame(&lookfrom, &lookat, &rotation),
+            time0,
+            time1,
+        }
+    }
+
+    /// Angle from the view direction at `radius` relative to the image circle
+    fn angle(&self, radius: f32) -> f32 {
+        let max_angle = degrees_to_radians(self.fov) / 2.;
+        match self.projection {
+            FisheyeProjection::Equidistant => radius * max_angle,
+            FisheyeProjection::Equisolid => {
+                2. * f32::asin((radius * f32::sin(max_angle / 2.)).min(1.))
+            }
+        }
+    }
+}
+
+impl Camera for FisheyeCamera {
+    fn get_ray(&self, x: f32, y: f32) -> Option<Ray> {
+        let u = (2. * x - 1.) * self.aspect_ratio;
+        let v = 2. * y - 1.;
+        let radius = f32::sqrt(u * u + v * v);
+        if radius > 1. {
+            return None;
+        }
+
+        let (sin_theta, cos_theta) = self.angle(radius).sin_cos();
+        let phi = f32::atan2(v, u);
+        let local = Vec3::new(sin_theta * phi.cos(), sin_theta * phi.sin(), -cos_theta);
+        Some(Ray::new(
+            self.origin,
+            self.frame.to_world(&local),
+            shutter_time(self.time0, self.time1),
+        ))
+    }
+
+    /// Exact at the image center
+    fn pixel_spread(&self, height: usize) -> f32 {
+        let max_angle = degrees_to_radians(self.fov) / 2.;
+        let scale = match self.projection {
+            FisheyeProjection::Equidistant => max_angle,
+            FisheyeProjection::Equisolid => 2. * f32::sin(max_angle / 2.),
+        };
+        2. * scale.min(PI) / height as f32
+    }
+
+    fn set_shutter(&mut self, open: f32, close: f32) {
+        self.time0 = open;
+        self.time1 = close;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::math::vec3::Vec3;
+
+    use super::{Camera, FisheyeCamera, FisheyeProjection};
+
+    #[test]
+    fn projection_test() {
+        for projection in [FisheyeProjection::Equidistant, FisheyeProjection::Equisolid] {
+            let camera = FisheyeCamera::new(
+                Vec3::zero(),
+                Vec3::new(0., 0., -1.),
+                Vec3::new(0., 1., 0.),
+                projection,
+                180.,
+                2.,
+                0.,
+                1.,
+            );
+            let direction = |x, y| camera.get_ray(x, y).unwrap().direction.norm();
+
+            assert!((direction(0.5, 0.5) - Vec3::new(0., 0., -1.)).length() < 1e-5);
+            // Edge of the image circle is perpendicular to the view direction
+            assert!((direction(0.5, 1.) - Vec3::new(0., 1., 0.)).length() < 1e-5);
+            assert!((direction(0.25, 0.5) - Vec3::new(-1., 0., 0.)).length() < 1e-5);
+            assert!(camera.get_ray(0., 0.).is_none());
+        }
+    }
+}
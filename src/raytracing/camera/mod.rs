@@ -0,0 +1,65 @@
+pub mod animated;
+pub mod aperture;
+pub mod cubemap;
+pub mod equirectangular;
+pub mod fisheye;
+pub mod orthographic;
+pub mod perspective;
+pub mod realistic;
+pub mod stereo;
+
+use rand::{thread_rng, Rng};
+
+use crate::math::{onb::Onb, vec3::Vec3};
+
+use super::ray::Ray;
+
+pub use self::{
+    animated::AnimatedCamera,
+    aperture::Aperture,
+    cubemap::CubemapCamera,
+    equirectangular::EquirectangularCamera,
+    fisheye::{FisheyeCamera, FisheyeProjection},
+    orthographic::OrthographicCamera,
+    perspective::PerspectiveCamera,
+    realistic::{LensElement, LensSystemError, RealisticCamera, DOUBLE_GAUSS_50MM},
+    stereo::{StereoCamera, StereoLayout, StereoMode},
+};
+
+/// Projection of the image plane into rays traced by the [renderer](super::renderer::Renderer)
+pub trait Camera {
+    /// Ray through the point of the image, `x` and `y` are in range [0, 1]
+    /// from the lower left corner. `None` for points outside of the projection,
+    /// e.g. corners around circular fisheye image
+    fn get_ray(&self, x: f32, y: f32) -> Option<Ray>;
+
+    /// Angle between rays of neighbouring pixels of an image with `height` pixels,
+    /// used to pick texture detail level
+    fn pixel_spread(&self, height: usize) -> f32;
+
+    /// Width of rays at their origin for an image with `height` pixels, 0 for rays from
+    /// a single point
+    fn pixel_width(&self, _height: usize) -> f32 {
+        0.
+    }
+
+    /// Move the interval in which rays are traced, e.g. for each frame of an animation
+    fn set_shutter(&mut self, open: f32, close: f32);
+}
+
+/// Random time of a ray within the shutter interval
+fn shutter_time(open: f32, close: f32) -> f32 {
+    if close > open {
+        thread_rng().gen_range(open..close)
+    } else {
+        open
+    }
+}
+
+/// Basis with `u` axis to the right, `v` axis up and `w` axis backwards from `lookat`
+fn view_frame(lookfrom: &Vec3, lookat: &Vec3, rotation: &Vec3) -> Onb {
+    let w = (*lookfrom - *lookat).norm();
+    let u = rotation.cross(&w).norm();
+    let v = w.cross(&u);
+    Onb { u, v, w }
+}
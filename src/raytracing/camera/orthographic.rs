@@ -0,0 +1,128 @@
+use crate::{math::vec3::Vec3, raytracing::ray::Ray};
+
+use super::{shutter_time, Camera};
+
+/// Camera with parallel rays, sizes of objects don't depend on their distance.
+/// Used for technical and architectural renders
+pub struct OrthographicCamera {
+    /// Size of the visible area in scene units
+    pub view_height: f32,
+    pub view_width: f32,
+    direction: Vec3,
+    horizontal: Vec3,
+    vertical: Vec3,
+    lower_left_corner: Vec3,
+    time0: f32, // shutter open time
+    time1: f32, // shutter close time
+}
+
+impl OrthographicCamera {
+    pub fn new(
+        lookfrom: Vec3,
+        lookat: Vec3,
+        rotation: Vec3,
+        view_height: f32,
+        aspect_ratio: f32,
+        time0: f32,
+        time1: f32,
+    ) -> Self {
+        let view_width = aspect_ratio * view_height;
+
+        let basis_forward = (lookfrom - lookat).norm();
+        let basis_right = rotation.cross(&basis_forward).norm();
+        let basis_up = basis_forward.cross(&basis_right);
+
+        let horizontal = view_width * basis_right;
+        let vertical = view_height * basis_up;
+        let lower_left_corner = lookfrom - &horizontal / 2. - &vertical / 2.;
+
+        Self {
+            view_height,
+            view_width,
+            direction: -basis_forward,
+            horizontal,
+            vertical,
+            lower_left_corner,
+            time0,
+            time1,
+        }
+    }
+}
+
+impl Camera for OrthographicCamera {
+    fn get_ray(&self, x: f32, y: f32) -> Option<Ray> {
+        Some(Ray::new(
+            self.lower_left_corner + x * self.horizontal + y * self.vertical,
+            self.direction,
+            shutter_time(self.time0, self.time1),
+        ))
+    }
+
+    /// Rays are parallel
+    fn pixel_spread(&self, _: usize) -> f32 {
+        0.
+    }
+
+    /// Rays are as wide as pixels at any distance
+    fn pixel_width(&self, height: usize) -> f32 {
+        self.view_height / height as f32
+    }
+
+    fn set_shutter(&mut self, open: f32, close: f32) {
+        self.time0 = open;
+        self.time1 = close;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::math::vec3::Vec3;
+
+    use super::{Camera, OrthographicCamera};
+
+    #[test]
+    fn parallel_rays_test() {
+        let camera = OrthographicCamera::new(
+            Vec3::new(0., 0., 10.),
+            Vec3::zero(),
+            Vec3::new(0., 1., 0.),
+            2.,
+            2.,
+            0.,
+            1.,
+        );
+        let corner = camera.get_ray(0., 0.).unwrap();
+        let center = camera.get_ray(0.5, 0.5).unwrap();
+        let top_right = camera.get_ray(1., 1.).unwrap();
+
+        assert!((center.origin - Vec3::new(0., 0., 10.)).length() < 1e-5);
+        assert!((corner.origin - Vec3::new(-2., -1., 10.)).length() < 1e-5);
+        assert!((top_right.origin - Vec3::new(2., 1., 10.)).length() < 1e-5);
+        for ray in [corner, center, top_right] {
+            assert!((ray.direction - Vec3::new(0., 0., -1.)).length() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn footprint_test() {
+        let camera = OrthographicCamera::new(
+            Vec3::new(0., 0., 10.),
+            Vec3::zero(),
+            Vec3::new(0., 1., 0.),
+            2.,
+            2.,
+            0.,
+            1.,
+        );
+        let height = 100;
+        let ray = camera
+            .get_ray(0.5, 0.5)
+            .unwrap()
+            .with_spread(camera.pixel_spread(height))
+            .with_width(camera.pixel_width(height));
+
+        // Texture footprint is one pixel of the view at any distance
+        assert!((ray.footprint(1.) - 0.02).abs() < 1e-6);
+        assert!((ray.footprint(100.) - 0.02).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,151 @@
+use crate::math::{degrees_to_radians, vec3::Vec3};
+
+use crate::raytracing::ray::Ray;
+
+use super::{shutter_time, Aperture, Camera};
+
+/// Pinhole or thin lens camera with perspective projection
+pub struct PerspectiveCamera {
+    pub viewport_height: f32,
+    pub viewport_width: f32,
+    pub origin: Vec3,
+    _basis_forward: Vec3,
+    basis_up: Vec3,
+    basis_left: Vec3,
+    horizontal: Vec3,
+    vertical: Vec3,
+    lower_left_corner: Vec3,
+    lens_radius: f32,
+    aperture: Aperture,
+    /// Horizontal compression of the aperture, e.g. 2 for oval bokeh of anamorphic lenses
+    anamorphic_squeeze: f32,
+    /// Shift of the lens barrel circle clipping the aperture towards the image corners
+    cats_eye: f32,
+    time0: f32, // shutter open time
+    time1: f32, // shutter close time
+}
+
+impl PerspectiveCamera {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        lookfrom: Vec3,
+        lookup: Vec3,
+        rotation: Vec3,
+        vfov: f32,
+        aspect_ratio: f32,
+        aperture: f32,
+        focus_dist: f32,
+        time0: f32,
+        time1: f32,
+    ) -> Self {
+        let theta = degrees_to_radians(vfov);
+        let h = f32::tan(theta / 2.0);
+        let viewport_height = 2.0 * h;
+        let viewport_width = aspect_ratio * viewport_height;
+
+        let basis_forward = (lookfrom - lookup).norm();
+        let basis_up = rotation.cross(&basis_forward).norm();
+        let basis_left = basis_forward.cross(&basis_up);
+
+        let horizontal = focus_dist * viewport_width * basis_up;
+        let vertical = focus_dist * viewport_height * basis_left;
+        let origin = lookfrom;
+
+        let lower_left_corner =
+            origin - &horizontal / 2. - &vertical / 2. - focus_dist * basis_forward;
+
+        Self {
+            viewport_height,
+            viewport_width,
+            origin,
+            horizontal,
+            vertical,
+            lower_left_corner,
+            lens_radius: aperture / 2.,
+            aperture: Aperture::Circular,
+            anamorphic_squeeze: 1.,
+            cats_eye: 0.,
+            _basis_forward: basis_forward,
+            basis_up,
+            basis_left,
+            time0,
+            time1,
+        }
+    }
+
+    /// Shift the image plane sideways by `shift` of its width without rotating the camera,
+    /// e.g. to keep verticals parallel or for off-axis stereo
+    pub fn with_lens_shift(mut self, shift: f32) -> Self {
+        self.lower_left_corner += shift * self.horizontal;
+        self
+    }
+
+    pub fn with_aperture(mut self, aperture: Aperture) -> Self {
+        self.aperture = aperture;
+        self
+    }
+
+    /// Squeeze the aperture horizontally by `squeeze` times
+    pub fn with_anamorphic_squeeze(mut self, squeeze: f32) -> Self {
+        self.anamorphic_squeeze = squeeze.max(1e-3);
+        self
+    }
+
+    /// Clip bokeh towards the image corners by the lens barrel, like optical vignetting
+    /// of real lenses. `strength` is the barrel shift in the aperture radii at the image edge
+    pub fn with_cats_eye(mut self, strength: f32) -> Self {
+        self.cats_eye = strength.max(0.);
+        self
+    }
+
+    /// Point on the lens relative to its center, `None` if blocked by the lens barrel
+    pub(super) fn sample_lens(&self, x: f32, y: f32) -> Option<Vec3> {
+        if self.lens_radius == 0. {
+            return Some(Vec3::zero());
+        }
+        let mut point = self.aperture.sample();
+        let barrel = Vec3::new(2. * x - 1., 2. * y - 1., 0.);
+        if (point - self.cats_eye * barrel).length_squared() > 1. {
+            return None;
+        }
+        point.set_x(point.x() / self.anamorphic_squeeze);
+        Some(self.lens_radius * point)
+    }
+
+    /// Ray through the image point from `lens` point given by [sample_lens](Self::sample_lens)
+    pub(super) fn lens_ray(&self, x: f32, y: f32, lens: &Vec3, time: f32) -> Ray {
+        let offset = lens.x() * self.basis_up + lens.y() * self.basis_left;
+        let direction = self.lower_left_corner() + &(x * self.horizontal()) + y * self.vertical()
+            - self.origin
+            - offset;
+        Ray::new(self.origin + offset, direction, time)
+    }
+
+    pub const fn horizontal(&self) -> &Vec3 {
+        &self.horizontal
+    }
+
+    pub const fn vertical(&self) -> &Vec3 {
+        &self.vertical
+    }
+
+    pub const fn lower_left_corner(&self) -> &Vec3 {
+        &self.lower_left_corner
+    }
+}
+
+impl Camera for PerspectiveCamera {
+    fn get_ray(&self, x: f32, y: f32) -> Option<Ray> {
+        let lens = self.sample_lens(x, y)?;
+        Some(self.lens_ray(x, y, &lens, shutter_time(self.time0, self.time1)))
+    }
+
+    fn pixel_spread(&self, height: usize) -> f32 {
+        self.viewport_height / height as f32
+    }
+
+    fn set_shutter(&mut self, open: f32, close: f32) {
+        self.time0 = open;
+        self.time1 = close;
+    }
+}
@@ -0,0 +1,453 @@
+use std::f32::consts::PI;
+
+use rand::{thread_rng, Rng};
+use thiserror::Error;
+
+use crate::{
+    math::{onb::Onb, random_in_unit_disk, vec3::Vec3},
+    raytracing::ray::Ray,
+};
+
+use super::{shutter_time, view_frame, Camera};
+
+#[derive(Error, Debug)]
+pub enum LensSystemError {
+    #[error("Lens system can't focus at distance {0}")]
+    Focus(f32),
+    #[error("No light passes through the lens system")]
+    Blocked,
+}
+
+/// Spherical surface of a lens prescription. Surfaces are listed from the front of the lens
+/// to the film, lengths are in millimeters
+#[derive(Clone, Copy)]
+pub struct LensElement {
+    /// Positive when the center of curvature is on the film side, zero for the aperture stop
+    pub curvature_radius: f32,
+    /// Distance along the axis to the next surface
+    pub thickness: f32,
+    /// Refraction index of the medium behind the surface, zero for air
+    pub refraction_index: f32,
+    /// Diameter of the surface
+    pub aperture: f32,
+}
+
+impl LensElement {
+    pub const fn new(
+        curvature_radius: f32,
+        thickness: f32,
+        refraction_index: f32,
+        aperture: f32,
+    ) -> Self {
+        Self {
+            curvature_radius,
+            thickness,
+            refraction_index,
+            aperture,
+        }
+    }
+}
+
+/// Double Gauss 50mm f/2 normal lens
+pub const DOUBLE_GAUSS_50MM: [LensElement; 11] = [
+    LensElement::new(29.475, 3.76, 1.67, 25.2),
+    LensElement::new(84.83, 0.12, 0., 25.2),
+    LensElement::new(19.275, 4.025, 1.67, 23.),
+    LensElement::new(40.77, 3.275, 1.699, 23.),
+    LensElement::new(12.75, 5.705, 0., 18.),
+    LensElement::new(0., 4.5, 0., 17.1),
+    LensElement::new(-14.495, 1.18, 1.603, 17.),
+    LensElement::new(40.77, 6.065, 1.658, 20.),
+    LensElement::new(-20.385, 0.19, 0., 20.),
+    LensElement::new(437.065, 3.22, 1.717, 20.),
+    LensElement::new(-39.73, 0., 0., 20.),
+];
+
+/// Lens surface placed on the optical axis, film is at `z = 0` and the scene is towards `+z`
+struct Interface {
+    z: f32,
+    curvature_radius: f32,
+    aperture_radius: f32,
+    /// Refraction index on the scene side
+    eta_front: f32,
+    /// Refraction index on the film side
+    eta_back: f32,
+}
+
+/// Area on the rear lens surface through which light reaches a ring of the film,
+/// bounds are for points on the `+x` axis
+#[derive(Clone, Copy)]
+struct ExitPupil {
+    center: f32,
+    radius: f32,
+}
+
+impl ExitPupil {
+    fn area(&self) -> f32 {
+        PI * self.radius * self.radius
+    }
+}
+
+/// Camera tracing rays through a stack of spherical lenses, see "A Realistic Camera Model
+/// for Computer Graphics" by Kolb et al. Reproduces aberrations and vignetting of real lenses
+pub struct RealisticCamera {
+    pub origin: Vec3,
+    /// Effective focal length in millimeters
+    pub focal_length: f32,
+    frame: Onb,
+    /// Scene units per millimeter of the lens
+    scale: f32,
+    interfaces: Vec<Interface>,
+    film_width: f32,
+    film_height: f32,
+    /// Bounds for equally wide rings from the film center to the corner
+    exit_pupils: Vec<Option<ExitPupil>>,
+    /// Largest area of the exit pupils, rays are kept in proportion to their pupil area
+    max_pupil_area: f32,
+    time0: f32, // shutter open time
+    time1: f32, // shutter close time
+}
+
+impl RealisticCamera {
+    const PUPIL_RINGS: usize = 32;
+    const PUPIL_GRID: usize = 48;
+
+    /// Camera with front lens at `lookfrom`, focused at `focus_distance` in scene units.
+    ///
+    /// `scale` is scene units per millimeter, e.g. 0.001 for meters. `aperture_diameter`
+    /// stops down the aperture stop of the prescription, `film_diagonal` is in millimeters
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        lookfrom: Vec3,
+        lookat: Vec3,
+        rotation: Vec3,
+        elements: &[LensElement],
+        scale: f32,
+        film_diagonal: f32,
+        aspect_ratio: f32,
+        aperture_diameter: f32,
+        focus_distance: f32,
+        time0: f32,
+        time1: f32,
+    ) -> Result<Self, LensSystemError> {
+        let mut interfaces = Self::interfaces(elements, aperture_diameter);
+        let film_distance = Self::focus(&interfaces, focus_distance / scale)
+            .ok_or(LensSystemError::Focus(focus_distance))?;
+        for interface in interfaces.iter_mut() {
+            interface.z += film_distance;
+        }
+
+        let film_height = film_diagonal / f32::sqrt(1. + aspect_ratio * aspect_ratio);
+        let mut camera = Self {
+            origin: lookfrom,
+            focal_length: Self::effective_focal_length(&interfaces),
+            frame: view_frame(&lookfrom, &lookat, &rotation),
+            scale,
+            interfaces,
+            film_width: aspect_ratio * film_height,
+            film_height,
+            exit_pupils: Vec::new(),
+            max_pupil_area: 0.,
+            time0,
+            time1,
+        };
+        camera.exit_pupils = (0..Self::PUPIL_RINGS)
+            .map(|ring| camera.exit_pupil(ring))
+            .collect();
+        camera.exit_pupils[0].ok_or(LensSystemError::Blocked)?;
+        camera.max_pupil_area = camera
+            .exit_pupils
+            .iter()
+            .flatten()
+            .map(ExitPupil::area)
+            .fold(0., f32::max);
+        Ok(camera)
+    }
+
+    /// Surfaces with the last one at `z = 0`
+    fn interfaces(elements: &[LensElement], aperture_diameter: f32) -> Vec<Interface> {
+        let air = |eta: f32| if eta == 0. { 1. } else { eta };
+        let mut z = 0.;
+        let mut interfaces = Vec::with_capacity(elements.len());
+        for (i, element) in elements.iter().enumerate().rev() {
+            // Thickness is the distance to the next surface
+            if i + 1 < elements.len() {
+                z += element.thickness;
+            }
+            let aperture = if element.curvature_radius == 0. {
+                element.aperture.min(aperture_diameter)
+            } else {
+                element.aperture
+            };
+            interfaces.push(Interface {
+                z,
+                curvature_radius: element.curvature_radius,
+                aperture_radius: aperture / 2.,
+                eta_front: match i {
+                    0 => 1.,
+                    _ => air(elements[i - 1].refraction_index),
+                },
+                eta_back: air(element.refraction_index),
+            });
+        }
+        interfaces.reverse();
+        interfaces
+    }
+
+    /// Distance from the last surface to the film at which point on the axis at `distance`
+    /// in front of the lens is sharp
+    fn focus(interfaces: &[Interface], distance: f32) -> Option<f32> {
+        let front = &interfaces[0];
+        let height = 0.05 * front.aperture_radius;
+        let object = Vec3::new(0., 0., front.z + distance);
+        let target = Vec3::new(height, 0., front.z);
+        let (point, direction) = trace(interfaces, object, (target - object).norm(), false)?;
+        // Where the ray crosses the axis behind the lens
+        if direction.x() * point.x() >= 0. || direction.z() >= 0. {
+            return None;
+        }
+        let t = -point.x() / direction.x();
+        let film_distance = -(point.z() + t * direction.z());
+        (film_distance > 0.).then_some(film_distance)
+    }
+
+    fn effective_focal_length(interfaces: &[Interface]) -> f32 {
+        let front = &interfaces[0];
+        let height = 0.05 * front.aperture_radius;
+        let origin = Vec3::new(height, 0., front.z + 1.);
+        match trace(interfaces, origin, Vec3::new(0., 0., -1.), false) {
+            Some((_, direction)) if direction.x() < 0. => height * -direction.z() / -direction.x(),
+            _ => f32::INFINITY,
+        }
+    }
+
+    fn film_radius(&self) -> f32 {
+        f32::sqrt(self.film_width * self.film_width + self.film_height * self.film_height) / 2.
+    }
+
+    /// Bounds of the rear surface area passing light to the film `ring`
+    fn exit_pupil(&self, ring: usize) -> Option<ExitPupil> {
+        let rear = self.interfaces.last()?;
+        let ring_width = self.film_radius() / Self::PUPIL_RINGS as f32;
+        let cell = 2. * rear.aperture_radius / Self::PUPIL_GRID as f32;
+
+        let mut min = Vec3::new(f32::INFINITY, f32::INFINITY, 0.);
+        let mut max = -min;
+        let mut passed = Vec::new();
+        for film_step in 0..4 {
+            let film_x = (ring as f32 + film_step as f32 / 3.) * ring_width;
+            let film_point = Vec3::new(film_x, 0., 0.);
+            for i in 0..Self::PUPIL_GRID {
+                for j in 0..Self::PUPIL_GRID {
+                    let target = Vec3::new(
+                        -rear.aperture_radius + (i as f32 + 0.5) * cell,
+                        -rear.aperture_radius + (j as f32 + 0.5) * cell,
+                        rear.z,
+                    );
+                    let direction = (target - film_point).norm();
+                    if trace(&self.interfaces, film_point, direction, true).is_some() {
+                        min = Vec3::new(min.x().min(target.x()), min.y().min(target.y()), 0.);
+                        max = Vec3::new(max.x().max(target.x()), max.y().max(target.y()), 0.);
+                        passed.push(target);
+                    }
+                }
+            }
+        }
+        if passed.is_empty() {
+            return None;
+        }
+
+        let center = (min.x() + max.x()) / 2.;
+        let radius = passed
+            .iter()
+            .map(|target| f32::hypot(target.x() - center, target.y()))
+            .fold(0., f32::max);
+        Some(ExitPupil {
+            center,
+            radius: radius + cell,
+        })
+    }
+}
+
+impl Camera for RealisticCamera {
+    fn get_ray(&self, x: f32, y: f32) -> Option<Ray> {
+        // Lens flips the image
+        let film_point = Vec3::new(
+            -(x - 0.5) * self.film_width,
+            -(y - 0.5) * self.film_height,
+            0.,
+        );
+        let film_radius = f32::hypot(film_point.x(), film_point.y());
+        let ring = (film_radius / self.film_radius() * Self::PUPIL_RINGS as f32) as usize;
+        let pupil = self.exit_pupils[ring.min(Self::PUPIL_RINGS - 1)]?;
+
+        // Pupil bounds are rotated to the angle of the film point
+        let (sin_phi, cos_phi) = if film_radius > 0. {
+            (film_point.y() / film_radius, film_point.x() / film_radius)
+        } else {
+            (0., 1.)
+        };
+        let offset = pupil.radius * random_in_unit_disk();
+        let rear = self.interfaces.last()?;
+        let target = Vec3::new(
+            pupil.center * cos_phi + offset.x(),
+            pupil.center * sin_phi + offset.y(),
+            rear.z,
+        );
+        let direction = (target - film_point).norm();
+
+        // Natural vignetting and the smaller pupil towards the film corners
+        let mut rng = thread_rng();
+        let area_ratio = pupil.area() / self.max_pupil_area;
+        let cos2_theta = direction.z() * direction.z();
+        if rng.gen::<f32>() > area_ratio * cos2_theta * cos2_theta {
+            return None;
+        }
+
+        let (point, direction) = trace(&self.interfaces, film_point, direction, true)?;
+        let front = self.interfaces[0].z;
+        let offset = Vec3::new(point.x(), point.y(), -(point.z() - front));
+        Some(Ray::new(
+            self.origin + self.scale * self.frame.to_world(&offset),
+            self.frame
+                .to_world(&Vec3::new(direction.x(), direction.y(), -direction.z())),
+            shutter_time(self.time0, self.time1),
+        ))
+    }
+
+    fn pixel_spread(&self, height: usize) -> f32 {
+        self.film_height / height as f32 / self.focal_length
+    }
+
+    fn set_shutter(&mut self, open: f32, close: f32) {
+        self.time0 = open;
+        self.time1 = close;
+    }
+}
+
+/// Trace ray through the lens towards the scene or towards the film.
+/// Returns point and direction behind the last passed surface, `None` if the ray is blocked
+fn trace(
+    interfaces: &[Interface],
+    mut origin: Vec3,
+    mut direction: Vec3,
+    towards_scene: bool,
+) -> Option<(Vec3, Vec3)> {
+    let order: Box<dyn Iterator<Item = &Interface>> = if towards_scene {
+        Box::new(interfaces.iter().rev())
+    } else {
+        Box::new(interfaces.iter())
+    };
+    for interface in order {
+        let (point, normal) = intersect(interface, &origin, &direction)?;
+        if point.x() * point.x() + point.y() * point.y()
+            > interface.aperture_radius * interface.aperture_radius
+        {
+            return None;
+        }
+        if let Some(normal) = normal {
+            let eta = if towards_scene {
+                interface.eta_back / interface.eta_front
+            } else {
+                interface.eta_front / interface.eta_back
+            };
+            direction = refract(&direction, &normal, eta)?;
+        }
+        origin = point;
+    }
+    Some((origin, direction))
+}
+
+/// Hit point and normal facing the ray, the aperture stop has no normal
+fn intersect(
+    interface: &Interface,
+    origin: &Vec3,
+    direction: &Vec3,
+) -> Option<(Vec3, Option<Vec3>)> {
+    let radius = interface.curvature_radius;
+    if radius == 0. {
+        let t = (interface.z - origin.z()) / direction.z();
+        return (t > 0.).then(|| (*origin + t * *direction, None));
+    }
+
+    let center = Vec3::new(0., 0., interface.z - radius);
+    let oc = *origin - center;
+    let half_b = oc.dot(direction);
+    let c = oc.length_squared() - radius * radius;
+    let discriminant = half_b * half_b - c;
+    if discriminant < 0. {
+        return None;
+    }
+    let root = discriminant.sqrt();
+    // Surface is the cap of the sphere around the vertex
+    let t = [-half_b - root, -half_b + root]
+        .into_iter()
+        .filter(|t| *t > 1e-6)
+        .find(|t| (origin.z() + t * direction.z() - center.z()) * radius > 0.)?;
+
+    let point = *origin + t * *direction;
+    let mut normal = (point - center).norm();
+    if normal.dot(direction) > 0. {
+        normal = -normal;
+    }
+    Some((point, Some(normal)))
+}
+
+/// Snell's law for normalized `direction` and `normal` facing it, `None` on total internal
+/// reflection
+fn refract(direction: &Vec3, normal: &Vec3, eta: f32) -> Option<Vec3> {
+    let cos_i = -normal.dot(direction);
+    let sin2_t = eta * eta * (1. - cos_i * cos_i);
+    if sin2_t > 1. {
+        return None;
+    }
+    let cos_t = f32::sqrt(1. - sin2_t);
+    Some(eta * *direction + (eta * cos_i - cos_t) * *normal)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::math::vec3::Vec3;
+
+    use super::{Camera, RealisticCamera, DOUBLE_GAUSS_50MM};
+
+    #[test]
+    fn focus_test() {
+        let camera = RealisticCamera::new(
+            Vec3::zero(),
+            Vec3::new(0., 0., -1.),
+            Vec3::new(0., 1., 0.),
+            &DOUBLE_GAUSS_50MM,
+            0.001,
+            43.3,
+            1.5,
+            8.,
+            2.,
+            0.,
+            1.,
+        )
+        .unwrap();
+        assert!(
+            (camera.focal_length - 50.).abs() < 2.,
+            "{}",
+            camera.focal_length
+        );
+
+        // Rays from the film center meet at the focus distance
+        let mut rays = 0;
+        while rays < 100 {
+            let Some(ray) = camera.get_ray(0.5, 0.5) else {
+                continue;
+            };
+            let direction = ray.direction.norm();
+            let t = (-2. - ray.origin.z()) / direction.z();
+            let point = ray.at(t);
+            assert!(f32::hypot(point.x(), point.y()) < 1e-3, "{point}");
+            rays += 1;
+        }
+
+        // Corners of the image are darker
+        let passed = |x: f32, y: f32| (0..2000).filter(|_| camera.get_ray(x, y).is_some()).count();
+        assert!(passed(0.5, 0.5) > passed(0.02, 0.02));
+    }
+}
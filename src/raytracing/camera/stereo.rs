@@ -0,0 +1,212 @@
+use crate::{math::vec3::Vec3, raytracing::ray::Ray};
+
+use super::{view_frame, Camera, EquirectangularCamera, PerspectiveCamera};
+
+/// How optical axes of the eyes are arranged
+#[derive(Clone, Copy)]
+pub enum StereoMode {
+    /// Parallel axes, everything appears in front of the screen
+    Parallel,
+    /// Eyes rotated towards the convergence point, causes vertical parallax in the corners
+    ToeIn,
+    /// Parallel axes with image planes shifted to meet at the convergence distance
+    OffAxis,
+}
+
+/// Placement of the eye images in the output image
+#[derive(Clone, Copy)]
+pub enum StereoLayout {
+    /// Left eye in the left half
+    SideBySide,
+    /// Left eye in the top half
+    TopBottom,
+}
+
+/// Pair of cameras rendering both eyes into a single image
+pub struct StereoCamera {
+    pub left: Box<dyn Camera + Send + Sync>,
+    pub right: Box<dyn Camera + Send + Sync>,
+    pub layout: StereoLayout,
+}
+
+impl StereoCamera {
+    pub fn new(
+        left: Box<dyn Camera + Send + Sync>,
+        right: Box<dyn Camera + Send + Sync>,
+        layout: StereoLayout,
+    ) -> Self {
+        Self {
+            left,
+            right,
+            layout,
+        }
+    }
+
+    /// Perspective rig with eyes `interocular` apart around `lookfrom`. Objects at
+    /// `convergence` distance appear at the screen depth, except for parallel mode.
+    /// `aspect_ratio` is of a single eye image
+    #[allow(clippy::too_many_arguments)]
+    pub fn perspective(
+        lookfrom: Vec3,
+        lookat: Vec3,
+        rotation: Vec3,
+        vfov: f32,
+        aspect_ratio: f32,
+        interocular: f32,
+        convergence: f32,
+        mode: StereoMode,
+        layout: StereoLayout,
+        time0: f32,
+        time1: f32,
+    ) -> Self {
+        let frame = view_frame(&lookfrom, &lookat, &rotation);
+        let eye = |side: f32| {
+            let position = lookfrom + (side * interocular / 2.) * frame.u;
+            let target = match mode {
+                StereoMode::ToeIn => lookfrom - convergence * frame.w,
+                StereoMode::Parallel | StereoMode::OffAxis => position - frame.w,
+            };
+            let camera = PerspectiveCamera::new(
+                position,
+                target,
+                rotation,
+                vfov,
+                aspect_ratio,
+                0.,
+                convergence,
+                time0,
+                time1,
+            );
+            let camera = match mode {
+                StereoMode::OffAxis => {
+                    let shift = interocular / 2. / (convergence * camera.viewport_width);
+                    camera.with_lens_shift(-side * shift)
+                }
+                StereoMode::Parallel | StereoMode::ToeIn => camera,
+            };
+            Box::new(camera)
+        };
+        Self::new(eye(-1.), eye(1.), layout)
+    }
+
+    /// Omni-directional stereo panorama, usually in top-bottom layout for VR viewers
+    pub fn omnidirectional(
+        lookfrom: Vec3,
+        lookat: Vec3,
+        rotation: Vec3,
+        interocular: f32,
+        layout: StereoLayout,
+        time0: f32,
+        time1: f32,
+    ) -> Self {
+        let eye = |side: f32| {
+            let mut camera = EquirectangularCamera::new(lookfrom, lookat, rotation, time0, time1);
+            camera.eye_offset = side * interocular / 2.;
+            Box::new(camera)
+        };
+        Self::new(eye(-1.), eye(1.), layout)
+    }
+}
+
+impl Camera for StereoCamera {
+    fn get_ray(&self, x: f32, y: f32) -> Option<Ray> {
+        match self.layout {
+            StereoLayout::SideBySide if x < 0.5 => self.left.get_ray(2. * x, y),
+            StereoLayout::SideBySide => self.right.get_ray(2. * x - 1., y),
+            StereoLayout::TopBottom if y >= 0.5 => self.left.get_ray(x, 2. * y - 1.),
+            StereoLayout::TopBottom => self.right.get_ray(x, 2. * y),
+        }
+    }
+
+    fn pixel_spread(&self, height: usize) -> f32 {
+        match self.layout {
+            StereoLayout::SideBySide => self.left.pixel_spread(height),
+            StereoLayout::TopBottom => self.left.pixel_spread(height / 2),
+        }
+    }
+
+    fn pixel_width(&self, height: usize) -> f32 {
+        match self.layout {
+            StereoLayout::SideBySide => self.left.pixel_width(height),
+            StereoLayout::TopBottom => self.left.pixel_width(height / 2),
+        }
+    }
+
+    fn set_shutter(&mut self, open: f32, close: f32) {
+        self.left.set_shutter(open, close);
+        self.right.set_shutter(open, close);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{math::vec3::Vec3, raytracing::camera::orthographic::OrthographicCamera};
+
+    use super::{Camera, StereoCamera, StereoLayout, StereoMode};
+
+    #[test]
+    fn convergence_test() {
+        let rig = |mode| {
+            StereoCamera::perspective(
+                Vec3::zero(),
+                Vec3::new(0., 0., -1.),
+                Vec3::new(0., 1., 0.),
+                40.,
+                1.,
+                0.06,
+                2.,
+                mode,
+                StereoLayout::SideBySide,
+                0.,
+                1.,
+            )
+        };
+        // Point on the image where the ray crosses the central axis
+        let crossing = |camera: &StereoCamera, x: f32| {
+            let ray = camera.get_ray(x, 0.5).unwrap();
+            let t = -ray.origin.x() / ray.direction.x();
+            ray.at(t).z()
+        };
+
+        let parallel = rig(StereoMode::Parallel);
+        let left = parallel.get_ray(0.25, 0.5).unwrap();
+        let right = parallel.get_ray(0.75, 0.5).unwrap();
+        assert!((left.origin - Vec3::new(-0.03, 0., 0.)).length() < 1e-5);
+        assert!((right.origin - Vec3::new(0.03, 0., 0.)).length() < 1e-5);
+        assert!(left.direction.norm().dot(&right.direction.norm()) > 1. - 1e-6);
+
+        // Centers of both eye images meet at the convergence distance
+        for mode in [StereoMode::ToeIn, StereoMode::OffAxis] {
+            let camera = rig(mode);
+            assert!((crossing(&camera, 0.25) + 2.).abs() < 1e-3);
+            assert!((crossing(&camera, 0.75) + 2.).abs() < 1e-3);
+        }
+        // Unlike toe-in, off-axis eyes see corners of the image at the same height
+        let vertical_parallax = |camera: &StereoCamera| {
+            let left = camera.get_ray(0.05, 0.95).unwrap().direction;
+            let right = camera.get_ray(0.55, 0.95).unwrap().direction;
+            (left.y() / left.z() - right.y() / right.z()).abs()
+        };
+        assert!(vertical_parallax(&rig(StereoMode::OffAxis)) < 1e-5);
+        assert!(vertical_parallax(&rig(StereoMode::ToeIn)) > 1e-4);
+    }
+    #[test]
+    fn pixel_width_test() {
+        let eye = || {
+            Box::new(OrthographicCamera::new(
+                Vec3::zero(),
+                Vec3::new(0., 0., -1.),
+                Vec3::new(0., 1., 0.),
+                2.,
+                1.,
+                0.,
+                1.,
+            ))
+        };
+        // Each eye of top-bottom layout covers half of the image height
+        let side_by_side = StereoCamera::new(eye(), eye(), StereoLayout::SideBySide);
+        let top_bottom = StereoCamera::new(eye(), eye(), StereoLayout::TopBottom);
+        assert!((side_by_side.pixel_width(100) - 0.02).abs() < 1e-6);
+        assert!((top_bottom.pixel_width(100) - 0.04).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,95 @@
+use std::sync::Arc;
+
+use crate::{
+    math::vec3::Vec3,
+    raytracing::{objects::SampleableObject, ray::Ray},
+};
+
+use super::LightSample;
+
+/// Emissive object sampled explicitly with shadow rays.
+///
+/// The object has to be also added to the scene, light is emitted by its material
+pub struct AreaLight {
+    pub object: Arc<dyn SampleableObject + Send + Sync>,
+}
+
+impl AreaLight {
+    pub fn new(object: Arc<dyn SampleableObject + Send + Sync>) -> Self {
+        Self { object }
+    }
+
+    pub fn sample(&self, point: &Vec3, time: f32) -> Option<LightSample> {
+        let direction = self.object.sample_direction(point).norm();
+        let hit = self
+            .object
+            .hit(&Ray::new(*point, direction, time), 0.001, f32::INFINITY)?;
+        let pdf = self.object.direction_pdf(point, &hit);
+        if pdf <= 0. || !pdf.is_finite() {
+            return None;
+        }
+        Some(LightSample {
+            direction,
+            distance: hit.distance,
+            radiance: hit.material.emitted(&hit),
+            pdf: Some(pdf),
+        })
+    }
+
+    pub fn pdf(&self, ray: &Ray, distance: f32) -> f32 {
+        let ray = Ray::new(ray.origin, ray.direction.norm(), ray.time);
+        match self.object.hit(&ray, 0.001, f32::INFINITY) {
+            // Light surface is the one hit by the ray
+            Some(hit) if (hit.distance - distance).abs() <= 1e-3 * distance.max(1.) => {
+                self.object.direction_pdf(&ray.origin, &hit)
+            }
+            _ => 0.,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{f32::consts::PI, sync::Arc};
+
+    use crate::{
+        math::vec3::Vec3,
+        raytracing::{
+            material::{MatDiffuseLight, Material},
+            objects::Sphere,
+            ray::Ray,
+            texture::{SolidColorTexture, Texture},
+        },
+    };
+
+    use super::AreaLight;
+
+    #[test]
+    fn sphere_pdf_test() {
+        let material = Arc::new(Material::DiffuseLight(MatDiffuseLight {
+            emit: Arc::new(Texture::SolidColor(SolidColorTexture::new(1., 1., 1.))),
+        }));
+        let light = AreaLight::new(Arc::new(Sphere::new(Vec3::new(0., 0., 5.), 1., material)));
+        let origin = Vec3::zero();
+
+        let samples = 1000;
+        let mut solid_angle = 0.;
+        for _ in 0..samples {
+            let sample = light.sample(&origin, 0.).unwrap();
+            let pdf = sample.pdf.unwrap();
+            let ray = Ray::new(origin, sample.direction, 0.);
+            let expected = light.pdf(&ray, sample.distance);
+            assert!((pdf - expected).abs() < 1e-3 * pdf, "{pdf} {expected}");
+            solid_angle += 1. / pdf;
+        }
+        solid_angle /= samples as f32;
+
+        let cos_theta_max = f32::sqrt(1. - 1. / 25.);
+        let expected = 2. * PI * (1. - cos_theta_max);
+        assert!((solid_angle - expected).abs() < 1e-3, "{solid_angle}");
+
+        // Rays missing the light
+        let ray = Ray::new(origin, Vec3::new(0., 1., 0.), 0.);
+        assert_eq!(light.pdf(&ray, 4.), 0.);
+    }
+}
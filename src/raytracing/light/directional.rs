@@ -0,0 +1,51 @@
+use crate::math::vec3::Vec3;
+
+use super::LightSample;
+
+/// Infinitely distant light with parallel rays, e.g. the sun
+pub struct DirectionalLight {
+    /// Direction of the light rays
+    direction: Vec3,
+    /// Irradiance on a surface perpendicular to the rays
+    pub intensity: Vec3,
+}
+
+impl DirectionalLight {
+    pub fn new(direction: Vec3, intensity: Vec3) -> Self {
+        Self {
+            direction: direction.norm(),
+            intensity,
+        }
+    }
+
+    pub fn sample(&self, _: &Vec3) -> Option<LightSample> {
+        Some(LightSample {
+            direction: -self.direction,
+            distance: f32::INFINITY,
+            radiance: self.intensity,
+            pdf: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::math::vec3::Vec3;
+
+    use super::DirectionalLight;
+
+    #[test]
+    fn constant_radiance_test() {
+        let intensity = Vec3::new(3., 2., 1.);
+        let light = DirectionalLight::new(Vec3::new(0., -2., 0.), intensity);
+
+        // Same radiance near and far, shadow rays reach to infinity
+        for point in [Vec3::zero(), Vec3::new(100., -50., 20.)] {
+            let sample = light.sample(&point).unwrap();
+            assert!((sample.direction - Vec3::new(0., 1., 0.)).length() < 1e-6);
+            assert_eq!(sample.distance, f32::INFINITY);
+            assert!((sample.radiance - intensity).length() < 1e-6);
+            assert!(sample.pdf.is_none());
+        }
+    }
+}
@@ -0,0 +1,65 @@
+pub mod area;
+pub mod directional;
+pub mod point;
+pub mod spot;
+pub mod sun;
+
+use crate::{math::vec3::Vec3, raytracing::ray::Ray};
+
+pub use self::{
+    area::AreaLight, directional::DirectionalLight, point::PointLight, spot::SpotLight,
+    sun::SunLight,
+};
+
+/// Light sources sampled explicitly with shadow rays.
+/// Unlike emissive materials punctual lights don't have geometry and can't be hit by rays
+pub enum Light {
+    Point(PointLight),
+    Spot(SpotLight),
+    Directional(DirectionalLight),
+    Area(AreaLight),
+    Sun(SunLight),
+}
+
+/// Light arriving to the shaded point
+pub struct LightSample {
+    /// Normalized direction from the shaded point to the light
+    pub direction: Vec3,
+    /// Distance to the light, infinite for directional lights
+    pub distance: f32,
+    pub radiance: Vec3,
+    /// Probability density of the direction over solid angle.
+    /// `None` for punctual lights, which can't be hit by scattered rays
+    pub pdf: Option<f32>,
+}
+
+impl Light {
+    /// Light arriving to `point` at `time`. Returns `None` if the point is not lit
+    pub fn sample(&self, point: &Vec3, time: f32) -> Option<LightSample> {
+        match self {
+            Light::Point(light) => light.sample(point),
+            Light::Spot(light) => light.sample(point),
+            Light::Directional(light) => light.sample(point),
+            Light::Area(light) => light.sample(point, time),
+            Light::Sun(light) => light.sample(point),
+        }
+    }
+
+    /// Probability density of sampling direction of the `ray`, if the surface of the light
+    /// is hit at `distance`. Distant lights are hit at infinite distance
+    pub fn pdf(&self, ray: &Ray, distance: f32) -> f32 {
+        match self {
+            Light::Area(light) => light.pdf(ray, distance),
+            Light::Sun(light) => light.pdf(ray, distance),
+            _ => 0.,
+        }
+    }
+
+    /// Radiance of distant lights seen by the `ray` escaping the scene
+    pub fn escaped_radiance(&self, ray: &Ray) -> Vec3 {
+        match self {
+            Light::Sun(light) => light.radiance(ray),
+            _ => Vec3::zero(),
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use crate::math::vec3::Vec3;
+
+use super::LightSample;
+
+/// Light emitted equally in all directions from single point
+pub struct PointLight {
+    pub position: Vec3,
+    /// Radiant intensity, i.e. light color multiplied by power
+    pub intensity: Vec3,
+}
+
+impl PointLight {
+    pub fn new(position: Vec3, intensity: Vec3) -> Self {
+        Self {
+            position,
+            intensity,
+        }
+    }
+
+    pub fn sample(&self, point: &Vec3) -> Option<LightSample> {
+        let to_light = self.position - *point;
+        let distance_squared = to_light.length_squared();
+        if distance_squared == 0. {
+            return None;
+        }
+        let distance = distance_squared.sqrt();
+        Some(LightSample {
+            direction: &to_light / distance,
+            distance,
+            // Inverse square falloff
+            radiance: (1. / distance_squared) * self.intensity,
+            pdf: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::math::vec3::Vec3;
+
+    use super::PointLight;
+
+    #[test]
+    fn falloff_test() {
+        let light = PointLight::new(Vec3::new(0., 4., 0.), Vec3::new(8., 4., 2.));
+
+        let sample = light.sample(&Vec3::new(0., 2., 0.)).unwrap();
+        assert!((sample.direction - Vec3::new(0., 1., 0.)).length() < 1e-6);
+        assert_eq!(sample.distance, 2.);
+        assert!((sample.radiance - Vec3::new(2., 1., 0.5)).length() < 1e-6);
+        assert!(sample.pdf.is_none());
+
+        // Twice the distance gives a quarter of the radiance
+        let sample = light.sample(&Vec3::new(4., 4., 0.)).unwrap();
+        assert_eq!(sample.distance, 4.);
+        assert!((sample.radiance - Vec3::new(0.5, 0.25, 0.125)).length() < 1e-6);
+
+        assert!(light.sample(&light.position).is_none());
+    }
+}
@@ -0,0 +1,127 @@
+use crate::math::{degrees_to_radians, vec3::Vec3};
+
+use super::LightSample;
+
+/// Point light emitting inside a cone
+pub struct SpotLight {
+    pub position: Vec3,
+    /// Radiant intensity at the cone axis
+    pub intensity: Vec3,
+    direction: Vec3,
+    cos_cone: f32,
+    cos_falloff: f32,
+}
+
+impl SpotLight {
+    /// `cone_angle` - angle in degrees between the axis and the edge of the cone
+    ///
+    /// `falloff_angle` - angle in degrees where intensity starts fading out to the edge
+    pub fn new(
+        position: Vec3,
+        target: Vec3,
+        intensity: Vec3,
+        cone_angle: f32,
+        falloff_angle: f32,
+    ) -> Self {
+        let cos_cone = f32::cos(degrees_to_radians(cone_angle));
+        Self {
+            position,
+            intensity,
+            direction: (target - position).norm(),
+            cos_cone,
+            cos_falloff: f32::cos(degrees_to_radians(falloff_angle)).max(cos_cone),
+        }
+    }
+
+    pub fn sample(&self, point: &Vec3) -> Option<LightSample> {
+        let to_light = self.position - *point;
+        let distance_squared = to_light.length_squared();
+        if distance_squared == 0. {
+            return None;
+        }
+        let distance = distance_squared.sqrt();
+        let direction = &to_light / distance;
+
+        let falloff = self.falloff(-direction.dot(&self.direction));
+        if falloff == 0. {
+            return None;
+        }
+        Some(LightSample {
+            direction,
+            distance,
+            radiance: (falloff / distance_squared) * self.intensity,
+            pdf: None,
+        })
+    }
+
+    /// Smooth transition from full intensity to zero at the cone edge
+    fn falloff(&self, cos_theta: f32) -> f32 {
+        if cos_theta <= self.cos_cone {
+            0.
+        } else if cos_theta >= self.cos_falloff {
+            1.
+        } else {
+            let t = (cos_theta - self.cos_cone) / (self.cos_falloff - self.cos_cone);
+            t * t * (3. - 2. * t)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::math::{degrees_to_radians, vec3::Vec3};
+
+    use super::SpotLight;
+
+    /// Point at `distance` from the light, `angle` degrees off the downward axis
+    fn point(angle: f32, distance: f32) -> Vec3 {
+        let (sin, cos) = degrees_to_radians(angle).sin_cos();
+        Vec3::new(distance * sin, 10. - distance * cos, 0.)
+    }
+
+    #[test]
+    fn cone_test() {
+        let intensity = Vec3::new(4., 4., 4.);
+        let light = SpotLight::new(Vec3::new(0., 10., 0.), Vec3::zero(), intensity, 30., 20.);
+
+        // Inside the falloff angle, inverse square falloff
+        let sample = light.sample(&point(10., 2.)).unwrap();
+        assert!((sample.radiance - Vec3::new(1., 1., 1.)).length() < 1e-5);
+        let sample = light.sample(&point(10., 4.)).unwrap();
+        assert!((sample.radiance - Vec3::new(0.25, 0.25, 0.25)).length() < 1e-5);
+        assert!((sample.direction - (&(light.position - point(10., 4.)) / 4.)).length() < 1e-5);
+
+        // Smoothstep between the falloff angle and the cone edge
+        let cos_cone = degrees_to_radians(30.).cos();
+        let cos_falloff = degrees_to_radians(20.).cos();
+        let angle = f32::acos(0.5 * (cos_cone + cos_falloff)).to_degrees();
+        let sample = light.sample(&point(angle, 2.)).unwrap();
+        assert!(
+            (sample.radiance.x() - 0.5).abs() < 1e-3,
+            "{}",
+            sample.radiance
+        );
+        let edge = light.sample(&point(29., 2.)).unwrap().radiance.x();
+        let band = light.sample(&point(25., 2.)).unwrap().radiance.x();
+        assert!(0. < edge && edge < band && band < 1.);
+
+        // Outside the cone and behind the light
+        assert!(light.sample(&point(31., 2.)).is_none());
+        assert!(light.sample(&Vec3::new(0., 12., 0.)).is_none());
+    }
+
+    #[test]
+    fn falloff_wider_than_cone_test() {
+        // Falloff angle is clamped to the cone, giving a hard edge
+        let light = SpotLight::new(
+            Vec3::new(0., 10., 0.),
+            Vec3::zero(),
+            Vec3::new(1., 1., 1.),
+            30.,
+            45.,
+        );
+        let sample = light.sample(&point(29.9, 1.)).unwrap();
+        assert!((sample.radiance - Vec3::new(1., 1., 1.)).length() < 1e-5);
+        assert!(light.sample(&point(30.1, 1.)).is_none());
+    }
+}
@@ -0,0 +1,123 @@
+use std::f32::consts::PI;
+
+use crate::{
+    math::{degrees_to_radians, onb::Onb, random_in_cone, vec3::Vec3},
+    raytracing::{background::PhysicalSky, ray::Ray},
+};
+
+use super::LightSample;
+
+/// Distant light filling a cone of directions, e.g. the sun disk.
+///
+/// Unlike [`DirectionalLight`](super::DirectionalLight) the disk has finite angular size, so it
+/// casts soft shadows and is seen by rays escaping the scene
+pub struct SunLight {
+    /// Normalized direction towards the center of the disk
+    direction: Vec3,
+    /// Cosine of the angular radius of the disk
+    cos_radius: f32,
+    /// Radiance of the disk
+    pub radiance: Vec3,
+}
+
+impl SunLight {
+    /// `angular_radius` of the disk in degrees
+    pub fn new(direction: Vec3, angular_radius: f32, radiance: Vec3) -> Self {
+        Self {
+            direction: direction.norm(),
+            cos_radius: f32::cos(degrees_to_radians(angular_radius)),
+            radiance,
+        }
+    }
+
+    /// Sun matching the [sky](PhysicalSky), attenuated by its atmosphere
+    pub fn from_sky(sky: &PhysicalSky) -> Self {
+        Self::new(*sky.sun_direction(), sky.sun_radius, sky.sun_radiance())
+    }
+
+    pub fn sample(&self, _: &Vec3) -> Option<LightSample> {
+        if self.radiance.length_squared() == 0. {
+            return None;
+        }
+        let direction = Onb::from_w(&self.direction).to_world(&random_in_cone(self.cos_radius));
+        Some(LightSample {
+            direction: direction.norm(),
+            distance: f32::INFINITY,
+            radiance: self.radiance,
+            pdf: Some(self.cone_pdf()),
+        })
+    }
+
+    /// Radiance arriving along the `ray` from infinity
+    pub fn radiance(&self, ray: &Ray) -> Vec3 {
+        if self.contains(&ray.direction) {
+            self.radiance
+        } else {
+            Vec3::zero()
+        }
+    }
+
+    pub fn pdf(&self, ray: &Ray, distance: f32) -> f32 {
+        if distance.is_infinite()
+            && self.radiance.length_squared() > 0.
+            && self.contains(&ray.direction)
+        {
+            self.cone_pdf()
+        } else {
+            0.
+        }
+    }
+
+    fn contains(&self, direction: &Vec3) -> bool {
+        direction.norm().dot(&self.direction) >= self.cos_radius
+    }
+
+    fn cone_pdf(&self) -> f32 {
+        1. / (2. * PI * (1. - self.cos_radius))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        math::vec3::Vec3,
+        raytracing::{background::PhysicalSky, ray::Ray},
+    };
+
+    use super::SunLight;
+
+    #[test]
+    fn sample_pdf_test() {
+        let sun = SunLight::new(Vec3::new(1., 1., 0.), 0.5, Vec3::new(1., 1., 1.));
+        for _ in 0..100 {
+            let sample = sun.sample(&Vec3::zero()).unwrap();
+            let ray = Ray::new(Vec3::zero(), sample.direction, 0.);
+            assert_eq!(sample.distance, f32::INFINITY);
+            assert!((sun.pdf(&ray, f32::INFINITY) - sample.pdf.unwrap()).abs() < 1e-3);
+            assert!((sun.radiance(&ray) - sample.radiance).length() < 1e-6);
+        }
+
+        // Rays hitting geometry or missing the disk never reach the sun
+        let ray = Ray::new(Vec3::zero(), Vec3::new(1., 1., 0.), 0.);
+        assert_eq!(sun.pdf(&ray, 10.), 0.);
+        let ray = Ray::new(Vec3::zero(), Vec3::new(0., 1., 0.), 0.);
+        assert_eq!(sun.pdf(&ray, f32::INFINITY), 0.);
+        assert!(sun.radiance(&ray).length() == 0.);
+    }
+
+    #[test]
+    fn from_sky_test() {
+        let sky = PhysicalSky::from_angles(30., 0., 3.);
+        let sun = SunLight::from_sky(&sky);
+        let ray = Ray::new(Vec3::zero(), *sky.sun_direction(), 0.);
+        let zenith = sky.radiance(&Vec3::new(0., 1., 0.));
+        // Sun disk is much brighter than the sky
+        assert!(sun.radiance(&ray).y() > 100. * zenith.y());
+        // Sky itself doesn't contain the disk
+        assert!(sky.radiance(sky.sun_direction()).y() < 100. * zenith.y());
+
+        // Sun set below the horizon doesn't light the scene
+        let sky = PhysicalSky::from_angles(-5., 0., 3.);
+        assert!(SunLight::from_sky(&sky).sample(&Vec3::zero()).is_none());
+    }
+}
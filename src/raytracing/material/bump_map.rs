@@ -0,0 +1,181 @@
+use std::sync::Arc;
+
+use crate::{
+    math::{spectrum::SampledWavelengths, vec3::Vec3},
+    raytracing::{
+        ray::Ray,
+        ray_hit::HitResult,
+        texture::{Texture, TextureFunc, UvCoords},
+    },
+};
+
+use super::{BsdfEval, Material, MaterialFunctions, ScatterResult};
+
+/// Perturbs shading normal of the base material as if the surface was displaced
+/// by height texture
+pub struct MatBumpMap {
+    pub base: Arc<Material>,
+    /// Displacement along the normal, uses [scalar](TextureFunc::scalar) value of the texture
+    pub height: Arc<Texture>,
+    /// Displacement distance for unit texture value
+    pub strength: f32,
+}
+
+impl MatBumpMap {
+    /// Smallest offset in texture coordinates for height differences
+    const MIN_DELTA: f32 = 5e-4;
+
+    pub fn new(base: Arc<Material>, height: Arc<Texture>, strength: f32) -> Self {
+        Self {
+            base,
+            height,
+            strength,
+        }
+    }
+
+    pub fn scatter(&self, in_ray: &Ray, hit_result: &HitResult) -> Option<ScatterResult> {
+        self.base
+            .scatter(in_ray, &self.shading_hit(in_ray, hit_result))
+    }
+
+    pub fn scatter_from(
+        &self,
+        in_ray: &Ray,
+        hit_result: &HitResult,
+        exterior_index: f32,
+        wavelengths: Option<&mut SampledWavelengths>,
+    ) -> Option<ScatterResult> {
+        self.base.scatter_from(
+            in_ray,
+            &self.shading_hit(in_ray, hit_result),
+            exterior_index,
+            wavelengths,
+        )
+    }
+
+    pub fn evaluate(
+        &self,
+        in_ray: &Ray,
+        hit_result: &HitResult,
+        direction: &Vec3,
+    ) -> Option<BsdfEval> {
+        self.base
+            .evaluate(in_ray, &self.shading_hit(in_ray, hit_result), direction)
+    }
+
+    pub fn evaluate_from(
+        &self,
+        in_ray: &Ray,
+        hit_result: &HitResult,
+        direction: &Vec3,
+        wavelengths: Option<&SampledWavelengths>,
+    ) -> Option<BsdfEval> {
+        self.base.evaluate_from(
+            in_ray,
+            &self.shading_hit(in_ray, hit_result),
+            direction,
+            wavelengths,
+        )
+    }
+
+    pub fn emitted(&self, hit_result: &HitResult) -> Vec3 {
+        self.base.emitted(hit_result)
+    }
+
+    fn height(&self, hit_result: &HitResult, du: f32, dv: f32) -> f32 {
+        let uv = UvCoords {
+            u: hit_result.uv.u + du,
+            v: hit_result.uv.v + dv,
+            ..hit_result.uv
+        };
+        let point = hit_result.location + du * hit_result.dpdu + dv * hit_result.dpdv;
+        self.strength * self.height.scalar(&uv, &point)
+    }
+
+    fn shading_hit(&self, in_ray: &Ray, hit_result: &HitResult) -> HitResult {
+        // Finite differences over the filter width
+        let delta = f32::max(0.5 * hit_result.uv.footprint, Self::MIN_DELTA);
+        let height = self.height(hit_result, 0., 0.);
+        let dhdu = (self.height(hit_result, delta, 0.) - height) / delta;
+        let dhdv = (self.height(hit_result, 0., delta) - height) / delta;
+
+        // Displacement is along the outward normal
+        let outward = if hit_result.front_face {
+            hit_result.normal
+        } else {
+            -hit_result.normal
+        };
+        let dpdu = hit_result.dpdu + dhdu * outward;
+        let dpdv = hit_result.dpdv + dhdv * outward;
+        let normal = dpdu.cross(&dpdv);
+        if normal.length_squared() < 1e-12 {
+            return hit_result.clone();
+        }
+
+        let normal = if normal.dot(&hit_result.normal) < 0. {
+            -normal
+        } else {
+            normal
+        };
+        MaterialFunctions::with_shading_normal(in_ray, hit_result, &normal)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use image::{DynamicImage, Rgb, Rgb32FImage};
+
+    use crate::{
+        math::vec3::Vec3,
+        raytracing::{
+            material::{MatLabmertian, Material},
+            ray::Ray,
+            ray_hit::HitResult,
+            texture::{ColorSpace, ImageTexture, SolidColorTexture, Texture, UvCoords},
+        },
+    };
+
+    use super::MatBumpMap;
+
+    #[test]
+    fn slope_test() {
+        let base = Arc::new(Material::Labmertian(MatLabmertian {
+            albedo: Arc::new(Texture::SolidColor(SolidColorTexture::new(1., 1., 1.))),
+        }));
+        let hit_result = HitResult {
+            location: Vec3::zero(),
+            normal: Vec3::new(0., 1., 0.),
+            distance: 1.,
+            front_face: true,
+            material: base.clone(),
+            uv: UvCoords::new(0.5, 0.5),
+            dpdu: Vec3::new(1., 0., 0.),
+            dpdv: Vec3::new(0., 0., 1.),
+        };
+        let ray = Ray::new(Vec3::new(0., 1., 0.), Vec3::new(0., -1., 0.), 0.);
+
+        // Constant height keeps the normal
+        let flat = MatBumpMap::new(
+            base.clone(),
+            Arc::new(Texture::SolidColor(SolidColorTexture::new(0.5, 0.5, 0.5))),
+            1.,
+        );
+        let shading = flat.shading_hit(&ray, &hit_result);
+        assert!((shading.normal - hit_result.normal).length() < 1e-5);
+
+        // Height grows along `u` with slope of 45 degrees
+        let gradient = Rgb32FImage::from_fn(256, 1, |x, _| {
+            let value = x as f32 / 255.;
+            Rgb([value, value, value])
+        });
+        let height =
+            ImageTexture::from_image(DynamicImage::ImageRgb32F(gradient), ColorSpace::Linear)
+                .unwrap();
+        let slope = MatBumpMap::new(base, Arc::new(Texture::Image(height)), 1.);
+        let shading = slope.shading_hit(&ray, &hit_result);
+        let expected = Vec3::new(-1., 1., 0.).norm();
+        assert!((shading.normal - expected).length() < 0.01);
+    }
+}
@@ -0,0 +1,192 @@
+use std::sync::Arc;
+
+use rand::{thread_rng, Rng};
+
+use crate::{
+    math::{onb::Onb, vec3::Vec3},
+    raytracing::{ray::Ray, ray_hit::HitResult},
+};
+
+use super::{BsdfEval, Material, MaterialFunctions, ScatterResult, TrowbridgeReitz};
+
+/// Thin dielectric coating, e.g. varnish or lacquer, over arbitrary base material.
+///
+/// Light reflected by the coating is not seen by the base, light scattered by the base
+/// passes the coating on the way in and out
+pub struct MatCoated {
+    pub base: Arc<Material>,
+    pub refraction_index: f32,
+    /// Perceptual roughness of the coating in range [0, 1]
+    pub roughness: f32,
+}
+
+impl MatCoated {
+    pub fn new(base: Arc<Material>, refraction_index: f32, roughness: f32) -> Self {
+        Self {
+            base,
+            refraction_index,
+            roughness: roughness.clamp(0., 1.),
+        }
+    }
+
+    /// Fraction of light reflected by the coating at given cosine with the normal
+    fn reflectance(&self, cos_theta: f32) -> f32 {
+        MaterialFunctions::fresnel_dielectric(cos_theta, self.refraction_index)
+    }
+
+    /// Fraction of light leaving the base in the direction with given cosine.
+    /// Light transmitted into the base, e.g. glass, passes the coating only once
+    fn exit_transmittance(&self, cos_theta: f32) -> f32 {
+        if cos_theta > 0. {
+            1. - self.reflectance(cos_theta)
+        } else {
+            1.
+        }
+    }
+
+    pub fn scatter(&self, in_ray: &Ray, hit_result: &HitResult) -> Option<ScatterResult> {
+        // Coating is on the outer side only
+        if !hit_result.front_face {
+            return self.base.scatter(in_ray, hit_result);
+        }
+
+        let frame = Onb::from_w(&hit_result.normal);
+        let wo = frame.to_local(&-in_ray.direction.norm());
+        let coat_probability = self.reflectance(wo.z());
+        let distribution = TrowbridgeReitz::from_roughness(self.roughness);
+        let mut rng = thread_rng();
+
+        let direction = if rng.gen::<f32>() < coat_probability {
+            if distribution.is_smooth() {
+                let wi = Vec3::new(-wo.x(), -wo.y(), wo.z());
+                return Some(ScatterResult {
+                    attenuation: Vec3::new(1., 1., 1.),
+                    ray: Ray::new(hit_result.location, frame.to_world(&wi), in_ray.time),
+                    pdf: None,
+                });
+            }
+            let wi = distribution.sample_reflection(&wo, rng.gen(), rng.gen());
+            if wi.z() <= 0. {
+                return None;
+            }
+            frame.to_world(&wi)
+        } else {
+            let mut result = self.base.scatter(in_ray, hit_result)?;
+            if result.pdf.is_none() {
+                let cos_theta = result.ray.direction.norm().dot(&hit_result.normal);
+                // Transmittance on the way in cancels out with selection probability
+                result.attenuation *= self.exit_transmittance(cos_theta);
+                return Some(result);
+            }
+            result.ray.direction
+        };
+
+        let eval = self.evaluate(in_ray, hit_result, &direction)?;
+        if eval.pdf == 0. {
+            return None;
+        }
+        Some(ScatterResult {
+            attenuation: &eval.value / eval.pdf,
+            ray: Ray::new(hit_result.location, direction, in_ray.time),
+            pdf: Some(eval.pdf),
+        })
+    }
+
+    pub fn evaluate(
+        &self,
+        in_ray: &Ray,
+        hit_result: &HitResult,
+        direction: &Vec3,
+    ) -> Option<BsdfEval> {
+        if !hit_result.front_face {
+            return self.base.evaluate(in_ray, hit_result, direction);
+        }
+
+        let frame = Onb::from_w(&hit_result.normal);
+        let wo = frame.to_local(&-in_ray.direction.norm());
+        let wi = frame.to_local(&direction.norm());
+        let coat_probability = self.reflectance(wo.z());
+        let distribution = TrowbridgeReitz::from_roughness(self.roughness);
+
+        let coat = (!distribution.is_smooth()).then(|| {
+            let (mut value, pdf) = distribution.reflection(&wo, &wi);
+            if value > 0. {
+                value *= self.reflectance(wo.dot(&(wo + wi).norm()));
+            }
+            BsdfEval {
+                value: Vec3::new(value, value, value),
+                pdf: coat_probability * pdf,
+            }
+        });
+        let base = self
+            .base
+            .evaluate(in_ray, hit_result, direction)
+            .map(|eval| {
+                let transmittance = (1. - coat_probability) * self.exit_transmittance(wi.z());
+                BsdfEval {
+                    value: transmittance * eval.value,
+                    pdf: (1. - coat_probability) * eval.pdf,
+                }
+            });
+
+        match (coat, base) {
+            (Some(coat), Some(base)) => Some(BsdfEval {
+                value: coat.value + base.value,
+                pdf: coat.pdf + base.pdf,
+            }),
+            (coat, base) => coat.or(base),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use crate::{
+        math::vec3::Vec3,
+        raytracing::{
+            material::{MatLabmertian, Material},
+            ray::Ray,
+            ray_hit::HitResult,
+            texture::{SolidColorTexture, Texture, UvCoords},
+        },
+    };
+
+    use super::MatCoated;
+
+    #[test]
+    fn energy_test() {
+        let base = Arc::new(Material::Labmertian(MatLabmertian {
+            albedo: Arc::new(Texture::SolidColor(SolidColorTexture::new(1., 1., 1.))),
+        }));
+        let material = Arc::new(Material::Coated(MatCoated::new(base, 1.5, 0.3)));
+        let hit_result = HitResult {
+            location: Vec3::zero(),
+            normal: Vec3::new(0., 1., 0.),
+            distance: 1.,
+            front_face: true,
+            material: material.clone(),
+            uv: UvCoords::new(0.5, 0.5),
+            dpdu: Vec3::new(1., 0., 0.),
+            dpdv: Vec3::new(0., 0., 1.),
+        };
+        let ray = Ray::new(Vec3::new(-1., 1., 0.), Vec3::new(1., -1., 0.), 0.);
+
+        let samples = 20000;
+        let mut albedo = 0.;
+        for _ in 0..samples {
+            if let Some(result) = material.scatter(&ray, &hit_result) {
+                albedo += result.attenuation.y();
+                // Sampling density is consistent with evaluation
+                let eval = material
+                    .evaluate(&ray, &hit_result, &result.ray.direction)
+                    .unwrap();
+                assert!((eval.pdf - result.pdf.unwrap()).abs() <= 1e-3 * eval.pdf);
+            }
+        }
+        albedo /= samples as f32;
+        // Coating reflects part of the light scattered by the base back inside
+        assert!(albedo > 0.8 && albedo < 1., "{albedo}");
+    }
+}
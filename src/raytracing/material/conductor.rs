@@ -0,0 +1,273 @@
+use std::sync::Arc;
+
+use rand::{thread_rng, Rng};
+
+use crate::{
+    math::{
+        complex::Complex,
+        onb::Onb,
+        spectrum::{spectrum_value, SampledWavelengths},
+        vec3::Vec3,
+    },
+    raytracing::{
+        ray::Ray,
+        ray_hit::HitResult,
+        texture::{Texture, TextureFunc},
+    },
+};
+
+use super::{BsdfEval, MaterialFunctions, ScatterResult, ThinFilm, TrowbridgeReitz};
+
+/// Metal with GGX microfacet distribution and Fresnel reflectance from complex refraction index.
+///
+/// Different roughness along and across the tangent gives stretched highlights
+/// of brushed metal
+pub struct MatConductor {
+    /// Real part of the refraction index for red, green and blue wavelengths
+    pub eta: Vec3,
+    /// Absorption coefficient for red, green and blue wavelengths
+    pub k: Vec3,
+    /// Microfacet roughness along the tangent, zero is a perfect mirror
+    pub alpha_x: f32,
+    /// Microfacet roughness along the bitangent
+    pub alpha_y: f32,
+    /// Tangent direction encoded like a normal map, red and green are tangent and bitangent
+    /// components of the surface frame. Tangent follows `dpdu` of the object if `None`
+    pub flow_map: Option<Arc<Texture>>,
+    /// Coating of the metal, e.g. oxide layer of heated steel
+    pub thin_film: Option<ThinFilm>,
+}
+
+impl MatConductor {
+    /// Isotropic metal with perceptual `roughness` in range [0, 1]
+    pub fn new(eta: Vec3, k: Vec3, roughness: f32) -> Self {
+        let alpha = roughness.clamp(0., 1.).powi(2);
+        Self {
+            eta,
+            k,
+            alpha_x: alpha,
+            alpha_y: alpha,
+            flow_map: None,
+            thin_film: None,
+        }
+    }
+
+    /// Brushed metal with perceptual roughness along and across the tangent
+    pub fn anisotropic(self, roughness_x: f32, roughness_y: f32) -> Self {
+        Self {
+            alpha_x: roughness_x.clamp(0., 1.).powi(2),
+            alpha_y: roughness_y.clamp(0., 1.).powi(2),
+            ..self
+        }
+    }
+
+    pub fn gold(roughness: f32) -> Self {
+        Self::new(
+            Vec3::new(0.143, 0.374, 1.442),
+            Vec3::new(3.983, 2.385, 1.603),
+            roughness,
+        )
+    }
+
+    pub fn copper(roughness: f32) -> Self {
+        Self::new(
+            Vec3::new(0.200, 0.924, 1.102),
+            Vec3::new(3.912, 2.452, 2.142),
+            roughness,
+        )
+    }
+
+    pub fn silver(roughness: f32) -> Self {
+        Self::new(
+            Vec3::new(0.155, 0.117, 0.138),
+            Vec3::new(4.828, 3.122, 2.147),
+            roughness,
+        )
+    }
+
+    pub fn aluminium(roughness: f32) -> Self {
+        Self::new(
+            Vec3::new(1.657, 0.880, 0.521),
+            Vec3::new(9.224, 6.270, 4.837),
+            roughness,
+        )
+    }
+
+    /// Per channel reflectance of the coated or bare metal, at the path `wavelengths`
+    /// in spectral rendering
+    fn fresnel(
+        &self,
+        hit_result: &HitResult,
+        cos_theta_i: f32,
+        wavelengths: Option<&SampledWavelengths>,
+    ) -> Vec3 {
+        let eta = |wavelength| {
+            Complex::new(
+                spectrum_value(&self.eta, wavelength),
+                spectrum_value(&self.k, wavelength),
+            )
+        };
+        match (&self.thin_film, wavelengths) {
+            (Some(thin_film), _) => {
+                thin_film.reflectance(hit_result, cos_theta_i, 1., eta, wavelengths)
+            }
+            (None, Some(wavelengths)) => wavelengths.sample(|wavelength| {
+                MaterialFunctions::fresnel_complex(cos_theta_i, eta(wavelength))
+            }),
+            (None, None) => MaterialFunctions::fresnel_conductor(cos_theta_i, &self.eta, &self.k),
+        }
+    }
+
+    /// Shading frame with `u` axis along the brushing direction
+    fn frame(&self, hit_result: &HitResult) -> Onb {
+        let frame = hit_result.tangent_frame();
+        let Some(flow_map) = &self.flow_map else {
+            return frame;
+        };
+        let encoded = flow_map.value(&hit_result.uv, &hit_result.location);
+        let tangent = frame.to_world(&Vec3::new(2. * encoded.x() - 1., 2. * encoded.y() - 1., 0.));
+        Onb::from_wu(&hit_result.normal, &tangent)
+    }
+
+    pub fn scatter(&self, in_ray: &Ray, hit_result: &HitResult) -> Option<ScatterResult> {
+        self.scatter_spectral(in_ray, hit_result, None)
+    }
+
+    /// Scatter with reflectance at the path `wavelengths` in spectral rendering
+    pub fn scatter_spectral(
+        &self,
+        in_ray: &Ray,
+        hit_result: &HitResult,
+        wavelengths: Option<&SampledWavelengths>,
+    ) -> Option<ScatterResult> {
+        let frame = self.frame(hit_result);
+        let wo = frame.to_local(&-in_ray.direction.norm());
+        if wo.z() <= 0. {
+            return None;
+        }
+
+        let distribution = TrowbridgeReitz::new(self.alpha_x, self.alpha_y);
+        if distribution.is_smooth() {
+            let wi = Vec3::new(-wo.x(), -wo.y(), wo.z());
+            return Some(ScatterResult {
+                attenuation: self.fresnel(hit_result, wo.z(), wavelengths),
+                ray: Ray::new(hit_result.location, frame.to_world(&wi), in_ray.time),
+                pdf: None,
+            });
+        }
+
+        let mut rng = thread_rng();
+        let wm = distribution.sample_wm(&wo, rng.gen(), rng.gen());
+        let wi = MaterialFunctions::reflect(&-wo, &wm);
+        if wi.z() <= 0. {
+            return None;
+        }
+
+        let fresnel = self.fresnel(hit_result, wo.dot(&wm), wavelengths);
+        // BSDF * cos / pdf of visible normal sampling
+        let weight = distribution.g(&wo, &wi) / distribution.g1(&wo);
+        Some(ScatterResult {
+            attenuation: weight * fresnel,
+            ray: Ray::new(hit_result.location, frame.to_world(&wi), in_ray.time),
+            pdf: Some(distribution.d_visible(&wo, &wm) / (4. * wo.dot(&wm))),
+        })
+    }
+
+    /// Returns `None` for perfectly smooth surface
+    pub fn evaluate(
+        &self,
+        in_ray: &Ray,
+        hit_result: &HitResult,
+        direction: &Vec3,
+    ) -> Option<BsdfEval> {
+        self.evaluate_spectral(in_ray, hit_result, direction, None)
+    }
+
+    /// Evaluate with reflectance at the path `wavelengths` in spectral rendering
+    pub fn evaluate_spectral(
+        &self,
+        in_ray: &Ray,
+        hit_result: &HitResult,
+        direction: &Vec3,
+        wavelengths: Option<&SampledWavelengths>,
+    ) -> Option<BsdfEval> {
+        let distribution = TrowbridgeReitz::new(self.alpha_x, self.alpha_y);
+        if distribution.is_smooth() {
+            return None;
+        }
+
+        let frame = self.frame(hit_result);
+        let wo = frame.to_local(&-in_ray.direction.norm());
+        let wi = frame.to_local(&direction.norm());
+        let wm = wo + wi;
+        if wo.z() <= 0. || wi.z() <= 0. || wm.length_squared() == 0. {
+            return Some(BsdfEval {
+                value: Vec3::zero(),
+                pdf: 0.,
+            });
+        }
+        let wm = wm.norm();
+
+        let fresnel = self.fresnel(hit_result, wo.dot(&wm), wavelengths);
+        let value = distribution.d(&wm) * distribution.g(&wo, &wi) / (4. * wo.z());
+        Some(BsdfEval {
+            value: value * fresnel,
+            pdf: distribution.d_visible(&wo, &wm) / (4. * wo.dot(&wm)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use crate::{
+        math::vec3::Vec3,
+        raytracing::{
+            material::Material,
+            ray::Ray,
+            ray_hit::HitResult,
+            texture::{SolidColorTexture, Texture, UvCoords},
+        },
+    };
+
+    use super::MatConductor;
+
+    #[test]
+    fn anisotropy_test() {
+        let brushed = MatConductor::aluminium(0.).anisotropic(0.6, 0.1);
+        let material = Arc::new(Material::Conductor(brushed));
+        let hit_result = HitResult {
+            location: Vec3::zero(),
+            normal: Vec3::new(0., 1., 0.),
+            distance: 1.,
+            front_face: true,
+            material: material.clone(),
+            uv: UvCoords::new(0.5, 0.5),
+            dpdu: Vec3::new(1., 0., 0.),
+            dpdv: Vec3::new(0., 0., 1.),
+        };
+        let ray = Ray::new(Vec3::new(0., 1., 0.), Vec3::new(0., -1., 0.), 0.);
+        let highlight = |direction: Vec3| {
+            let eval = material.evaluate(&ray, &hit_result, &direction).unwrap();
+            eval.value.y()
+        };
+
+        // Highlight is stretched along the tangent
+        let along = highlight(Vec3::new(0.3, 1., 0.));
+        let across = highlight(Vec3::new(0., 1., 0.3));
+        assert!(along > 10. * across, "{along} {across}");
+
+        // Flow map turns the brushing direction to the bitangent
+        let mut brushed = MatConductor::aluminium(0.).anisotropic(0.6, 0.1);
+        brushed.flow_map = Some(Arc::new(Texture::SolidColor(SolidColorTexture::new(
+            0.5, 1., 0.5,
+        ))));
+        let material = Arc::new(Material::Conductor(brushed));
+        let eval = |direction: Vec3| {
+            let eval = material.evaluate(&ray, &hit_result, &direction).unwrap();
+            eval.value.y()
+        };
+        assert!((eval(Vec3::new(0., 1., 0.3)) - along).abs() < 1e-3 * along);
+    }
+}
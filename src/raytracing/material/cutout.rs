@@ -0,0 +1,69 @@
+use std::sync::Arc;
+
+use rand::{thread_rng, Rng};
+
+use crate::{
+    math::vec3::Vec3,
+    raytracing::{
+        ray::Ray,
+        ray_hit::HitResult,
+        texture::{Texture, TextureFunc},
+    },
+};
+
+use super::{BsdfEval, Material, ScatterResult};
+
+/// How opacity selects hits skipped by rays
+pub enum AlphaMode {
+    /// Surface is removed where opacity is below the value
+    Threshold(f32),
+    /// Surface is skipped randomly with probability `1 - opacity`, for soft edges
+    Stochastic,
+}
+
+/// Removes parts of the base material surface by opacity texture, e.g. leaves or fences.
+///
+/// Transparent hits are skipped by objects during traversal, so they affect shadow rays
+/// as well and don't bend rays like dielectrics
+pub struct MatCutout {
+    pub base: Arc<Material>,
+    /// Uses [scalar](TextureFunc::scalar) value of the texture, 0 is fully transparent
+    pub opacity: Arc<Texture>,
+    pub mode: AlphaMode,
+}
+
+impl MatCutout {
+    pub fn new(base: Arc<Material>, opacity: Arc<Texture>) -> Self {
+        Self {
+            base,
+            opacity,
+            mode: AlphaMode::Threshold(0.5),
+        }
+    }
+
+    pub fn is_opaque(&self, hit_result: &HitResult) -> bool {
+        let opacity = self.opacity.scalar(&hit_result.uv, &hit_result.location);
+        let visible = match self.mode {
+            AlphaMode::Threshold(threshold) => opacity >= threshold,
+            AlphaMode::Stochastic => opacity >= 1. || thread_rng().gen::<f32>() < opacity,
+        };
+        visible && self.base.is_opaque(hit_result)
+    }
+
+    pub fn scatter(&self, in_ray: &Ray, hit_result: &HitResult) -> Option<ScatterResult> {
+        self.base.scatter(in_ray, hit_result)
+    }
+
+    pub fn evaluate(
+        &self,
+        in_ray: &Ray,
+        hit_result: &HitResult,
+        direction: &Vec3,
+    ) -> Option<BsdfEval> {
+        self.base.evaluate(in_ray, hit_result, direction)
+    }
+
+    pub fn emitted(&self, hit_result: &HitResult) -> Vec3 {
+        self.base.emitted(hit_result)
+    }
+}
@@ -0,0 +1,56 @@
+/// Dependency of refraction index on the wavelength, used in spectral rendering
+#[derive(Clone, Copy)]
+pub enum Dispersion {
+    /// `n = a + b / λ²` with wavelength in micrometers
+    Cauchy { a: f32, b: f32 },
+    /// `n² = 1 + Σ bᵢλ² / (λ² - cᵢ)` with wavelength in micrometers
+    Sellmeier { b: [f32; 3], c: [f32; 3] },
+}
+
+impl Dispersion {
+    /// Borosilicate crown glass
+    pub const BK7: Dispersion = Dispersion::Sellmeier {
+        b: [1.039_612, 0.231_792_3, 1.010_469_4],
+        c: [0.006_000_7, 0.020_017_914, 103.560_65],
+    };
+
+    pub const DIAMOND: Dispersion = Dispersion::Sellmeier {
+        b: [0.3306, 4.3356, 0.],
+        c: [0.030_625, 0.011_236, 0.],
+    };
+
+    /// Refraction index for `wavelength` in nanometers
+    pub fn refraction_index(&self, wavelength: f32) -> f32 {
+        let micrometers = wavelength * 1e-3;
+        let lambda2 = micrometers * micrometers;
+        match self {
+            Dispersion::Cauchy { a, b } => a + b / lambda2,
+            Dispersion::Sellmeier { b, c } => {
+                let sum: f32 = b
+                    .iter()
+                    .zip(c)
+                    .map(|(b, c)| b * lambda2 / (lambda2 - c))
+                    .sum();
+                f32::sqrt(1. + sum)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Dispersion;
+
+    #[test]
+    fn refraction_index_test() {
+        // Sodium D line
+        let bk7 = Dispersion::BK7.refraction_index(589.3);
+        assert!((bk7 - 1.5168).abs() < 1e-3, "{bk7}");
+        let diamond = Dispersion::DIAMOND.refraction_index(589.3);
+        assert!((diamond - 2.417).abs() < 2e-3, "{diamond}");
+
+        // Blue light is refracted more
+        let cauchy = Dispersion::Cauchy { a: 1.5, b: 0.004 };
+        assert!(cauchy.refraction_index(450.) > cauchy.refraction_index(650.));
+    }
+}
@@ -0,0 +1,43 @@
+use std::sync::Arc;
+
+use crate::{
+    math::vec3::Vec3,
+    raytracing::{
+        ray_hit::HitResult,
+        texture::{Texture, TextureFunc},
+    },
+};
+
+use super::Material;
+
+/// Adds light emission to the base material, e.g. glowing screens or hot metal.
+///
+/// To be sampled explicitly the object has to be also added as
+/// [area light](crate::raytracing::light::AreaLight)
+pub struct MatEmissive {
+    pub base: Arc<Material>,
+    pub emission: Arc<Texture>,
+    /// Emission color multiplier
+    pub strength: f32,
+    /// Emit light only from the front face, i.e. outer side of the object
+    pub one_sided: bool,
+}
+
+impl MatEmissive {
+    pub fn new(base: Arc<Material>, emission: Arc<Texture>, strength: f32) -> Self {
+        Self {
+            base,
+            emission,
+            strength,
+            one_sided: false,
+        }
+    }
+
+    pub fn emitted(&self, hit_result: &HitResult) -> Vec3 {
+        let base = self.base.emitted(hit_result);
+        if self.one_sided && !hit_result.front_face {
+            return base;
+        }
+        base + self.strength * self.emission.value(&hit_result.uv, &hit_result.location)
+    }
+}
@@ -0,0 +1,181 @@
+use std::f32::consts::PI;
+
+use crate::math::vec3::Vec3;
+
+/// Trowbridge-Reitz (GGX) microfacet distribution.
+///
+/// Directions are given in shading space where `z` axis is the surface normal
+pub struct TrowbridgeReitz {
+    pub alpha_x: f32,
+    pub alpha_y: f32,
+}
+
+impl TrowbridgeReitz {
+    /// Distributions with lower roughness are treated as perfectly smooth
+    const SMOOTH_ALPHA: f32 = 1e-3;
+
+    pub fn new(alpha_x: f32, alpha_y: f32) -> Self {
+        Self {
+            alpha_x: alpha_x.max(1e-4),
+            alpha_y: alpha_y.max(1e-4),
+        }
+    }
+
+    /// Isotropic distribution with perceptually linear `roughness` in range [0, 1]
+    pub fn from_roughness(roughness: f32) -> Self {
+        let alpha = roughness * roughness;
+        Self::new(alpha, alpha)
+    }
+
+    /// Surface acts as perfect mirror
+    pub fn is_smooth(&self) -> bool {
+        self.alpha_x.max(self.alpha_y) < Self::SMOOTH_ALPHA
+    }
+
+    /// Density of microfacets with normal `wm`
+    pub fn d(&self, wm: &Vec3) -> f32 {
+        let x = wm.x() / self.alpha_x;
+        let y = wm.y() / self.alpha_y;
+        let denominator = x * x + y * y + wm.z() * wm.z();
+        1. / (PI * self.alpha_x * self.alpha_y * denominator * denominator)
+    }
+
+    /// Smith auxiliary function
+    pub fn lambda(&self, w: &Vec3) -> f32 {
+        let cos2_theta = w.z() * w.z();
+        if cos2_theta == 0. {
+            return f32::INFINITY;
+        }
+        let x = w.x() * self.alpha_x;
+        let y = w.y() * self.alpha_y;
+        let alpha2_tan2_theta = (x * x + y * y) / cos2_theta;
+        0.5 * (f32::sqrt(1. + alpha2_tan2_theta) - 1.)
+    }
+
+    /// Fraction of microfacets visible from direction `w`
+    pub fn g1(&self, w: &Vec3) -> f32 {
+        1. / (1. + self.lambda(w))
+    }
+
+    /// Height correlated masking-shadowing, fraction of microfacets visible from both directions
+    pub fn g(&self, wo: &Vec3, wi: &Vec3) -> f32 {
+        1. / (1. + self.lambda(wo) + self.lambda(wi))
+    }
+
+    /// Density of microfacet normals `wm` visible from direction `w`
+    pub fn d_visible(&self, w: &Vec3, wm: &Vec3) -> f32 {
+        if w.z() == 0. {
+            return 0.;
+        }
+        self.g1(w) / w.z().abs() * self.d(wm) * w.dot(wm).max(0.)
+    }
+
+    /// Microfacet reflection multiplied by cosine without Fresnel term and probability
+    /// density of [sampling](Self::sample_reflection) the direction `wi`
+    pub fn reflection(&self, wo: &Vec3, wi: &Vec3) -> (f32, f32) {
+        let wm = *wo + *wi;
+        if wo.z() <= 0. || wi.z() <= 0. || wm.length_squared() == 0. {
+            return (0., 0.);
+        }
+        let wm = wm.norm();
+        (
+            self.d(&wm) * self.g(wo, wi) / (4. * wo.z()),
+            self.d_visible(wo, &wm) / (4. * wo.dot(&wm)),
+        )
+    }
+
+    /// Reflect `wo` from sampled visible microfacet. Resulting direction may point below
+    /// the surface
+    pub fn sample_reflection(&self, wo: &Vec3, u1: f32, u2: f32) -> Vec3 {
+        let wm = self.sample_wm(wo, u1, u2);
+        2. * wo.dot(&wm) * wm - *wo
+    }
+
+    /// Sample microfacet normal visible from direction `w`
+    /// (Heitz "Sampling the GGX Distribution of Visible Normals")
+    pub fn sample_wm(&self, w: &Vec3, u1: f32, u2: f32) -> Vec3 {
+        // Transform view direction to hemisphere configuration
+        let mut wh = Vec3::new(self.alpha_x * w.x(), self.alpha_y * w.y(), w.z()).norm();
+        if wh.z() < 0. {
+            wh = -wh;
+        }
+
+        let t1 = if wh.z() < 0.99999 {
+            Vec3::new(0., 0., 1.).cross(&wh).norm()
+        } else {
+            Vec3::new(1., 0., 0.)
+        };
+        let t2 = wh.cross(&t1);
+
+        // Uniform point on the disk, warped to the visible part of hemisphere
+        let r = u1.sqrt();
+        let phi = 2. * PI * u2;
+        let p1 = r * phi.cos();
+        let mut p2 = r * phi.sin();
+        let s = 0.5 * (1. + wh.z());
+        p2 = (1. - s) * f32::sqrt(1. - p1 * p1) + s * p2;
+
+        let p3 = f32::sqrt(f32::max(0., 1. - p1 * p1 - p2 * p2));
+        let nh = p1 * t1 + p2 * t2 + p3 * wh;
+        Vec3::new(
+            self.alpha_x * nh.x(),
+            self.alpha_y * nh.y(),
+            f32::max(1e-6, nh.z()),
+        )
+        .norm()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::f32::consts::PI;
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use crate::math::vec3::Vec3;
+
+    use super::TrowbridgeReitz;
+
+    fn hemisphere_integral<F: Fn(&Vec3) -> f32>(func: F) -> f32 {
+        let steps = 400;
+        let mut sum = 0.;
+        for i in 0..steps {
+            let theta = (i as f32 + 0.5) / steps as f32 * PI / 2.;
+            for j in 0..steps {
+                let phi = (j as f32 + 0.5) / steps as f32 * 2. * PI;
+                let w = Vec3::new(
+                    theta.sin() * phi.cos(),
+                    theta.sin() * phi.sin(),
+                    theta.cos(),
+                );
+                sum += func(&w) * theta.sin();
+            }
+        }
+        sum * (PI / 2. / steps as f32) * (2. * PI / steps as f32)
+    }
+
+    #[test]
+    fn normalization_test() {
+        let distribution = TrowbridgeReitz::new(0.5, 0.3);
+        // Projected area of microfacets equals to the macro surface
+        let area = hemisphere_integral(|wm| distribution.d(wm) * wm.z());
+        assert!((area - 1.).abs() < 0.01, "{area}");
+
+        let wo = Vec3::new(0.6, 0.2, 0.7).norm();
+        let visible = hemisphere_integral(|wm| distribution.d_visible(&wo, wm));
+        assert!((visible - 1.).abs() < 0.01, "{visible}");
+    }
+
+    #[test]
+    fn sample_visible_test() {
+        let distribution = TrowbridgeReitz::from_roughness(0.6);
+        let wo = Vec3::new(-0.3, 0.5, 0.8).norm();
+        let mut rng = StdRng::seed_from_u64(5);
+        for _ in 0..100 {
+            let wm = distribution.sample_wm(&wo, rng.gen(), rng.gen());
+            assert!((wm.length() - 1.).abs() < 1e-4);
+            assert!(wm.z() > 0.);
+            assert!(wo.dot(&wm) >= -1e-4);
+        }
+    }
+}
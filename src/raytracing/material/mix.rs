@@ -0,0 +1,162 @@
+use std::sync::Arc;
+
+use rand::{thread_rng, Rng};
+
+use crate::{
+    math::vec3::Vec3,
+    raytracing::{ray::Ray, ray_hit::HitResult, texture::UvCoords},
+};
+
+use super::{BsdfEval, Material, ScalarInput, ScatterResult};
+
+/// Blend of two materials, one of them is selected randomly on each scattering
+pub struct MatMix {
+    pub first: Arc<Material>,
+    pub second: Arc<Material>,
+    /// Probability of selecting the `second` material, in range [0, 1]
+    pub weight: ScalarInput,
+}
+
+impl MatMix {
+    pub fn new(first: Arc<Material>, second: Arc<Material>, weight: ScalarInput) -> Self {
+        Self {
+            first,
+            second,
+            weight,
+        }
+    }
+
+    fn weight(&self, uv_coords: &UvCoords, point: &Vec3) -> f32 {
+        self.weight.value(uv_coords, point).clamp(0., 1.)
+    }
+
+    pub fn scatter(&self, in_ray: &Ray, hit_result: &HitResult) -> Option<ScatterResult> {
+        let weight = self.weight(&hit_result.uv, &hit_result.location);
+        let (chosen, other, probability) = if thread_rng().gen::<f32>() < weight {
+            (&self.second, &self.first, weight)
+        } else {
+            (&self.first, &self.second, 1. - weight)
+        };
+
+        let mut result = chosen.scatter(in_ray, hit_result)?;
+        let Some(pdf) = result.pdf else {
+            // Selection probability cancels out with the weight
+            return Some(result);
+        };
+
+        // Direction could be sampled by either material
+        let value = pdf * result.attenuation;
+        match other.evaluate(in_ray, hit_result, &result.ray.direction) {
+            Some(other) => {
+                let mixed_pdf = probability * pdf + (1. - probability) * other.pdf;
+                // Direction tangent to the surface
+                if mixed_pdf == 0. {
+                    return None;
+                }
+                let mixed_value = probability * value + (1. - probability) * other.value;
+                result.attenuation = &mixed_value / mixed_pdf;
+                result.pdf = Some(mixed_pdf);
+            }
+            None => result.pdf = Some(probability * pdf),
+        }
+        Some(result)
+    }
+
+    pub fn evaluate(
+        &self,
+        in_ray: &Ray,
+        hit_result: &HitResult,
+        direction: &Vec3,
+    ) -> Option<BsdfEval> {
+        let weight = self.weight(&hit_result.uv, &hit_result.location);
+        let first = self.first.evaluate(in_ray, hit_result, direction);
+        let second = self.second.evaluate(in_ray, hit_result, direction);
+
+        let scale = |eval: BsdfEval, weight: f32| BsdfEval {
+            value: weight * eval.value,
+            pdf: weight * eval.pdf,
+        };
+        match (first, second) {
+            (Some(first), Some(second)) => Some(BsdfEval {
+                value: (1. - weight) * first.value + weight * second.value,
+                pdf: (1. - weight) * first.pdf + weight * second.pdf,
+            }),
+            (Some(first), None) => Some(scale(first, 1. - weight)),
+            (None, Some(second)) => Some(scale(second, weight)),
+            (None, None) => None,
+        }
+    }
+
+    /// Opacity of randomly selected material
+    pub fn is_opaque(&self, hit_result: &HitResult) -> bool {
+        let weight = self.weight(&hit_result.uv, &hit_result.location);
+        if thread_rng().gen::<f32>() < weight {
+            self.second.is_opaque(hit_result)
+        } else {
+            self.first.is_opaque(hit_result)
+        }
+    }
+
+    pub fn emitted(&self, hit_result: &HitResult) -> Vec3 {
+        let weight = self.weight(&hit_result.uv, &hit_result.location);
+        (1. - weight) * self.first.emitted(hit_result) + weight * self.second.emitted(hit_result)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use crate::{
+        math::vec3::Vec3,
+        raytracing::{
+            material::{MatLabmertian, MatMetalic, Material},
+            ray::Ray,
+            ray_hit::HitResult,
+            texture::{SolidColorTexture, Texture, UvCoords},
+        },
+    };
+
+    use super::MatMix;
+
+    fn lambertian(albedo: f32) -> Arc<Material> {
+        Arc::new(Material::Labmertian(MatLabmertian {
+            albedo: Arc::new(Texture::SolidColor(SolidColorTexture::new(
+                albedo, albedo, albedo,
+            ))),
+        }))
+    }
+
+    fn albedo(material: Arc<Material>) -> f32 {
+        let hit_result = HitResult {
+            location: Vec3::zero(),
+            normal: Vec3::new(0., 1., 0.),
+            distance: 1.,
+            front_face: true,
+            material: material.clone(),
+            uv: UvCoords::new(0.5, 0.5),
+            dpdu: Vec3::new(1., 0., 0.),
+            dpdv: Vec3::new(0., 0., 1.),
+        };
+        let ray = Ray::new(Vec3::new(-1., 1., 0.), Vec3::new(1., -1., 0.), 0.);
+        let samples = 20000;
+        let sum: f32 = (0..samples)
+            .filter_map(|_| material.scatter(&ray, &hit_result))
+            .map(|result| result.attenuation.y())
+            .sum();
+        sum / samples as f32
+    }
+
+    #[test]
+    fn mix_test() {
+        let mix = Material::Mix(MatMix::new(lambertian(1.), lambertian(0.), 0.25.into()));
+        assert!((albedo(Arc::new(mix)) - 0.75).abs() < 1e-4);
+
+        let metal = Arc::new(Material::Metalic(MatMetalic::new(
+            Vec3::new(1., 1., 1.),
+            0.,
+        )));
+        let mix = Material::Mix(MatMix::new(lambertian(0.5), metal, 0.5.into()));
+        assert!((albedo(Arc::new(mix)) - 0.75).abs() < 0.02);
+    }
+}
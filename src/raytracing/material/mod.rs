@@ -0,0 +1,813 @@
+mod bump_map;
+mod coated;
+mod conductor;
+mod cutout;
+mod dispersion;
+mod emissive;
+mod microfacet;
+mod mix;
+mod normal_map;
+mod oren_nayar;
+mod principled;
+mod rough_dielectric;
+mod sheen;
+mod subsurface;
+mod thin_film;
+
+pub use bump_map::MatBumpMap;
+pub use coated::MatCoated;
+pub use conductor::MatConductor;
+pub use cutout::{AlphaMode, MatCutout};
+pub use dispersion::Dispersion;
+pub use emissive::MatEmissive;
+pub use microfacet::TrowbridgeReitz;
+pub use mix::MatMix;
+pub use normal_map::MatNormalMap;
+pub use oren_nayar::MatOrenNayar;
+pub use principled::MatPrincipled;
+pub use rough_dielectric::MatRoughDielectric;
+pub use sheen::MatSheen;
+pub use subsurface::MatSubsurface;
+pub use thin_film::ThinFilm;
+
+use std::{f32::consts::PI, sync::Arc};
+
+use rand::{thread_rng, Rng};
+
+/// TODO: Think about different design structure
+use crate::math::{
+    complex::Complex,
+    random_in_unit_sphere,
+    spectrum::{path_color, SampledWavelengths},
+    vec3::Vec3,
+};
+
+use super::{
+    medium::Medium,
+    ray::Ray,
+    ray_hit::HitResult,
+    texture::{Texture, TextureFunc, UvCoords},
+};
+
+pub enum Material {
+    Labmertian(MatLabmertian),
+    Metalic(MatMetalic),
+    Dielectric(MatDielectric),
+    DiffuseLight(MatDiffuseLight),
+    Conductor(MatConductor),
+    RoughDielectric(MatRoughDielectric),
+    Principled(MatPrincipled),
+    Mix(MatMix),
+    Coated(MatCoated),
+    NormalMap(MatNormalMap),
+    BumpMap(MatBumpMap),
+    Cutout(MatCutout),
+    Emissive(MatEmissive),
+    Subsurface(MatSubsurface),
+    OrenNayar(MatOrenNayar),
+    Sheen(MatSheen),
+}
+
+pub struct MatLabmertian {
+    pub albedo: Arc<Texture>,
+}
+
+pub struct MatMetalic {
+    pub albedo: Vec3,
+    pub roughness: f32,
+}
+
+pub struct MatDielectric {
+    pub refraction_index: f32,
+    /// Absorption inside of the object, see [Medium::absorption_from_color]
+    pub absorption: Vec3,
+    /// Color of light entering the object through the surface
+    pub tint: Vec3,
+    /// Priority of the interior [medium](Medium) for nested dielectrics
+    pub priority: u32,
+    /// Wavelength dependent refraction index, replaces `refraction_index` in spectral
+    /// rendering
+    pub dispersion: Option<Dispersion>,
+    /// Coating on the outer side of the surface
+    pub thin_film: Option<ThinFilm>,
+}
+
+pub struct MatDiffuseLight {
+    pub emit: Arc<Texture>,
+}
+
+/// Scalar material parameter, either constant or driven by a texture
+pub enum ScalarInput {
+    Value(f32),
+    /// Uses [scalar](TextureFunc::scalar) value of the texture
+    Texture(Arc<Texture>),
+}
+
+pub struct ScatterResult {
+    pub attenuation: Vec3,
+    pub ray: Ray,
+    /// Probability density of the scattered direction over solid angle.
+    /// `None` for specular scattering, which can't be evaluated for arbitrary directions
+    pub pdf: Option<f32>,
+}
+
+/// Scattering from the incoming ray to the given direction
+pub struct BsdfEval {
+    /// BSDF multiplied by cosine between the normal and the direction
+    pub value: Vec3,
+    /// Probability density of [scattering](Material::scatter) to the direction
+    pub pdf: f32,
+}
+
+impl Material {
+    pub fn scatter(&self, ray: &Ray, hit_result: &HitResult) -> Option<ScatterResult> {
+        match self {
+            Material::Labmertian(mat) => mat.scatter(ray, hit_result),
+            Material::Metalic(mat) => mat.scatter(ray, hit_result),
+            Material::Dielectric(mat) => mat.scatter(ray, hit_result),
+            Material::DiffuseLight(_) => None,
+            Material::Conductor(mat) => mat.scatter(ray, hit_result),
+            Material::RoughDielectric(mat) => mat.scatter(ray, hit_result),
+            Material::Principled(mat) => mat.scatter(ray, hit_result),
+            Material::Mix(mat) => mat.scatter(ray, hit_result),
+            Material::Coated(mat) => mat.scatter(ray, hit_result),
+            Material::NormalMap(mat) => mat.scatter(ray, hit_result),
+            Material::BumpMap(mat) => mat.scatter(ray, hit_result),
+            Material::Cutout(mat) => mat.scatter(ray, hit_result),
+            Material::Emissive(mat) => mat.base.scatter(ray, hit_result),
+            Material::Subsurface(mat) => mat.scatter(ray, hit_result),
+            Material::OrenNayar(mat) => mat.scatter(ray, hit_result),
+            Material::Sheen(mat) => mat.scatter(ray, hit_result),
+        }
+    }
+
+    /// Evaluate scattering of `ray` into `direction`, used for explicit light sampling.
+    /// Returns `None` for materials with specular scattering only
+    pub fn evaluate(
+        &self,
+        ray: &Ray,
+        hit_result: &HitResult,
+        direction: &Vec3,
+    ) -> Option<BsdfEval> {
+        match self {
+            Material::Labmertian(mat) => Some(mat.evaluate(ray, hit_result, direction)),
+            Material::Conductor(mat) => mat.evaluate(ray, hit_result, direction),
+            Material::RoughDielectric(mat) => mat.evaluate(ray, hit_result, direction),
+            Material::Principled(mat) => Some(mat.evaluate(ray, hit_result, direction)),
+            Material::Mix(mat) => mat.evaluate(ray, hit_result, direction),
+            Material::Coated(mat) => mat.evaluate(ray, hit_result, direction),
+            Material::NormalMap(mat) => mat.evaluate(ray, hit_result, direction),
+            Material::BumpMap(mat) => mat.evaluate(ray, hit_result, direction),
+            Material::Cutout(mat) => mat.evaluate(ray, hit_result, direction),
+            Material::Emissive(mat) => mat.base.evaluate(ray, hit_result, direction),
+            Material::Subsurface(mat) => Some(mat.evaluate(ray, hit_result, direction)),
+            Material::OrenNayar(mat) => Some(mat.evaluate(ray, hit_result, direction)),
+            Material::Sheen(mat) => Some(mat.evaluate(ray, hit_result, direction)),
+            Material::Metalic(_) | Material::Dielectric(_) | Material::DiffuseLight(_) => None,
+        }
+    }
+
+    /// Evaluate scattering into `direction` with value at the path `wavelengths`
+    /// in spectral rendering
+    pub fn evaluate_from(
+        &self,
+        ray: &Ray,
+        hit_result: &HitResult,
+        direction: &Vec3,
+        wavelengths: Option<&SampledWavelengths>,
+    ) -> Option<BsdfEval> {
+        match self {
+            Material::Conductor(mat) => {
+                mat.evaluate_spectral(ray, hit_result, direction, wavelengths)
+            }
+            Material::NormalMap(mat) => mat.evaluate_from(ray, hit_result, direction, wavelengths),
+            Material::BumpMap(mat) => mat.evaluate_from(ray, hit_result, direction, wavelengths),
+            Material::Cutout(mat) => {
+                mat.base
+                    .evaluate_from(ray, hit_result, direction, wavelengths)
+            }
+            Material::Emissive(mat) => {
+                mat.base
+                    .evaluate_from(ray, hit_result, direction, wavelengths)
+            }
+            _ => {
+                let eval = self.evaluate(ray, hit_result, direction)?;
+                Some(BsdfEval {
+                    value: path_color(wavelengths, &eval.value),
+                    ..eval
+                })
+            }
+        }
+    }
+
+    /// Scatter the `ray` at the boundary with medium of `exterior_index` refraction index
+    /// on the outer side, used for nested dielectrics.
+    ///
+    /// `wavelengths` - wavelengths of the path in spectral rendering. Attenuation is
+    /// the value at them, dispersive materials terminate the secondary ones
+    pub fn scatter_from(
+        &self,
+        ray: &Ray,
+        hit_result: &HitResult,
+        exterior_index: f32,
+        wavelengths: Option<&mut SampledWavelengths>,
+    ) -> Option<ScatterResult> {
+        match self {
+            Material::Dielectric(mat) => {
+                mat.scatter_from(ray, hit_result, exterior_index, wavelengths)
+            }
+            Material::Conductor(mat) => {
+                mat.scatter_spectral(ray, hit_result, wavelengths.as_deref())
+            }
+            Material::NormalMap(mat) => {
+                mat.scatter_from(ray, hit_result, exterior_index, wavelengths)
+            }
+            Material::BumpMap(mat) => {
+                mat.scatter_from(ray, hit_result, exterior_index, wavelengths)
+            }
+            Material::Cutout(mat) => {
+                mat.base
+                    .scatter_from(ray, hit_result, exterior_index, wavelengths)
+            }
+            Material::Emissive(mat) => {
+                mat.base
+                    .scatter_from(ray, hit_result, exterior_index, wavelengths)
+            }
+            _ => {
+                let result = self.scatter(ray, hit_result)?;
+                Some(ScatterResult {
+                    attenuation: path_color(wavelengths.as_deref(), &result.attenuation),
+                    ..result
+                })
+            }
+        }
+    }
+
+    /// Medium enclosed by the surface, if the material is transparent, with id of the
+    /// material defining it. Wrapped materials share the medium and id of their base
+    pub fn medium(&self) -> Option<(usize, Medium)> {
+        let id = self as *const Self as usize;
+        match self {
+            Material::Dielectric(mat) => Some((id, mat.medium())),
+            Material::Subsurface(mat) => Some((id, mat.medium())),
+            Material::Coated(mat) => mat.base.medium(),
+            Material::NormalMap(mat) => mat.base.medium(),
+            Material::BumpMap(mat) => mat.base.medium(),
+            Material::Cutout(mat) => mat.base.medium(),
+            Material::Emissive(mat) => mat.base.medium(),
+            _ => None,
+        }
+    }
+
+    /// Whether the surface exists at the hit. Objects skip hits with transparent
+    /// [cutout](MatCutout) materials
+    pub fn is_opaque(&self, hit_result: &HitResult) -> bool {
+        match self {
+            Material::Cutout(mat) => mat.is_opaque(hit_result),
+            Material::Coated(mat) => mat.base.is_opaque(hit_result),
+            Material::NormalMap(mat) => mat.base.is_opaque(hit_result),
+            Material::BumpMap(mat) => mat.base.is_opaque(hit_result),
+            Material::Emissive(mat) => mat.base.is_opaque(hit_result),
+            Material::Mix(mat) => mat.is_opaque(hit_result),
+            _ => true,
+        }
+    }
+
+    /// Light emitted by the surface at the hit towards the incoming ray
+    pub fn emitted(&self, hit_result: &HitResult) -> Vec3 {
+        const NO_EMIT_COLOR: Vec3 = Vec3::new(0., 0., 0.);
+
+        match self {
+            Material::DiffuseLight(mat) => mat.emitted(&hit_result.uv, &hit_result.location),
+            Material::Emissive(mat) => mat.emitted(hit_result),
+            Material::Mix(mat) => mat.emitted(hit_result),
+            Material::Coated(mat) => mat.base.emitted(hit_result),
+            Material::NormalMap(mat) => mat.emitted(hit_result),
+            Material::BumpMap(mat) => mat.emitted(hit_result),
+            Material::Cutout(mat) => mat.emitted(hit_result),
+            _ => NO_EMIT_COLOR,
+        }
+    }
+}
+
+impl ScalarInput {
+    pub fn value(&self, uv_coords: &UvCoords, point: &Vec3) -> f32 {
+        match self {
+            ScalarInput::Value(value) => *value,
+            ScalarInput::Texture(texture) => texture.scalar(uv_coords, point),
+        }
+    }
+}
+
+impl From<f32> for ScalarInput {
+    fn from(value: f32) -> Self {
+        ScalarInput::Value(value)
+    }
+}
+
+impl From<Arc<Texture>> for ScalarInput {
+    fn from(texture: Arc<Texture>) -> Self {
+        ScalarInput::Texture(texture)
+    }
+}
+
+impl MatLabmertian {
+    pub fn scatter(&self, in_ray: &Ray, hit_result: &HitResult) -> Option<ScatterResult> {
+        let mut scatter_direction = hit_result.normal + random_in_unit_sphere().norm();
+        // Random vector is opposite to the normal
+        if scatter_direction.length_squared() < 1e-8 {
+            scatter_direction = hit_result.normal;
+        }
+        let scattered = Ray::new(hit_result.location, scatter_direction, in_ray.time);
+        Some(ScatterResult {
+            attenuation: self.albedo.value(&hit_result.uv, &hit_result.location),
+            pdf: Some(f32::max(hit_result.normal.dot(&scatter_direction.norm()), 0.) / PI),
+            ray: scattered,
+        })
+    }
+
+    pub fn evaluate(&self, _: &Ray, hit_result: &HitResult, direction: &Vec3) -> BsdfEval {
+        let cos_theta = f32::max(hit_result.normal.dot(&direction.norm()), 0.);
+        let albedo = self.albedo.value(&hit_result.uv, &hit_result.location);
+        BsdfEval {
+            value: (cos_theta / PI) * albedo,
+            pdf: cos_theta / PI,
+        }
+    }
+}
+
+impl MatMetalic {
+    pub fn new(albedo: Vec3, roughness: f32) -> Self {
+        Self {
+            albedo,
+            roughness: roughness.clamp(-1., 1.),
+        }
+    }
+
+    pub fn scatter(&self, in_ray: &Ray, hit_result: &HitResult) -> Option<ScatterResult> {
+        let reflection = MaterialFunctions::reflect(&in_ray.direction, &hit_result.normal);
+        let scattered = Ray::new(
+            hit_result.location,
+            reflection + self.roughness * random_in_unit_sphere(),
+            in_ray.time,
+        );
+
+        if scattered.direction.dot(&hit_result.normal) > 0. {
+            Some(ScatterResult {
+                attenuation: self.albedo,
+                ray: scattered,
+                pdf: None,
+            })
+        } else {
+            None
+        }
+    }
+}
+
+impl MatDielectric {
+    pub fn new(refraction_index: f32) -> Self {
+        Self {
+            refraction_index,
+            absorption: Vec3::zero(),
+            tint: Vec3::new(1., 1., 1.),
+            priority: 0,
+            dispersion: None,
+            thin_film: None,
+        }
+    }
+
+    pub fn medium(&self) -> Medium {
+        Medium {
+            refraction_index: self.refraction_index,
+            absorption: self.absorption,
+            scattering: Vec3::zero(),
+            priority: self.priority,
+        }
+    }
+
+    pub fn scatter(&self, in_ray: &Ray, hit_result: &HitResult) -> Option<ScatterResult> {
+        self.scatter_from(in_ray, hit_result, 1., None)
+    }
+
+    pub fn scatter_from(
+        &self,
+        in_ray: &Ray,
+        hit_result: &HitResult,
+        exterior_index: f32,
+        mut wavelengths: Option<&mut SampledWavelengths>,
+    ) -> Option<ScatterResult> {
+        let refraction_index = match (&self.dispersion, wavelengths.as_deref_mut()) {
+            (Some(dispersion), Some(wavelengths)) => {
+                wavelengths.terminate_secondary();
+                dispersion.refraction_index(wavelengths.hero())
+            }
+            _ => self.refraction_index,
+        };
+        let wavelengths = wavelengths.as_deref();
+        let (incident_index, transmitted_index) = if hit_result.front_face {
+            (exterior_index, refraction_index)
+        } else {
+            (refraction_index, exterior_index)
+        };
+        if let Some(thin_film) = &self.thin_film {
+            let indices = (incident_index, transmitted_index);
+            return self.scatter_thin_film(in_ray, hit_result, thin_film, indices, wavelengths);
+        }
+        let refraction_ratio = incident_index / transmitted_index;
+
+        let refracted = MaterialFunctions::refract(
+            &in_ray.direction.norm(),
+            &hit_result.normal,
+            refraction_ratio,
+        );
+        let transmitted = refracted.dot(&hit_result.normal) < 0.;
+        Some(ScatterResult {
+            attenuation: if transmitted && hit_result.front_face {
+                path_color(wavelengths, &self.tint)
+            } else {
+                Vec3::new(1., 1., 1.)
+            },
+            ray: Ray::new(hit_result.location, refracted, in_ray.time),
+            pdf: None,
+        })
+    }
+
+    /// Reflection or refraction chosen by average reflectance of the coated surface.
+    /// Film doesn't absorb, so the rest of light is transmitted
+    fn scatter_thin_film(
+        &self,
+        in_ray: &Ray,
+        hit_result: &HitResult,
+        thin_film: &ThinFilm,
+        (incident_index, transmitted_index): (f32, f32),
+        wavelengths: Option<&SampledWavelengths>,
+    ) -> Option<ScatterResult> {
+        let wo = -in_ray.direction.norm();
+        let cos_theta_i = wo.dot(&hit_result.normal);
+        // Film is on the outer side, between the exterior and the object
+        let reflectance = thin_film.reflectance(
+            hit_result,
+            cos_theta_i,
+            incident_index,
+            |_| Complex::from(transmitted_index),
+            wavelengths,
+        );
+
+        let reflected = MaterialFunctions::reflect(&-wo, &hit_result.normal);
+        let white = Vec3::new(1., 1., 1.);
+        let tint = if hit_result.front_face {
+            path_color(wavelengths, &self.tint)
+        } else {
+            white
+        };
+        let probability = (reflectance.x() + reflectance.y() + reflectance.z()) / 3.;
+        let (direction, attenuation) = match MaterialFunctions::transmit(
+            &wo,
+            &hit_result.normal,
+            transmitted_index / incident_index,
+        ) {
+            Some(refracted) if thread_rng().gen::<f32>() >= probability => (
+                refracted,
+                &(tint * (white - reflectance)) / (1. - probability),
+            ),
+            Some(_) => (reflected, &reflectance / probability),
+            None => (reflected, white),
+        };
+        Some(ScatterResult {
+            attenuation,
+            ray: Ray::new(hit_result.location, direction, in_ray.time),
+            pdf: None,
+        })
+    }
+}
+
+impl MatDiffuseLight {
+    pub fn emitted(&self, uv_coords: &UvCoords, point: &Vec3) -> Vec3 {
+        self.emit.value(uv_coords, point)
+    }
+}
+
+struct MaterialFunctions;
+
+impl MaterialFunctions {
+    /// `normal` must be normalized
+    fn reflect(v: &Vec3, normal: &Vec3) -> Vec3 {
+        *v - 2. * v.dot(normal) * normal
+    }
+
+    fn refract(v: &Vec3, normal: &Vec3, refraction_ratio: f32) -> Vec3 {
+        let cos_theta = f32::min((-v).dot(normal), 1.);
+        let sin_theta = f32::sqrt(1. - cos_theta * cos_theta);
+
+        let cannot_refract = refraction_ratio * sin_theta > 1.0;
+
+        let mut rng = thread_rng();
+        if cannot_refract
+            || MaterialFunctions::reflectance(cos_theta, refraction_ratio) > rng.gen::<f32>()
+        {
+            MaterialFunctions::reflect(v, normal)
+        } else {
+            let ray_out_perpendicular = refraction_ratio * (v + &(cos_theta * normal));
+            let ray_out_parallel =
+                -f32::sqrt(f32::abs(1.0 - ray_out_perpendicular.length_squared())) * normal;
+            ray_out_parallel + ray_out_perpendicular
+        }
+    }
+
+    fn reflectance(cos_theta: f32, refraction_ratio: f32) -> f32 {
+        // Use Schlick's approximation for reflectance.
+        let mut r0 = (1. - refraction_ratio) / (1. + refraction_ratio);
+        r0 *= r0;
+        r0 + (1. - r0) * (1. - cos_theta).powi(5)
+    }
+
+    /// Copy of the hit with replaced shading `normal`. Normal is bent towards the incoming
+    /// ray if the ray would arrive from below the shading surface
+    fn with_shading_normal(ray: &Ray, hit_result: &HitResult, normal: &Vec3) -> HitResult {
+        const MIN_COSINE: f32 = 0.01;
+        let normal = normal.norm();
+        let wo = -ray.direction.norm();
+        let cos_theta = normal.dot(&wo);
+        let normal = if cos_theta < MIN_COSINE {
+            (normal + (MIN_COSINE - cos_theta) * wo).norm()
+        } else {
+            normal
+        };
+        HitResult {
+            normal,
+            ..hit_result.clone()
+        }
+    }
+
+    /// Exact Fresnel reflectance of dielectric interface.
+    /// `eta` is ratio of refraction indices, transmitted side over incident side
+    fn fresnel_dielectric(cos_theta_i: f32, eta: f32) -> f32 {
+        let mut cos_theta_i = cos_theta_i.clamp(-1., 1.);
+        let mut eta = eta;
+        // Incident direction is on the inner side
+        if cos_theta_i < 0. {
+            eta = 1. / eta;
+            cos_theta_i = -cos_theta_i;
+        }
+
+        let sin2_theta_t = (1. - cos_theta_i * cos_theta_i) / (eta * eta);
+        // Total internal reflection
+        if sin2_theta_t >= 1. {
+            return 1.;
+        }
+        let cos_theta_t = f32::sqrt(1. - sin2_theta_t);
+
+        let r_parallel = (eta * cos_theta_i - cos_theta_t) / (eta * cos_theta_i + cos_theta_t);
+        let r_perpendicular = (cos_theta_i - eta * cos_theta_t) / (cos_theta_i + eta * cos_theta_t);
+        0.5 * (r_parallel * r_parallel + r_perpendicular * r_perpendicular)
+    }
+
+    /// Fresnel reflectance of interface with complex refraction index, e.g. conductor
+    fn fresnel_complex(cos_theta_i: f32, eta: Complex) -> f32 {
+        let cos_theta_i = cos_theta_i.clamp(0., 1.);
+        let sin2_theta_i = 1. - cos_theta_i * cos_theta_i;
+        let sin2_theta_t = Complex::from(sin2_theta_i) / (eta * eta);
+        let cos_theta_t = (Complex::from(1.) - sin2_theta_t).sqrt();
+
+        let cos_i = Complex::from(cos_theta_i);
+        let r_parallel = (eta * cos_i - cos_theta_t) / (eta * cos_i + cos_theta_t);
+        let r_perpendicular = (cos_i - eta * cos_theta_t) / (cos_i + eta * cos_theta_t);
+        0.5 * (r_parallel.norm() + r_perpendicular.norm())
+    }
+
+    /// Reflectance of interface coated with thin film of `film_index` refraction index and
+    /// `thickness` in nanometers, for light of `wavelength` in nanometers coming from medium
+    /// with `exterior_index`. Reflections from both sides of the film interfere
+    /// (Airy summation)
+    fn fresnel_thin_film(
+        cos_theta_i: f32,
+        exterior_index: f32,
+        film_index: f32,
+        thickness: f32,
+        substrate: Complex,
+        wavelength: f32,
+    ) -> f32 {
+        let cos_theta_i = cos_theta_i.clamp(0., 1.);
+        let sin2_theta_i = 1. - cos_theta_i * cos_theta_i;
+        let relative_index = exterior_index / film_index;
+        let sin2_theta_f = sin2_theta_i * relative_index * relative_index;
+        // Total internal reflection on the top of the film
+        if sin2_theta_f >= 1. {
+            return 1.;
+        }
+        let cos_theta_f = f32::sqrt(1. - sin2_theta_f);
+        let sin2_theta_t =
+            Complex::from(sin2_theta_i * exterior_index * exterior_index) / (substrate * substrate);
+        let cos_theta_t = (Complex::from(1.) - sin2_theta_t).sqrt();
+
+        let n1 = Complex::from(exterior_index);
+        let n2 = Complex::from(film_index);
+        let n3 = substrate;
+        let cos_i = Complex::from(cos_theta_i);
+        let cos_f = Complex::from(cos_theta_f);
+        let cos_t = cos_theta_t;
+
+        // Phase difference of the wave reflected inside of the film
+        let phase = 4. * PI * film_index * thickness * cos_theta_f / wavelength;
+        let delay = Complex::from_phase(phase);
+        let airy = |r12: Complex, r23: Complex| {
+            let r23 = r23 * delay;
+            ((r12 + r23) / (Complex::from(1.) + r12 * r23)).norm()
+        };
+        let r_perpendicular = airy(
+            (n1 * cos_i - n2 * cos_f) / (n1 * cos_i + n2 * cos_f),
+            (n2 * cos_f - n3 * cos_t) / (n2 * cos_f + n3 * cos_t),
+        );
+        let r_parallel = airy(
+            (n2 * cos_i - n1 * cos_f) / (n2 * cos_i + n1 * cos_f),
+            (n3 * cos_f - n2 * cos_t) / (n3 * cos_f + n2 * cos_t),
+        );
+        0.5 * (r_perpendicular + r_parallel)
+    }
+
+    /// Per channel Fresnel reflectance of conductor with refraction index `eta`
+    /// and absorption coefficient `k`
+    fn fresnel_conductor(cos_theta_i: f32, eta: &Vec3, k: &Vec3) -> Vec3 {
+        let channel = |i: usize| Self::fresnel_complex(cos_theta_i, Complex::new(eta[i], k[i]));
+        Vec3::new(channel(0), channel(1), channel(2))
+    }
+
+    /// Refract direction `w` pointing away from the surface through interface with `normal`
+    /// on the same side. `eta` is ratio of refraction indices, transmitted side over
+    /// incident side. Returns `None` on total internal reflection
+    fn transmit(w: &Vec3, normal: &Vec3, eta: f32) -> Option<Vec3> {
+        let cos_theta_i = normal.dot(w);
+        let sin2_theta_i = f32::max(0., 1. - cos_theta_i * cos_theta_i);
+        let sin2_theta_t = sin2_theta_i / (eta * eta);
+        if sin2_theta_t >= 1. {
+            return None;
+        }
+        let cos_theta_t = f32::sqrt(1. - sin2_theta_t);
+        Some(&(-w) / eta + (cos_theta_i / eta - cos_theta_t) * normal)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use crate::{
+        math::{
+            complex::Complex,
+            spectrum::{spectrum_value, SampledWavelengths},
+            vec3::Vec3,
+        },
+        raytracing::{
+            ray::Ray,
+            ray_hit::HitResult,
+            texture::{SolidColorTexture, Texture, UvCoords},
+        },
+    };
+
+    use super::{
+        conductor::MatConductor, MatDielectric, MatLabmertian, Material, MaterialFunctions,
+        ThinFilm,
+    };
+
+    #[test]
+    fn fresnel_dielectric_test() {
+        // Glass at normal incidence reflects 4%
+        assert!((MaterialFunctions::fresnel_dielectric(1., 1.5) - 0.04).abs() < 1e-4);
+        // Grazing angle reflects everything
+        assert!((MaterialFunctions::fresnel_dielectric(0., 1.5) - 1.).abs() < 1e-4);
+        // Total internal reflection from the inside
+        assert_eq!(MaterialFunctions::fresnel_dielectric(-0.3, 1.5), 1.);
+    }
+
+    #[test]
+    fn fresnel_thin_film_test() {
+        let glass = Complex::from(1.5);
+        // Film of zero thickness or matching the exterior doesn't change reflectance
+        for cos_theta in [1., 0.7, 0.2] {
+            let expected = MaterialFunctions::fresnel_dielectric(cos_theta, 1.5);
+            let empty = MaterialFunctions::fresnel_thin_film(cos_theta, 1., 1.33, 0., glass, 550.);
+            let air = MaterialFunctions::fresnel_thin_film(cos_theta, 1., 1., 300., glass, 550.);
+            assert!((empty - expected).abs() < 1e-4, "{empty} {expected}");
+            assert!((air - expected).abs() < 1e-4, "{air} {expected}");
+        }
+
+        // Quarter wave anti-reflective coating
+        let film_index = f32::sqrt(1.5);
+        let thickness = 550. / (4. * film_index);
+        let reflectance =
+            MaterialFunctions::fresnel_thin_film(1., 1., film_index, thickness, glass, 550.);
+        assert!(reflectance < 1e-4, "{reflectance}");
+    }
+
+    #[test]
+    fn fresnel_conductor_test() {
+        let gold = MatConductor::gold(0.);
+        let reflectance = MaterialFunctions::fresnel_conductor(1., &gold.eta, &gold.k);
+        // Gold is yellow
+        assert!(reflectance.x() > 0.9 && reflectance.x() > reflectance.z());
+        assert!(reflectance.z() < 0.5);
+
+        // Conductor without absorption matches dielectric
+        let reflectance =
+            MaterialFunctions::fresnel_conductor(0.5, &Vec3::new(1.5, 1.5, 1.5), &Vec3::zero());
+        let expected = MaterialFunctions::fresnel_dielectric(0.5, 1.5);
+        assert!((reflectance.x() - expected).abs() < 1e-5);
+    }
+
+    #[test]
+    fn transmit_test() {
+        let normal = Vec3::new(0., 0., 1.);
+        let w = Vec3::new(1., 0., 1.).norm();
+        let refracted = MaterialFunctions::transmit(&w, &normal, 1.5).unwrap();
+        assert!((refracted.length() - 1.).abs() < 1e-5);
+        // Snell's law
+        assert!((-refracted.x() * 1.5 - w.x()).abs() < 1e-5);
+        assert!(MaterialFunctions::transmit(&w, &normal, 1. / 1.5).is_none());
+    }
+
+    #[test]
+    fn tint_test() {
+        // Matching refraction index always transmits
+        let mut glass = MatDielectric::new(1.);
+        glass.tint = Vec3::new(0.9, 0.6, 0.5);
+        let front = HitResult {
+            location: Vec3::zero(),
+            normal: Vec3::new(0., 1., 0.),
+            distance: 1.,
+            front_face: true,
+            material: Arc::new(Material::Dielectric(MatDielectric::new(1.))),
+            uv: UvCoords::new(0.5, 0.5),
+            dpdu: Vec3::new(1., 0., 0.),
+            dpdv: Vec3::new(0., 0., 1.),
+        };
+        let ray = Ray::new(Vec3::new(0., 1., 0.), Vec3::new(0., -1., 0.), 0.);
+        let entering = glass.scatter(&ray, &front).unwrap();
+        assert!((entering.attenuation - glass.tint).length() < 1e-6);
+
+        // Light is tinted once per passage through the object
+        let back = HitResult {
+            front_face: false,
+            ..front
+        };
+        let leaving = glass.scatter(&ray, &back).unwrap();
+        assert!((leaving.attenuation - Vec3::new(1., 1., 1.)).length() < 1e-6);
+    }
+    #[test]
+    fn spectral_scatter_test() {
+        let hit_result = |material: Material| HitResult {
+            location: Vec3::zero(),
+            normal: Vec3::new(0., 1., 0.),
+            distance: 1.,
+            front_face: true,
+            material: Arc::new(material),
+            uv: UvCoords::new(0.5, 0.5),
+            dpdu: Vec3::new(1., 0., 0.),
+            dpdv: Vec3::new(0., 0., 1.),
+        };
+        let ray = Ray::new(Vec3::new(0., 1., 0.), Vec3::new(0., -1., 0.), 0.);
+        let mut wavelengths = SampledWavelengths::sample_visible(0.3);
+        // Hero wavelength with the others rotated by thirds of the visible range
+        let at_path = |spectrum: &dyn Fn(f32) -> f32| {
+            let [a, b, c] = [0., 1., 2.].map(|i| spectrum(380. + (0.3 + i / 3.) % 1. * 340.));
+            Vec3::new(a, b, c)
+        };
+
+        // RGB albedo is uplifted at the hit
+        let albedo = Vec3::new(0.7, 0.4, 0.2);
+        let hit = hit_result(Material::Labmertian(MatLabmertian {
+            albedo: Arc::new(Texture::SolidColor(SolidColorTexture::new(0.7, 0.4, 0.2))),
+        }));
+        let result = hit
+            .material
+            .scatter_from(&ray, &hit, 1., Some(&mut wavelengths))
+            .unwrap();
+        assert!((result.attenuation - wavelengths.uplift(&albedo)).length() < 1e-6);
+
+        // Metal reflects by its refraction index at the path wavelengths
+        let gold = MatConductor::gold(0.);
+        let expected = at_path(&|wavelength| {
+            MaterialFunctions::fresnel_complex(
+                1.,
+                Complex::new(
+                    spectrum_value(&gold.eta, wavelength),
+                    spectrum_value(&gold.k, wavelength),
+                ),
+            )
+        });
+        let hit = hit_result(Material::Conductor(gold));
+        let result = hit
+            .material
+            .scatter_from(&ray, &hit, 1., Some(&mut wavelengths))
+            .unwrap();
+        assert!((result.attenuation - expected).length() < 1e-5);
+
+        // Thin film interferes at the path wavelengths, not at fixed RGB ones
+        let film = ThinFilm::new(400., 1.33);
+        let glass = Complex::from(1.5);
+        let reflectance = film.reflectance(&hit, 1., 1., |_| glass, Some(&wavelengths));
+        let expected = at_path(&|wavelength| {
+            MaterialFunctions::fresnel_thin_film(1., 1., 1.33, 400., glass, wavelength)
+        });
+        assert!((reflectance - expected).length() < 1e-5);
+    }
+}
@@ -0,0 +1,173 @@
+use std::sync::Arc;
+
+use crate::{
+    math::{spectrum::SampledWavelengths, vec3::Vec3},
+    raytracing::{
+        ray::Ray,
+        ray_hit::HitResult,
+        texture::{Texture, TextureFunc},
+    },
+};
+
+use super::{BsdfEval, Material, MaterialFunctions, ScatterResult};
+
+/// Replaces shading normal of the base material with tangent space normal map
+pub struct MatNormalMap {
+    pub base: Arc<Material>,
+    /// Tangent space normals encoded as colors in range [0, 1], e.g. image texture loaded
+    /// with [linear](crate::raytracing::texture::ColorSpace::Linear) color space.
+    /// Red is along `u`, green is along `v` texture coordinate
+    pub normals: Arc<Texture>,
+    /// Blend between geometric (0) and mapped (1) normal
+    pub strength: f32,
+}
+
+impl MatNormalMap {
+    pub fn new(base: Arc<Material>, normals: Arc<Texture>) -> Self {
+        Self {
+            base,
+            normals,
+            strength: 1.,
+        }
+    }
+
+    pub fn scatter(&self, in_ray: &Ray, hit_result: &HitResult) -> Option<ScatterResult> {
+        self.base
+            .scatter(in_ray, &self.shading_hit(in_ray, hit_result))
+    }
+
+    pub fn scatter_from(
+        &self,
+        in_ray: &Ray,
+        hit_result: &HitResult,
+        exterior_index: f32,
+        wavelengths: Option<&mut SampledWavelengths>,
+    ) -> Option<ScatterResult> {
+        self.base.scatter_from(
+            in_ray,
+            &self.shading_hit(in_ray, hit_result),
+            exterior_index,
+            wavelengths,
+        )
+    }
+
+    pub fn evaluate(
+        &self,
+        in_ray: &Ray,
+        hit_result: &HitResult,
+        direction: &Vec3,
+    ) -> Option<BsdfEval> {
+        self.base
+            .evaluate(in_ray, &self.shading_hit(in_ray, hit_result), direction)
+    }
+
+    pub fn evaluate_from(
+        &self,
+        in_ray: &Ray,
+        hit_result: &HitResult,
+        direction: &Vec3,
+        wavelengths: Option<&SampledWavelengths>,
+    ) -> Option<BsdfEval> {
+        self.base.evaluate_from(
+            in_ray,
+            &self.shading_hit(in_ray, hit_result),
+            direction,
+            wavelengths,
+        )
+    }
+
+    pub fn emitted(&self, hit_result: &HitResult) -> Vec3 {
+        self.base.emitted(hit_result)
+    }
+
+    fn shading_hit(&self, in_ray: &Ray, hit_result: &HitResult) -> HitResult {
+        let encoded = self.normals.value(&hit_result.uv, &hit_result.location);
+        let local = Vec3::new(
+            2. * encoded.x() - 1.,
+            2. * encoded.y() - 1.,
+            2. * encoded.z() - 1.,
+        );
+        if local.length_squared() == 0. {
+            return hit_result.clone();
+        }
+
+        // Map in the frame of the outward normal, a flipped frame would be mirrored
+        let outward = HitResult {
+            normal: if hit_result.front_face {
+                hit_result.normal
+            } else {
+                -hit_result.normal
+            },
+            ..hit_result.clone()
+        };
+        let mapped = outward.tangent_frame().to_world(&local).norm();
+        let mapped = if hit_result.front_face {
+            mapped
+        } else {
+            -mapped
+        };
+        let normal = (1. - self.strength) * hit_result.normal + self.strength * mapped;
+        MaterialFunctions::with_shading_normal(in_ray, hit_result, &normal)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use crate::{
+        math::vec3::Vec3,
+        raytracing::{
+            material::{MatDielectric, MatLabmertian, Material},
+            ray::Ray,
+            ray_hit::HitResult,
+            texture::{SolidColorTexture, Texture, UvCoords},
+        },
+    };
+
+    use super::MatNormalMap;
+
+    #[test]
+    fn back_face_test() {
+        let base = Arc::new(Material::Labmertian(MatLabmertian {
+            albedo: Arc::new(Texture::SolidColor(SolidColorTexture::new(1., 1., 1.))),
+        }));
+        // Normal tilted along `u`, encoded from (0.6, 0, 0.8)
+        let normals = Arc::new(Texture::SolidColor(SolidColorTexture::new(0.8, 0.5, 0.9)));
+        let material = MatNormalMap::new(base.clone(), normals);
+        let front = HitResult {
+            location: Vec3::zero(),
+            normal: Vec3::new(0., 1., 0.),
+            distance: 1.,
+            front_face: true,
+            material: base,
+            uv: UvCoords::new(0.5, 0.5),
+            dpdu: Vec3::new(1., 0., 0.),
+            dpdv: Vec3::new(0., 0., 1.),
+        };
+        let ray = Ray::new(Vec3::new(0., 1., 0.), Vec3::new(0., -1., 0.), 0.);
+        let shading = material.shading_hit(&ray, &front);
+        assert!((shading.normal - Vec3::new(0.6, 0.8, 0.)).length() < 1e-5);
+
+        // Same surface seen from below has the opposite normal, the relief is not mirrored
+        let back = HitResult {
+            normal: Vec3::new(0., -1., 0.),
+            front_face: false,
+            ..front
+        };
+        let ray = Ray::new(Vec3::new(0., -1., 0.), Vec3::new(0., 1., 0.), 0.);
+        let shading = material.shading_hit(&ray, &back);
+        assert!((shading.normal - Vec3::new(-0.6, -0.8, 0.)).length() < 1e-5);
+    }
+    #[test]
+    fn medium_test() {
+        let glass = Arc::new(Material::Dielectric(MatDielectric::new(1.5)));
+        let normals = Arc::new(Texture::SolidColor(SolidColorTexture::new(0.5, 0.5, 1.)));
+        let material = Material::NormalMap(MatNormalMap::new(glass.clone(), normals));
+        // Ray enters and leaves the same medium through the base and the wrapped surface
+        let (id, medium) = material.medium().unwrap();
+        assert_eq!(id, Arc::as_ptr(&glass) as usize);
+        assert_eq!(id, glass.medium().unwrap().0);
+        assert_eq!(medium.refraction_index, 1.5);
+    }
+}
@@ -0,0 +1,114 @@
+use std::{f32::consts::PI, sync::Arc};
+
+use crate::{
+    math::{onb::Onb, random_cosine_direction, vec3::Vec3},
+    raytracing::{
+        ray::Ray,
+        ray_hit::HitResult,
+        texture::{Texture, TextureFunc},
+    },
+};
+
+use super::{BsdfEval, ScalarInput, ScatterResult};
+
+/// Rough diffuse surface made of V-shaped Lambertian microfacets, e.g. clay or concrete.
+/// Rough surfaces look flatter and reflect more back towards the light
+/// (Oren, Nayar "Generalization of Lambert's Reflectance Model")
+pub struct MatOrenNayar {
+    pub albedo: Arc<Texture>,
+    /// Standard deviation of microfacet slope angle in radians, zero is Lambertian
+    pub roughness: ScalarInput,
+}
+
+impl MatOrenNayar {
+    pub fn new(albedo: Arc<Texture>, roughness: f32) -> Self {
+        Self {
+            albedo,
+            roughness: ScalarInput::Value(roughness),
+        }
+    }
+
+    pub fn scatter(&self, in_ray: &Ray, hit_result: &HitResult) -> Option<ScatterResult> {
+        let frame = Onb::from_w(&hit_result.normal);
+        let wo = frame.to_local(&-in_ray.direction.norm());
+        let wi = random_cosine_direction();
+        let pdf = wi.z() / PI;
+        if pdf <= 0. {
+            return None;
+        }
+        Some(ScatterResult {
+            attenuation: &self.reflectance(hit_result, &wo, &wi) / pdf,
+            ray: Ray::new(hit_result.location, frame.to_world(&wi), in_ray.time),
+            pdf: Some(pdf),
+        })
+    }
+
+    pub fn evaluate(&self, in_ray: &Ray, hit_result: &HitResult, direction: &Vec3) -> BsdfEval {
+        let frame = Onb::from_w(&hit_result.normal);
+        let wo = frame.to_local(&-in_ray.direction.norm());
+        let wi = frame.to_local(&direction.norm());
+        BsdfEval {
+            value: self.reflectance(hit_result, &wo, &wi),
+            pdf: wi.z().max(0.) / PI,
+        }
+    }
+
+    /// BRDF multiplied by cosine in shading space
+    fn reflectance(&self, hit_result: &HitResult, wo: &Vec3, wi: &Vec3) -> Vec3 {
+        if wo.z() <= 0. || wi.z() <= 0. {
+            return Vec3::zero();
+        }
+        let sigma = self
+            .roughness
+            .value(&hit_result.uv, &hit_result.location)
+            .max(0.);
+        let albedo = self.albedo.value(&hit_result.uv, &hit_result.location);
+        wi.z() * oren_nayar(sigma, wo, wi) * albedo
+    }
+}
+
+/// Oren-Nayar BRDF without albedo, qualitative model
+fn oren_nayar(sigma: f32, wo: &Vec3, wi: &Vec3) -> f32 {
+    let sigma2 = sigma * sigma;
+    let a = 1. - sigma2 / (2. * (sigma2 + 0.33));
+    let b = 0.45 * sigma2 / (sigma2 + 0.09);
+
+    let sin_theta_o = f32::sqrt(f32::max(0., 1. - wo.z() * wo.z()));
+    let sin_theta_i = f32::sqrt(f32::max(0., 1. - wi.z() * wi.z()));
+    // Cosine of azimuth difference
+    let max_cos = if sin_theta_o > 1e-4 && sin_theta_i > 1e-4 {
+        let cos_phi = (wo.x() * wi.x() + wo.y() * wi.y()) / (sin_theta_o * sin_theta_i);
+        cos_phi.max(0.)
+    } else {
+        0.
+    };
+    // Larger and smaller of the angles to the normal
+    let (sin_alpha, tan_beta) = if wi.z() > wo.z() {
+        (sin_theta_o, sin_theta_i / wi.z())
+    } else {
+        (sin_theta_i, sin_theta_o / wo.z())
+    };
+    (a + b * max_cos * sin_alpha * tan_beta) / PI
+}
+
+#[cfg(test)]
+mod test {
+    use std::f32::consts::PI;
+
+    use crate::math::vec3::Vec3;
+
+    use super::oren_nayar;
+
+    #[test]
+    fn oren_nayar_test() {
+        let wo = Vec3::new(0.6, 0., 0.8);
+        let wi = Vec3::new(-0.3, 0.4, 0.866).norm();
+        // Smooth surface is Lambertian
+        assert!((oren_nayar(0., &wo, &wi) - 1. / PI).abs() < 1e-6);
+
+        // Rough surface reflects more back to the light than forward
+        let back = oren_nayar(0.5, &wo, &wo);
+        let forward = oren_nayar(0.5, &wo, &Vec3::new(-0.6, 0., 0.8));
+        assert!(back > forward);
+    }
+}
@@ -0,0 +1,291 @@
+use std::{f32::consts::PI, sync::Arc};
+
+use rand::{thread_rng, Rng};
+
+use crate::{
+    math::{colorimetry::luminance, random_cosine_direction, vec3::Vec3},
+    raytracing::{
+        ray::Ray,
+        ray_hit::HitResult,
+        texture::{Texture, TextureFunc, UvCoords},
+    },
+};
+
+use super::{BsdfEval, MatRoughDielectric, ScalarInput, ScatterResult, TrowbridgeReitz};
+
+/// Artist friendly uber material, based on Burley "Physically Based Shading at Disney"
+/// and "Extending the Disney BRDF to a BSDF with Integrated Subsurface Scattering".
+///
+/// All parameters except `base_color` are in range [0, 1]
+pub struct MatPrincipled {
+    pub base_color: Arc<Texture>,
+    /// Blend between dielectric and conductor
+    pub metallic: ScalarInput,
+    pub roughness: ScalarInput,
+    /// Dielectric reflectance at normal incidence, 0.5 corresponds to 4% (refraction index 1.5)
+    pub specular: ScalarInput,
+    /// Tint dielectric reflection towards the base color
+    pub specular_tint: ScalarInput,
+    /// Additional grazing reflection for cloth
+    pub sheen: ScalarInput,
+    /// Second specular layer on top of everything
+    pub clearcoat: ScalarInput,
+    pub clearcoat_roughness: ScalarInput,
+    /// Blend between opaque and transparent dielectric
+    pub transmission: ScalarInput,
+    /// Stretch of the specular highlight along the tangent
+    pub anisotropy: ScalarInput,
+}
+
+/// Material parameters evaluated at the hit point
+struct Lobes {
+    base_color: Vec3,
+    roughness: f32,
+    specular_color: Vec3,
+    sheen_color: Vec3,
+    eta: f32,
+    distribution: TrowbridgeReitz,
+    clearcoat_distribution: TrowbridgeReitz,
+    diffuse_weight: f32,
+    specular_weight: f32,
+    clearcoat_weight: f32,
+    transmission_weight: f32,
+}
+
+impl MatPrincipled {
+    pub fn new(base_color: Arc<Texture>) -> Self {
+        Self {
+            base_color,
+            metallic: ScalarInput::Value(0.),
+            roughness: ScalarInput::Value(0.5),
+            specular: ScalarInput::Value(0.5),
+            specular_tint: ScalarInput::Value(0.),
+            sheen: ScalarInput::Value(0.),
+            clearcoat: ScalarInput::Value(0.),
+            clearcoat_roughness: ScalarInput::Value(0.1),
+            transmission: ScalarInput::Value(0.),
+            anisotropy: ScalarInput::Value(0.),
+        }
+    }
+
+    pub fn scatter(&self, in_ray: &Ray, hit_result: &HitResult) -> Option<ScatterResult> {
+        let frame = hit_result.tangent_frame();
+        let wo = frame.to_local(&-in_ray.direction.norm());
+        if wo.z() <= 0. {
+            return None;
+        }
+        let lobes = self.lobes(hit_result);
+
+        // Select lobe proportionally to its weight
+        let mut rng = thread_rng();
+        let mut u = rng.gen::<f32>() * lobes.total_weight();
+        let wi = if u < lobes.diffuse_weight {
+            random_cosine_direction()
+        } else {
+            u -= lobes.diffuse_weight;
+            if u < lobes.specular_weight {
+                lobes
+                    .distribution
+                    .sample_reflection(&wo, rng.gen(), rng.gen())
+            } else if u < lobes.specular_weight + lobes.clearcoat_weight {
+                lobes
+                    .clearcoat_distribution
+                    .sample_reflection(&wo, rng.gen(), rng.gen())
+            } else {
+                MatRoughDielectric::sample_local(&lobes.distribution, &wo, lobes.eta)?
+            }
+        };
+
+        let (value, pdf) = lobes.evaluate(&wo, &wi);
+        if pdf == 0. {
+            return None;
+        }
+        Some(ScatterResult {
+            attenuation: &value / pdf,
+            ray: Ray::new(hit_result.location, frame.to_world(&wi), in_ray.time),
+            pdf: Some(pdf),
+        })
+    }
+
+    pub fn evaluate(&self, in_ray: &Ray, hit_result: &HitResult, direction: &Vec3) -> BsdfEval {
+        let frame = hit_result.tangent_frame();
+        let wo = frame.to_local(&-in_ray.direction.norm());
+        let wi = frame.to_local(&direction.norm());
+        let (value, pdf) = self.lobes(hit_result).evaluate(&wo, &wi);
+        BsdfEval { value, pdf }
+    }
+
+    fn lobes(&self, hit_result: &HitResult) -> Lobes {
+        self.lobes_at(&hit_result.uv, &hit_result.location, hit_result.front_face)
+    }
+
+    fn lobes_at(&self, uv: &UvCoords, point: &Vec3, front_face: bool) -> Lobes {
+        let value = |input: &ScalarInput| input.value(uv, point).clamp(0., 1.);
+        let white = Vec3::new(1., 1., 1.);
+
+        let base_color = self.base_color.value(uv, point);
+        let metallic = value(&self.metallic);
+        let roughness = value(&self.roughness);
+        let transmission = value(&self.transmission);
+
+        // Hue and saturation of the base color
+        let base_luminance = luminance(&base_color);
+        let tint = if base_luminance > 0. {
+            &base_color / base_luminance
+        } else {
+            white
+        };
+
+        let specular = value(&self.specular);
+        let dielectric_specular = 0.08 * specular * lerp(&white, &tint, value(&self.specular_tint));
+        let reflectance = f32::sqrt(0.08 * specular).min(0.99);
+        let refraction_index = (1. + reflectance) / (1. - reflectance);
+
+        // Squeeze highlight across the tangent
+        let alpha = f32::max(1e-3, roughness * roughness);
+        let aspect = f32::sqrt(1. - 0.9 * value(&self.anisotropy));
+        let clearcoat_roughness = value(&self.clearcoat_roughness);
+        let clearcoat_alpha = f32::max(1e-3, clearcoat_roughness * clearcoat_roughness);
+
+        Lobes {
+            roughness,
+            specular_color: lerp(&dielectric_specular, &base_color, metallic),
+            sheen_color: value(&self.sheen) * lerp(&white, &tint, 0.5),
+            eta: if front_face {
+                refraction_index
+            } else {
+                1. / refraction_index
+            },
+            distribution: TrowbridgeReitz::new(alpha / aspect, alpha * aspect),
+            clearcoat_distribution: TrowbridgeReitz::new(clearcoat_alpha, clearcoat_alpha),
+            diffuse_weight: (1. - metallic) * (1. - transmission),
+            specular_weight: 1. - (1. - metallic) * transmission,
+            clearcoat_weight: 0.25 * value(&self.clearcoat),
+            transmission_weight: (1. - metallic) * transmission,
+            base_color,
+        }
+    }
+}
+
+impl Lobes {
+    fn total_weight(&self) -> f32 {
+        self.diffuse_weight
+            + self.specular_weight
+            + self.clearcoat_weight
+            + self.transmission_weight
+    }
+
+    /// BSDF multiplied by cosine and probability density of sampling `wi` in shading space
+    fn evaluate(&self, wo: &Vec3, wi: &Vec3) -> (Vec3, f32) {
+        let total_weight = self.total_weight();
+        let mut value = Vec3::zero();
+        let mut pdf = 0.;
+        if wo.z() <= 0. || total_weight == 0. {
+            return (value, pdf);
+        }
+
+        let wm = *wo + *wi;
+        if wi.z() > 0. && wm.length_squared() > 0. {
+            let wm = wm.norm();
+            let cos_theta_d = wi.dot(&wm);
+            let fresnel_weight = schlick_weight(cos_theta_d);
+
+            if self.diffuse_weight > 0. {
+                // Retro-reflection increases with roughness at grazing angles
+                let fd90 = 0.5 + 2. * self.roughness * cos_theta_d * cos_theta_d;
+                let fl = 1. + (fd90 - 1.) * schlick_weight(wi.z());
+                let fv = 1. + (fd90 - 1.) * schlick_weight(wo.z());
+                let diffuse = (fl * fv / PI) * self.base_color;
+                let sheen = fresnel_weight * self.sheen_color;
+                value += (self.diffuse_weight * wi.z()) * (diffuse + sheen);
+                pdf += self.diffuse_weight / total_weight * wi.z() / PI;
+            }
+
+            let (specular, specular_pdf) = self.distribution.reflection(wo, wi);
+            let fresnel = lerp(&self.specular_color, &Vec3::new(1., 1., 1.), fresnel_weight);
+            value += (self.specular_weight * specular) * fresnel;
+            pdf += self.specular_weight / total_weight * specular_pdf;
+
+            if self.clearcoat_weight > 0. {
+                let (clearcoat, clearcoat_pdf) = self.clearcoat_distribution.reflection(wo, wi);
+                let fresnel = 0.04 + 0.96 * fresnel_weight;
+                let clearcoat = self.clearcoat_weight * clearcoat * fresnel;
+                value += Vec3::new(clearcoat, clearcoat, clearcoat);
+                pdf += self.clearcoat_weight / total_weight * clearcoat_pdf;
+            }
+        }
+
+        if self.transmission_weight > 0. {
+            let (glass, glass_pdf) =
+                MatRoughDielectric::evaluate_local(&self.distribution, wo, wi, self.eta);
+            // Light passing through is tinted by the base color
+            let color = if wi.z() < 0. {
+                self.base_color
+            } else {
+                Vec3::new(1., 1., 1.)
+            };
+            value += (self.transmission_weight * glass) * color;
+            pdf += self.transmission_weight / total_weight * glass_pdf;
+        }
+        (value, pdf)
+    }
+}
+
+fn schlick_weight(cos_theta: f32) -> f32 {
+    (1. - cos_theta.clamp(0., 1.)).powi(5)
+}
+
+fn lerp(a: &Vec3, b: &Vec3, t: f32) -> Vec3 {
+    (1. - t) * a + t * b
+}
+
+#[cfg(test)]
+mod test {
+    use std::{f32::consts::PI, sync::Arc};
+
+    use crate::{
+        math::vec3::Vec3,
+        raytracing::{
+            material::ScalarInput,
+            texture::{SolidColorTexture, Texture, UvCoords},
+        },
+    };
+
+    use super::MatPrincipled;
+
+    fn white_furnace(material: &MatPrincipled, wo: &Vec3) -> f32 {
+        // Integral of BSDF * cos over the whole sphere
+        let lobes = material.lobes_at(&UvCoords::new(0.5, 0.5), &Vec3::zero(), true);
+        let steps = 200;
+        let mut sum = 0.;
+        for i in 0..steps {
+            let theta = (i as f32 + 0.5) / steps as f32 * PI;
+            for j in 0..steps {
+                let phi = (j as f32 + 0.5) / steps as f32 * 2. * PI;
+                let wi = Vec3::new(
+                    theta.sin() * phi.cos(),
+                    theta.sin() * phi.sin(),
+                    theta.cos(),
+                );
+                sum += lobes.evaluate(wo, &wi).0.y() * theta.sin();
+            }
+        }
+        sum * (PI / steps as f32) * (2. * PI / steps as f32)
+    }
+
+    #[test]
+    fn energy_test() {
+        let white = Arc::new(Texture::SolidColor(SolidColorTexture::new(1., 1., 1.)));
+        let wo = Vec3::new(0.5, 0., 0.8).norm();
+
+        let mut material = MatPrincipled::new(white);
+        material.roughness = ScalarInput::Value(0.5);
+        let albedo = white_furnace(&material, &wo);
+        assert!(albedo > 0.8 && albedo < 1.1, "{albedo}");
+
+        // Single scattering microfacet model loses some energy
+        material.metallic = ScalarInput::Value(1.);
+        let albedo = white_furnace(&material, &wo);
+        assert!(albedo > 0.85 && albedo <= 1.01, "{albedo}");
+    }
+}
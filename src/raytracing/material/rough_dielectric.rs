@@ -0,0 +1,162 @@
+use rand::{thread_rng, Rng};
+
+use crate::{
+    math::{onb::Onb, vec3::Vec3},
+    raytracing::{ray::Ray, ray_hit::HitResult},
+};
+
+use super::{BsdfEval, MaterialFunctions, ScatterResult, TrowbridgeReitz};
+
+/// Glass with GGX microfacet distribution for both reflection and transmission
+/// (Walter et al. "Microfacet Models for Refraction through Rough Surfaces")
+pub struct MatRoughDielectric {
+    pub refraction_index: f32,
+    /// Perceptual roughness in range [0, 1], zero is a perfectly smooth surface
+    pub roughness: f32,
+}
+
+impl MatRoughDielectric {
+    pub fn new(refraction_index: f32, roughness: f32) -> Self {
+        Self {
+            refraction_index,
+            roughness: roughness.clamp(0., 1.),
+        }
+    }
+
+    pub fn scatter(&self, in_ray: &Ray, hit_result: &HitResult) -> Option<ScatterResult> {
+        let frame = Onb::from_w(&hit_result.normal);
+        let wo = frame.to_local(&-in_ray.direction.norm());
+        if wo.z() <= 0. {
+            return None;
+        }
+        let eta = Self::eta(self.refraction_index, hit_result);
+        let distribution = TrowbridgeReitz::from_roughness(self.roughness);
+
+        if distribution.is_smooth() {
+            let reflectance = MaterialFunctions::fresnel_dielectric(wo.z(), eta);
+            let normal = Vec3::new(0., 0., 1.);
+            let wi = match MaterialFunctions::transmit(&wo, &normal, eta) {
+                Some(refracted) if thread_rng().gen::<f32>() >= reflectance => refracted,
+                _ => Vec3::new(-wo.x(), -wo.y(), wo.z()),
+            };
+            return Some(ScatterResult {
+                attenuation: Vec3::new(1., 1., 1.),
+                ray: Ray::new(hit_result.location, frame.to_world(&wi), in_ray.time),
+                pdf: None,
+            });
+        }
+
+        let wi = Self::sample_local(&distribution, &wo, eta)?;
+        let (value, pdf) = Self::evaluate_local(&distribution, &wo, &wi, eta);
+        if pdf == 0. {
+            return None;
+        }
+        let weight = value / pdf;
+        Some(ScatterResult {
+            attenuation: Vec3::new(weight, weight, weight),
+            ray: Ray::new(hit_result.location, frame.to_world(&wi), in_ray.time),
+            pdf: Some(pdf),
+        })
+    }
+
+    /// Returns `None` for perfectly smooth surface
+    pub fn evaluate(
+        &self,
+        in_ray: &Ray,
+        hit_result: &HitResult,
+        direction: &Vec3,
+    ) -> Option<BsdfEval> {
+        let distribution = TrowbridgeReitz::from_roughness(self.roughness);
+        if distribution.is_smooth() {
+            return None;
+        }
+
+        let frame = Onb::from_w(&hit_result.normal);
+        let wo = frame.to_local(&-in_ray.direction.norm());
+        let wi = frame.to_local(&direction.norm());
+        let eta = Self::eta(self.refraction_index, hit_result);
+        let (value, pdf) = Self::evaluate_local(&distribution, &wo, &wi, eta);
+        Some(BsdfEval {
+            value: Vec3::new(value, value, value),
+            pdf,
+        })
+    }
+
+    /// Ratio of refraction indices, transmitted side over incident side
+    fn eta(refraction_index: f32, hit_result: &HitResult) -> f32 {
+        if hit_result.front_face {
+            refraction_index
+        } else {
+            1. / refraction_index
+        }
+    }
+
+    /// Sample reflected or transmitted direction in shading space
+    pub(super) fn sample_local(
+        distribution: &TrowbridgeReitz,
+        wo: &Vec3,
+        eta: f32,
+    ) -> Option<Vec3> {
+        let mut rng = thread_rng();
+        let wm = distribution.sample_wm(wo, rng.gen(), rng.gen());
+        let reflectance = MaterialFunctions::fresnel_dielectric(wo.dot(&wm), eta);
+
+        if rng.gen::<f32>() < reflectance {
+            let wi = MaterialFunctions::reflect(&-wo, &wm);
+            (wi.z() > 0.).then_some(wi)
+        } else {
+            let wi = MaterialFunctions::transmit(wo, &wm, eta)?;
+            (wi.z() < 0.).then_some(wi)
+        }
+    }
+
+    /// BSDF multiplied by cosine and probability density of [sampling](Self::sample_local)
+    /// the direction `wi` in shading space
+    pub(super) fn evaluate_local(
+        distribution: &TrowbridgeReitz,
+        wo: &Vec3,
+        wi: &Vec3,
+        eta: f32,
+    ) -> (f32, f32) {
+        const NO_SCATTERING: (f32, f32) = (0., 0.);
+        if wo.z() <= 0. || wi.z() == 0. {
+            return NO_SCATTERING;
+        }
+        let reflect = wi.z() > 0.;
+
+        // Generalized half vector
+        let wm = if reflect { wo + wi } else { wo + &(eta * wi) };
+        if wm.length_squared() == 0. {
+            return NO_SCATTERING;
+        }
+        let mut wm = wm.norm();
+        if wm.z() < 0. {
+            wm = -wm;
+        }
+        // Microfacets facing away from either direction
+        if wm.dot(wi) * wi.z() < 0. || wm.dot(wo) < 0. {
+            return NO_SCATTERING;
+        }
+
+        let cos_theta_o = wo.dot(&wm);
+        let reflectance = MaterialFunctions::fresnel_dielectric(cos_theta_o, eta);
+        let d = distribution.d(&wm);
+        let g = distribution.g(wo, wi);
+
+        if reflect {
+            (
+                d * g * reflectance / (4. * wo.z()),
+                distribution.d_visible(wo, &wm) / (4. * cos_theta_o) * reflectance,
+            )
+        } else {
+            let cos_theta_i = wi.dot(&wm);
+            let denominator = cos_theta_i + cos_theta_o / eta;
+            let denominator = denominator * denominator;
+            let transmittance = 1. - reflectance;
+            (
+                transmittance * d * g * (cos_theta_i * cos_theta_o).abs() / (denominator * wo.z()),
+                distribution.d_visible(wo, &wm) * cos_theta_i.abs() / denominator * transmittance,
+            )
+        }
+    }
+}
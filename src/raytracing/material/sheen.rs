@@ -0,0 +1,221 @@
+use std::{
+    f32::consts::PI,
+    sync::{Arc, OnceLock},
+};
+
+use crate::{
+    math::{onb::Onb, random_cosine_direction, vec3::Vec3},
+    raytracing::{
+        ray::Ray,
+        ray_hit::HitResult,
+        texture::{Texture, TextureFunc},
+    },
+};
+
+use super::{BsdfEval, ScalarInput, ScatterResult};
+
+/// Cloth and velvet, diffuse base covered by fibers which reflect at grazing angles.
+/// Fibers use "Charlie" distribution from Conty, Kulla "Production Friendly Microfacet
+/// Sheen BRDF" with visibility term of Neubelt, Pettineo. Base receives light not
+/// reflected by the fibers, so the material doesn't create energy
+pub struct MatSheen {
+    /// Diffuse color under the fibers
+    pub albedo: Arc<Texture>,
+    /// Color of light reflected by the fibers
+    pub sheen_color: Arc<Texture>,
+    /// Roughness of the fibers in range (0, 1], lower values give narrower rim
+    pub roughness: ScalarInput,
+}
+
+impl MatSheen {
+    pub fn new(albedo: Arc<Texture>, sheen_color: Arc<Texture>, roughness: f32) -> Self {
+        Self {
+            albedo,
+            sheen_color,
+            roughness: ScalarInput::Value(roughness),
+        }
+    }
+
+    pub fn scatter(&self, in_ray: &Ray, hit_result: &HitResult) -> Option<ScatterResult> {
+        let frame = Onb::from_w(&hit_result.normal);
+        let wo = frame.to_local(&-in_ray.direction.norm());
+        let wi = random_cosine_direction();
+        let pdf = wi.z() / PI;
+        if pdf <= 0. {
+            return None;
+        }
+        Some(ScatterResult {
+            attenuation: &self.reflectance(hit_result, &wo, &wi) / pdf,
+            ray: Ray::new(hit_result.location, frame.to_world(&wi), in_ray.time),
+            pdf: Some(pdf),
+        })
+    }
+
+    pub fn evaluate(&self, in_ray: &Ray, hit_result: &HitResult, direction: &Vec3) -> BsdfEval {
+        let frame = Onb::from_w(&hit_result.normal);
+        let wo = frame.to_local(&-in_ray.direction.norm());
+        let wi = frame.to_local(&direction.norm());
+        BsdfEval {
+            value: self.reflectance(hit_result, &wo, &wi),
+            pdf: wi.z().max(0.) / PI,
+        }
+    }
+
+    /// BRDF multiplied by cosine in shading space
+    fn reflectance(&self, hit_result: &HitResult, wo: &Vec3, wi: &Vec3) -> Vec3 {
+        if wo.z() <= 0. || wi.z() <= 0. {
+            return Vec3::zero();
+        }
+        let (uv, point) = (&hit_result.uv, &hit_result.location);
+        let roughness = self.roughness.value(uv, point).clamp(0.01, 1.);
+        let albedo = self.albedo.value(uv, point);
+        let sheen_color = self.sheen_color.value(uv, point);
+        let sheen_max = sheen_color.x().max(sheen_color.y()).max(sheen_color.z());
+        let base_scale = 1. - sheen_max * sheen_albedo(roughness, wo.z());
+        wi.z() * (base_scale / PI * albedo + sheen(roughness, wo, wi) * sheen_color)
+    }
+}
+
+/// Fraction of light reflected by the fibers for outgoing direction with `cos_theta_o`.
+/// Integrated numerically into a table on the first use
+fn sheen_albedo(roughness: f32, cos_theta_o: f32) -> f32 {
+    const SIZE: usize = 16;
+    const STEPS: usize = 48;
+    static TABLE: OnceLock<Vec<f32>> = OnceLock::new();
+
+    // Roughness along rows from 0 to 1, cosine along columns from 0 to 1
+    let table = TABLE.get_or_init(|| {
+        let mut table = Vec::with_capacity(SIZE * SIZE);
+        for i in 0..SIZE {
+            let roughness = (i as f32 / (SIZE - 1) as f32).max(0.01);
+            for j in 0..SIZE {
+                let cos_theta = j as f32 / (SIZE - 1) as f32;
+                let wo = Vec3::new(f32::sqrt(1. - cos_theta * cos_theta), 0., cos_theta);
+                let mut sum = 0.;
+                // Lobe is symmetric to the plane of `wo` and normal
+                for k in 0..STEPS {
+                    let theta = (k as f32 + 0.5) / STEPS as f32 * PI / 2.;
+                    for l in 0..STEPS {
+                        let phi = (l as f32 + 0.5) / STEPS as f32 * PI;
+                        let wi = Vec3::new(
+                            theta.sin() * phi.cos(),
+                            theta.sin() * phi.sin(),
+                            theta.cos(),
+                        );
+                        sum += sheen(roughness, &wo, &wi) * wi.z() * theta.sin();
+                    }
+                }
+                table.push(sum * 2. * (PI / 2. / STEPS as f32) * (PI / STEPS as f32));
+            }
+        }
+        table
+    });
+
+    // Bilinear interpolation
+    let x = roughness.clamp(0., 1.) * (SIZE - 1) as f32;
+    let y = cos_theta_o.clamp(0., 1.) * (SIZE - 1) as f32;
+    let (i, j) = ((x as usize).min(SIZE - 2), (y as usize).min(SIZE - 2));
+    let (s, t) = (x - i as f32, y - j as f32);
+    let at = |i: usize, j: usize| table[i * SIZE + j];
+    (1. - s) * ((1. - t) * at(i, j) + t * at(i, j + 1))
+        + s * ((1. - t) * at(i + 1, j) + t * at(i + 1, j + 1))
+}
+
+/// Fiber BRDF without color
+fn sheen(roughness: f32, wo: &Vec3, wi: &Vec3) -> f32 {
+    let wm = *wo + *wi;
+    if wm.length_squared() == 0. {
+        return 0.;
+    }
+    let cos_theta_m = wm.norm().z();
+    let sin_theta_m = f32::sqrt(f32::max(0., 1. - cos_theta_m * cos_theta_m));
+
+    let inverse_alpha = 1. / roughness;
+    let distribution = (2. + inverse_alpha) * sin_theta_m.powf(inverse_alpha) / (2. * PI);
+    let visibility = 1. / (4. * (wi.z() + wo.z() - wi.z() * wo.z()));
+    distribution * visibility
+}
+
+#[cfg(test)]
+mod test {
+    use std::f32::consts::PI;
+
+    use std::sync::Arc;
+
+    use crate::{
+        math::vec3::Vec3,
+        raytracing::{
+            material::Material,
+            ray_hit::HitResult,
+            texture::{SolidColorTexture, Texture, UvCoords},
+        },
+    };
+
+    use super::{sheen, MatSheen};
+
+    #[test]
+    fn sheen_test() {
+        let albedo = |wo: &Vec3| {
+            let steps = 200;
+            let mut sum = 0.;
+            for i in 0..steps {
+                let theta = (i as f32 + 0.5) / steps as f32 * PI / 2.;
+                for j in 0..steps {
+                    let phi = (j as f32 + 0.5) / steps as f32 * 2. * PI;
+                    let wi = Vec3::new(
+                        theta.sin() * phi.cos(),
+                        theta.sin() * phi.sin(),
+                        theta.cos(),
+                    );
+                    sum += sheen(0.5, wo, &wi) * wi.z() * theta.sin();
+                }
+            }
+            sum * (PI / 2. / steps as f32) * (2. * PI / steps as f32)
+        };
+
+        // Fibers reflect more at grazing angles and don't create energy
+        let normal = albedo(&Vec3::new(0., 0., 1.));
+        let grazing = albedo(&Vec3::new(0.95, 0., 0.312).norm());
+        assert!(normal > 0. && normal < grazing, "{normal} {grazing}");
+        assert!(grazing < 1., "{grazing}");
+    }
+    #[test]
+    fn white_furnace_test() {
+        let white = || Arc::new(Texture::SolidColor(SolidColorTexture::new(1., 1., 1.)));
+        let hit_result = HitResult {
+            location: Vec3::zero(),
+            normal: Vec3::new(0., 0., 1.),
+            distance: 1.,
+            front_face: true,
+            material: Arc::new(Material::Sheen(MatSheen::new(white(), white(), 1.))),
+            uv: UvCoords::new(0.5, 0.5),
+            dpdu: Vec3::new(1., 0., 0.),
+            dpdv: Vec3::new(0., 1., 0.),
+        };
+
+        // White fibers over white base reflect all light
+        for roughness in [0.2, 0.5, 1.] {
+            let material = MatSheen::new(white(), white(), roughness);
+            for cos_theta in [1., 0.7, 0.3, 0.05] {
+                let wo = Vec3::new(f32::sqrt(1. - cos_theta * cos_theta), 0., cos_theta);
+                let steps = 200;
+                let mut sum = Vec3::zero();
+                for i in 0..steps {
+                    let theta = (i as f32 + 0.5) / steps as f32 * PI / 2.;
+                    for j in 0..steps {
+                        let phi = (j as f32 + 0.5) / steps as f32 * 2. * PI;
+                        let wi = Vec3::new(
+                            theta.sin() * phi.cos(),
+                            theta.sin() * phi.sin(),
+                            theta.cos(),
+                        );
+                        sum += theta.sin() * material.reflectance(&hit_result, &wo, &wi);
+                    }
+                }
+                let albedo = (PI / 2. / steps as f32) * (2. * PI / steps as f32) * sum.x();
+                assert!(albedo < 1.01, "{roughness} {cos_theta} {albedo}");
+                assert!(albedo > 0.97, "{roughness} {cos_theta} {albedo}");
+            }
+        }
+    }
+}
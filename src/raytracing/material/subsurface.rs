@@ -0,0 +1,96 @@
+use std::f32::consts::PI;
+
+use crate::{
+    math::{onb::Onb, random_cosine_direction, vec3::Vec3},
+    raytracing::{medium::Medium, ray::Ray, ray_hit::HitResult},
+};
+
+use super::{BsdfEval, ScatterResult};
+
+/// Translucent material, e.g. skin, wax or marble. Light enters the object through
+/// diffuse surface and scatters inside of it by random walk until leaving.
+///
+/// Object must be closed, the walk is traced by the renderer in the interior
+/// [medium](Self::medium)
+pub struct MatSubsurface {
+    /// Color of the surface after all scattering inside of the object
+    pub albedo: Vec3,
+    /// Average distance travelled by light between scattering events for each channel
+    pub mean_free_path: Vec3,
+}
+
+impl MatSubsurface {
+    pub fn new(albedo: Vec3, mean_free_path: Vec3) -> Self {
+        Self {
+            albedo,
+            mean_free_path,
+        }
+    }
+
+    /// Scattering medium with multiple scattering albedo matching the `albedo`
+    pub fn medium(&self) -> Medium {
+        let channel = |albedo: f32, mean_free_path: f32| {
+            // Inverted van de Hulst relation between single and multiple scattering albedo
+            let albedo = albedo.clamp(0., 0.999);
+            let x = 4.09712 + 4.20863 * albedo
+                - f32::sqrt(9.59217 + 41.6808 * albedo + 17.7126 * albedo * albedo);
+            let single_albedo = 1. - x * x;
+            let extinction = 1. / mean_free_path.max(1e-6);
+            (
+                extinction * (1. - single_albedo),
+                extinction * single_albedo,
+            )
+        };
+        let (r, g, b) = (
+            channel(self.albedo.x(), self.mean_free_path.x()),
+            channel(self.albedo.y(), self.mean_free_path.y()),
+            channel(self.albedo.z(), self.mean_free_path.z()),
+        );
+        Medium {
+            refraction_index: 1.,
+            absorption: Vec3::new(r.0, g.0, b.0),
+            scattering: Vec3::new(r.1, g.1, b.1),
+            priority: 0,
+        }
+    }
+
+    /// Diffuse transmission through the surface, into the object or out of it
+    pub fn scatter(&self, in_ray: &Ray, hit_result: &HitResult) -> Option<ScatterResult> {
+        let frame = Onb::from_w(&-hit_result.normal);
+        let direction = random_cosine_direction();
+        Some(ScatterResult {
+            attenuation: Vec3::new(1., 1., 1.),
+            ray: Ray::new(hit_result.location, frame.to_world(&direction), in_ray.time),
+            pdf: Some(direction.z() / PI),
+        })
+    }
+
+    pub fn evaluate(&self, _: &Ray, hit_result: &HitResult, direction: &Vec3) -> BsdfEval {
+        let cos_theta = f32::max(-hit_result.normal.dot(&direction.norm()), 0.);
+        BsdfEval {
+            value: Vec3::new(cos_theta / PI, cos_theta / PI, cos_theta / PI),
+            pdf: cos_theta / PI,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::math::vec3::Vec3;
+
+    use super::MatSubsurface;
+
+    #[test]
+    fn medium_test() {
+        let material = MatSubsurface::new(Vec3::new(0., 0.5, 1.), Vec3::new(0.1, 0.2, 0.5));
+        let medium = material.medium();
+        let extinction = medium.absorption + medium.scattering;
+        let expected = Vec3::new(10., 5., 2.);
+        assert!((extinction - expected).length() < 1e-3, "{extinction}");
+
+        // Black surface absorbs everything, white one scatters almost without loss
+        assert!(medium.scattering.x() < 1e-3);
+        assert!(medium.absorption.z() < 0.01 * medium.scattering.z());
+        assert!(medium.scattering.y() > medium.absorption.y());
+    }
+}
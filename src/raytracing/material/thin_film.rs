@@ -0,0 +1,64 @@
+use crate::{
+    math::{
+        complex::Complex,
+        spectrum::{spectrum_to_rgb, SampledWavelengths},
+        vec3::Vec3,
+    },
+    raytracing::ray_hit::HitResult,
+};
+
+use super::{MaterialFunctions, ScalarInput};
+
+/// Thin transparent coating producing iridescent reflections, e.g. soap bubbles,
+/// oil on water or anti-reflective coating of lenses
+pub struct ThinFilm {
+    /// Film thickness in nanometers, visible colors appear roughly in range [100, 1000]
+    pub thickness: ScalarInput,
+    pub refraction_index: f32,
+}
+
+impl ThinFilm {
+    pub fn new(thickness: f32, refraction_index: f32) -> Self {
+        Self {
+            thickness: ScalarInput::Value(thickness),
+            refraction_index,
+        }
+    }
+
+    /// Per channel reflectance of the coated surface. `substrate` is the refraction index
+    /// under the film for the wavelength in nanometers.
+    ///
+    /// Reflectance is evaluated at the path `wavelengths` in spectral rendering
+    pub(super) fn reflectance<F: Fn(f32) -> Complex>(
+        &self,
+        hit_result: &HitResult,
+        cos_theta_i: f32,
+        exterior_index: f32,
+        substrate: F,
+        wavelengths: Option<&SampledWavelengths>,
+    ) -> Vec3 {
+        let thickness = self
+            .thickness
+            .value(&hit_result.uv, &hit_result.location)
+            .max(0.);
+        let spectrum = |wavelength| {
+            MaterialFunctions::fresnel_thin_film(
+                cos_theta_i,
+                exterior_index,
+                self.refraction_index,
+                thickness,
+                substrate(wavelength),
+                wavelength,
+            )
+        };
+        let reflectance = match wavelengths {
+            Some(wavelengths) => wavelengths.sample(spectrum),
+            None => spectrum_to_rgb(spectrum),
+        };
+        Vec3::new(
+            reflectance.x().clamp(0., 1.),
+            reflectance.y().clamp(0., 1.),
+            reflectance.z().clamp(0., 1.),
+        )
+    }
+}
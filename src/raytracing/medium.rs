@@ -0,0 +1,226 @@
+use crate::math::{spectrum::SampledWavelengths, vec3::Vec3};
+
+/// Interior of a closed object, e.g. glass or skin
+#[derive(Clone, Copy)]
+pub struct Medium {
+    pub refraction_index: f32,
+    /// Fraction of light absorbed per unit of distance for each color channel
+    pub absorption: Vec3,
+    /// Fraction of light scattered in random direction per unit of distance
+    pub scattering: Vec3,
+    /// Where media overlap, the one with higher priority fills the volume.
+    /// Boundaries of media with lower priority are ignored inside of it
+    pub priority: u32,
+}
+
+impl Medium {
+    pub const VACUUM: Medium = Medium {
+        refraction_index: 1.,
+        absorption: Vec3::new(0., 0., 0.),
+        scattering: Vec3::new(0., 0., 0.),
+        priority: 0,
+    };
+
+    /// Absorption coefficients with which `color` remains after travelling `distance`
+    pub fn absorption_from_color(color: &Vec3, distance: f32) -> Vec3 {
+        let coefficient = |c: f32| -f32::ln(c.clamp(1e-6, 1.)) / distance;
+        Vec3::new(
+            coefficient(color.x()),
+            coefficient(color.y()),
+            coefficient(color.z()),
+        )
+    }
+
+    /// Fraction of light passing `distance` through the medium without scattering
+    /// (Beer-Lambert law)
+    pub fn transmittance(&self, distance: f32) -> Vec3 {
+        Self::transmittance_with(&(self.absorption + self.scattering), distance)
+    }
+
+    /// Medium with coefficients at the path `wavelengths` in spectral rendering
+    pub fn sampled(&self, wavelengths: &SampledWavelengths) -> Medium {
+        Medium {
+            absorption: wavelengths.uplift(&self.absorption),
+            scattering: wavelengths.uplift(&self.scattering),
+            ..*self
+        }
+    }
+
+    pub fn is_scattering(&self) -> bool {
+        self.scattering.length_squared() > 0.
+    }
+
+    /// Sample distance to the next scattering before reaching surface at `max_distance`
+    /// by extinction of the color `channel`
+    pub fn sample_distance(&self, max_distance: f32, channel: usize, u: f32) -> FreeFlight {
+        let extinction = self.absorption + self.scattering;
+        let distance = if extinction[channel] > 0. {
+            -f32::ln(1. - u) / extinction[channel]
+        } else {
+            f32::INFINITY
+        };
+
+        if distance < max_distance {
+            let transmittance = Self::transmittance_with(&extinction, distance);
+            FreeFlight {
+                distance: Some(distance),
+                throughput: self.scattering * transmittance,
+                pdf: extinction * transmittance,
+            }
+        } else {
+            let transmittance = Self::transmittance_with(&extinction, max_distance);
+            FreeFlight {
+                distance: None,
+                throughput: transmittance,
+                pdf: transmittance,
+            }
+        }
+    }
+
+    fn transmittance_with(extinction: &Vec3, distance: f32) -> Vec3 {
+        let transmittance = |extinction: f32| {
+            if extinction == 0. {
+                1.
+            } else {
+                f32::exp(-extinction * distance)
+            }
+        };
+        Vec3::new(
+            transmittance(extinction.x()),
+            transmittance(extinction.y()),
+            transmittance(extinction.z()),
+        )
+    }
+}
+
+/// Path segment inside of scattering [medium](Medium)
+pub struct FreeFlight {
+    /// Distance to the scattering, `None` if the ray passed to the surface
+    pub distance: Option<f32>,
+    /// Fraction of light transported along the segment
+    pub throughput: Vec3,
+    /// Probability density of the segment when sampled by each color channel.
+    /// Path weight is its throughput over the average of path densities
+    pub pdf: Vec3,
+}
+
+/// Media the ray is currently inside of, in order of entering.
+///
+/// Media are identified by `id` of the surface, so the ray leaves the medium it entered
+/// through the same material
+#[derive(Clone, Default)]
+pub struct MediumStack {
+    media: Vec<(usize, Medium)>,
+}
+
+impl MediumStack {
+    /// Medium with the highest priority, the most recently entered one wins ties
+    pub fn current(&self) -> Medium {
+        self.media
+            .iter()
+            .max_by_key(|(_, medium)| medium.priority)
+            .map_or(Medium::VACUUM, |(_, medium)| *medium)
+    }
+
+    pub fn entered(&self, id: usize, medium: Medium) -> Self {
+        let mut media = self.media.clone();
+        media.push((id, medium));
+        Self { media }
+    }
+
+    pub fn exited(&self, id: usize) -> Self {
+        let mut media = self.media.clone();
+        if let Some(index) = media.iter().rposition(|(entry, _)| *entry == id) {
+            media.remove(index);
+        }
+        Self { media }
+    }
+
+    /// Boundary of `medium` is inside of other medium with higher priority
+    pub fn is_hidden(&self, id: usize, medium: &Medium) -> bool {
+        self.media
+            .iter()
+            .any(|(entry, other)| *entry != id && other.priority > medium.priority)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use crate::math::vec3::Vec3;
+
+    use super::{Medium, MediumStack};
+
+    #[test]
+    fn priority_test() {
+        let water = Medium {
+            refraction_index: 1.33,
+            absorption: Vec3::zero(),
+            scattering: Vec3::zero(),
+            priority: 0,
+        };
+        let ice = Medium {
+            refraction_index: 1.31,
+            priority: 1,
+            ..water
+        };
+
+        // Ice cube floating in water, partly overlapping it
+        let media = MediumStack::default().entered(1, water);
+        assert!(!media.is_hidden(2, &ice));
+        let media = media.entered(2, ice);
+        assert_eq!(media.current().refraction_index, 1.31);
+        assert!(media.is_hidden(1, &water));
+
+        let media = media.exited(1);
+        assert_eq!(media.current().refraction_index, 1.31);
+        let media = media.exited(2);
+        assert_eq!(media.current().refraction_index, 1.);
+    }
+
+    #[test]
+    fn absorption_test() {
+        let color = Vec3::new(0.9, 0.5, 0.1);
+        let medium = Medium {
+            absorption: Medium::absorption_from_color(&color, 2.),
+            ..Medium::VACUUM
+        };
+        let transmittance = medium.transmittance(2.);
+        assert!((transmittance - color).length() < 1e-5);
+        let transmittance = Medium::VACUUM.transmittance(f32::INFINITY);
+        assert!((transmittance - Vec3::new(1., 1., 1.)).length() < 1e-5);
+    }
+
+    #[test]
+    fn sample_distance_test() {
+        let medium = Medium {
+            scattering: Vec3::new(1., 2., 4.),
+            ..Medium::VACUUM
+        };
+        let mut rng = StdRng::seed_from_u64(7);
+        let samples = 100000;
+        let mut passed = Vec3::zero();
+        let mut scattered = Vec3::zero();
+        for _ in 0..samples {
+            let flight = medium.sample_distance(0.5, rng.gen_range(0..3), rng.gen());
+            let pdf = (flight.pdf.x() + flight.pdf.y() + flight.pdf.z()) / 3.;
+            let weight = &flight.throughput / pdf;
+            match flight.distance {
+                Some(distance) => {
+                    assert!(distance < 0.5);
+                    scattered += weight;
+                }
+                None => passed += weight,
+            }
+        }
+        passed /= samples as f32;
+        scattered /= samples as f32;
+
+        // Without absorption light either passes or scatters
+        let transmittance = medium.transmittance(0.5);
+        assert!((passed - transmittance).length() < 0.01, "{passed}");
+        let expected = Vec3::new(1., 1., 1.) - transmittance;
+        assert!((scattered - expected).length() < 0.02, "{scattered}");
+    }
+}
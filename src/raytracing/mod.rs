@@ -1,6 +1,10 @@
 pub mod aabb;
+pub mod animation;
+pub mod background;
 pub mod camera;
+pub mod light;
 pub mod material;
+pub mod medium;
 pub mod objects;
 pub mod ray;
 pub mod ray_hit;
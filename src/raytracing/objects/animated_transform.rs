@@ -0,0 +1,360 @@
+use crate::{
+    math::{quaternion::Quaternion, vec3::Vec3},
+    raytracing::{
+        aabb::{BoundingBox, BoundingBoxError, AABB},
+        animation::{Interpolation, Track},
+        ray::Ray,
+        ray_hit::{HitResult, RayHitTester},
+    },
+};
+
+use super::HittableObject;
+
+/// Number of uniform samples of the shutter interval for bounds of rotation
+const BOUNDS_SAMPLES: usize = 16;
+
+/// Scale, rotation and translation of the instance, applied in this order
+#[derive(Clone, Copy)]
+struct Transform {
+    translation: Vec3,
+    rotation: Quaternion,
+    scale: Vec3,
+}
+
+impl Transform {
+    fn point_to_world(&self, point: &Vec3) -> Vec3 {
+        self.rotation.rotate(&(self.scale * *point)) + self.translation
+    }
+
+    fn point_to_local(&self, point: &Vec3) -> Vec3 {
+        self.vector_to_local(&(point - &self.translation))
+    }
+
+    fn vector_to_world(&self, vector: &Vec3) -> Vec3 {
+        self.rotation.rotate(&(self.scale * *vector))
+    }
+
+    fn vector_to_local(&self, vector: &Vec3) -> Vec3 {
+        self.inverse_scale() * self.rotation.conjugate().rotate(vector)
+    }
+
+    /// Normals are transformed by the inverse transpose
+    fn normal_to_world(&self, normal: &Vec3) -> Vec3 {
+        self.rotation
+            .rotate(&(self.inverse_scale() * *normal))
+            .norm()
+    }
+
+    fn inverse_scale(&self) -> Vec3 {
+        Vec3::new(
+            1. / self.scale.x(),
+            1. / self.scale.y(),
+            1. / self.scale.z(),
+        )
+    }
+}
+
+/// Instance moving by keyframed translation, rotation and non-zero scale. Rays hit it
+/// transformed at their time, which blurs the motion during the exposure
+pub struct AnimatedTransform {
+    instance: Box<dyn HittableObject + Send + Sync>,
+    translation: Track<Vec3>,
+    rotation: Track<Quaternion>,
+    scale: Track<Vec3>,
+}
+
+impl AnimatedTransform {
+    pub fn new(
+        instance: Box<dyn HittableObject + Send + Sync>,
+        translation: Track<Vec3>,
+        rotation: Track<Quaternion>,
+        scale: Track<Vec3>,
+    ) -> Self {
+        Self {
+            instance,
+            translation,
+            rotation,
+            scale,
+        }
+    }
+
+    fn transform(&self, time: f32) -> Transform {
+        Transform {
+            translation: self.translation.value(time),
+            rotation: self.rotation.value(time).norm(),
+            scale: self.scale.value(time),
+        }
+    }
+
+    /// Bounds of the `local` box rotated during the interval. Linear rotations are
+    /// sampled, each point moves by a circular arc between the samples. Curves don't keep
+    /// the axis, so they are bounded by the sphere around the origin
+    fn rotated_bounds(&self, local: &AABB, start_time: f32, end_time: f32) -> AABB {
+        let corners = box_corners(local);
+        let radius = corners.iter().map(Vec3::length).fold(0., f32::max);
+        let curved = self.rotation.keyframes().windows(2).any(|pair| {
+            pair[1].time > start_time
+                && pair[0].time < end_time
+                && matches!(
+                    pair[0].interpolation,
+                    Interpolation::CatmullRom | Interpolation::Bezier
+                )
+        });
+        if curved {
+            let radius = Vec3::new(radius, radius, radius);
+            return AABB::new(-radius, radius);
+        }
+
+        // Samples include the keyframes, so they don't skip jumps of constant rotations
+        let mut times: Vec<f32> = (0..=BOUNDS_SAMPLES)
+            .map(|i| start_time + (end_time - start_time) * i as f32 / BOUNDS_SAMPLES as f32)
+            .collect();
+        let keyframe_times = self.rotation.keyframes().iter().map(|key| key.time);
+        times.extend(keyframe_times.filter(|time| start_time < *time && *time < end_time));
+        times.sort_by(f32::total_cmp);
+
+        let rotations: Vec<Quaternion> = times
+            .iter()
+            .map(|time| self.rotation.value(*time).norm())
+            .collect();
+        let rotated = rotations
+            .iter()
+            .flat_map(|rotation| corners.iter().map(|corner| rotation.rotate(corner)));
+        let (min, max) = points_bounds(rotated);
+
+        // Arc of angle `step` is within `2 r sin(step / 4)` of one of its ends
+        let step = rotations
+            .windows(2)
+            .map(|pair| 2. * f32::acos(pair[0].dot(&pair[1]).abs().min(1.)))
+            .fold(0., f32::max);
+        let margin = 2. * radius * f32::sin(step / 4.);
+        let margin = Vec3::new(margin, margin, margin);
+        AABB::new(min - margin, max + margin)
+    }
+}
+
+/// Corners of the box
+fn box_corners(aabb: &AABB) -> [Vec3; 8] {
+    let (min, max) = (&aabb.minimum, &aabb.maximum);
+    std::array::from_fn(|i| {
+        Vec3::new(
+            if i & 1 == 0 { min.x() } else { max.x() },
+            if i & 2 == 0 { min.y() } else { max.y() },
+            if i & 4 == 0 { min.z() } else { max.z() },
+        )
+    })
+}
+
+/// Smallest and largest coordinates of the points
+fn points_bounds(points: impl Iterator<Item = Vec3>) -> (Vec3, Vec3) {
+    let mut min = Vec3::new(f32::INFINITY, f32::INFINITY, f32::INFINITY);
+    let mut max = Vec3::new(-f32::INFINITY, -f32::INFINITY, -f32::INFINITY);
+    for point in points {
+        min = Vec3::new(
+            min.x().min(point.x()),
+            min.y().min(point.y()),
+            min.z().min(point.z()),
+        );
+        max = Vec3::new(
+            max.x().max(point.x()),
+            max.y().max(point.y()),
+            max.z().max(point.z()),
+        );
+    }
+    (min, max)
+}
+
+impl HittableObject for AnimatedTransform {}
+
+impl BoundingBox for AnimatedTransform {
+    /// Translation and scale are bounded by convex hulls of their curves, see
+    /// [Track::control_points]. Scaled instance is rotated as a whole box
+    fn bounding_box(&self, start_time: f32, end_time: f32) -> Result<AABB, BoundingBoxError> {
+        let aabb = self.instance.bounding_box(start_time, end_time)?;
+        let scales = self.scale.control_points(start_time, end_time);
+        let (scale_min, scale_max) = points_bounds(scales.into_iter());
+
+        // Every scale within the bounds keeps the instance inside of the scaled box
+        let range = |axis: usize| {
+            let products = [
+                aabb.minimum[axis] * scale_min[axis],
+                aabb.minimum[axis] * scale_max[axis],
+                aabb.maximum[axis] * scale_min[axis],
+                aabb.maximum[axis] * scale_max[axis],
+            ];
+            let min = products.into_iter().fold(f32::INFINITY, f32::min);
+            let max = products.into_iter().fold(-f32::INFINITY, f32::max);
+            (min, max)
+        };
+        let (x, y, z) = (range(0), range(1), range(2));
+        let local = AABB::new(Vec3::new(x.0, y.0, z.0), Vec3::new(x.1, y.1, z.1));
+
+        let rotated = self.rotated_bounds(&local, start_time, end_time);
+        let translations = self.translation.control_points(start_time, end_time);
+        let (translation_min, translation_max) = points_bounds(translations.into_iter());
+        Ok(AABB::new(
+            rotated.minimum + translation_min,
+            rotated.maximum + translation_max,
+        ))
+    }
+}
+
+impl RayHitTester for AnimatedTransform {
+    fn hit(&self, ray: &Ray, min_distance: f32, max_distance: f32) -> Option<HitResult> {
+        // Direction is not normalized, so distances along both rays are the same
+        let transform = self.transform(ray.time);
+        let local_ray = Ray::new(
+            transform.point_to_local(&ray.origin),
+            transform.vector_to_local(&ray.direction),
+            ray.time,
+        )
+        .with_spread(ray.spread)
+        .with_width(ray.width);
+
+        self.instance
+            .hit(&local_ray, min_distance, max_distance)
+            .map(|mut hit| {
+                hit.location = transform.point_to_world(&hit.location);
+                hit.normal = transform.normal_to_world(&hit.normal);
+                hit.dpdu = transform.vector_to_world(&hit.dpdu);
+                hit.dpdv = transform.vector_to_world(&hit.dpdv);
+                // Inverse transpose keeps the side of the normal towards the ray,
+                // so the instance's front face stays valid
+                hit
+            })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use crate::{
+        math::{quaternion::Quaternion, vec3::Vec3},
+        raytracing::{
+            aabb::BoundingBox,
+            animation::{Animatable, Interpolation, Keyframe, Track},
+            material::{MatDielectric, Material},
+            objects::{Cube, Sphere},
+            ray::Ray,
+            ray_hit::RayHitTester,
+        },
+    };
+
+    use super::{box_corners, AnimatedTransform, AABB};
+
+    fn material() -> Arc<Material> {
+        Arc::new(Material::Dielectric(MatDielectric::new(1.5)))
+    }
+
+    #[test]
+    fn hit_test() {
+        // Unit sphere moving along x and stretched twice along y
+        let sphere = AnimatedTransform::new(
+            Box::new(Sphere::new(Vec3::zero(), 1., material())),
+            Track::new(vec![
+                Keyframe::new(0., Vec3::zero(), Interpolation::Linear),
+                Keyframe::new(1., Vec3::new(4., 0., 0.), Interpolation::Linear),
+            ]),
+            Track::constant(Quaternion::from_axis_angle(&Vec3::new(0., 0., 1.), 90.)),
+            Track::constant(Vec3::new(2., 1., 1.)),
+        );
+
+        let ray = Ray::new(Vec3::new(2., 10., 0.), Vec3::new(0., -2., 0.), 0.5);
+        let hit = sphere.hit(&ray, 0.001, f32::INFINITY).unwrap();
+        assert!((hit.location - Vec3::new(2., 2., 0.)).length() < 1e-4);
+        assert!((ray.at(hit.distance) - hit.location).length() < 1e-4);
+        assert!((hit.normal - Vec3::new(0., 1., 0.)).length() < 1e-4);
+        assert!(hit.front_face);
+
+        // Ray from inside hits the back face, with normal against the ray
+        let ray = Ray::new(Vec3::new(4., 0., 0.), Vec3::new(1., 0., 0.), 1.);
+        let hit = sphere.hit(&ray, 0.001, f32::INFINITY).unwrap();
+        assert!((hit.location - Vec3::new(5., 0., 0.)).length() < 1e-4);
+        assert!((hit.normal - Vec3::new(-1., 0., 0.)).length() < 1e-4);
+        assert!(!hit.front_face);
+
+        let ray = Ray::new(Vec3::new(2., 10., 0.), Vec3::new(0., -1., 0.), 0.);
+        assert!(sphere.hit(&ray, 0.001, f32::INFINITY).is_none());
+    }
+
+    #[test]
+    fn bounding_box_test() {
+        // Cube away from the axis swings around it during the shutter interval
+        let up = Vec3::new(0., 1., 0.);
+        let cube = AnimatedTransform::new(
+            Box::new(Cube::new(
+                Vec3::new(2., 0., 0.),
+                Vec3::new(3., 1., 1.),
+                material(),
+            )),
+            Track::constant(Vec3::zero()),
+            Track::new(vec![
+                Keyframe::new(0., Quaternion::identity(), Interpolation::Linear),
+                Keyframe::new(
+                    1.,
+                    Quaternion::from_axis_angle(&up, 170.),
+                    Interpolation::Linear,
+                ),
+            ]),
+            Track::constant(Vec3::new(1., 1., 1.)),
+        );
+
+        let aabb = cube.bounding_box(0.2, 0.8).unwrap();
+        for i in 0..=1000 {
+            let time = 0.2 + 0.6 * i as f32 / 1000.;
+            let rotation = Quaternion::from_axis_angle(&up, 170. * time);
+            for corner in [Vec3::new(3., 1., 0.), Vec3::new(3., 0., 1.)] {
+                let corner = rotation.rotate(&corner);
+                for axis in 0..3 {
+                    assert!(aabb.minimum[axis] <= corner[axis] + 1e-4);
+                    assert!(corner[axis] <= aabb.maximum[axis] + 1e-4);
+                }
+            }
+        }
+        // Only the swept part of the circle is covered
+        assert!(aabb.minimum.x() > -3. && aabb.maximum.z() < 0.5);
+    }
+    #[test]
+    fn curve_bounding_box_test() {
+        // Curves overshoot their keyframes and the rotation changes its axis
+        fn curve<T: Animatable>(a: T, b: T, c: T) -> Track<T> {
+            Track::new(vec![
+                Keyframe::new(0., a, Interpolation::CatmullRom),
+                Keyframe::new(1., b, Interpolation::CatmullRom),
+                Keyframe::new(2., c, Interpolation::CatmullRom),
+            ])
+        }
+        let tilt = |axis: Vec3| Quaternion::from_axis_angle(&axis, 60.);
+        let cube = AnimatedTransform::new(
+            Box::new(Cube::new(
+                Vec3::new(1., 0., 0.),
+                Vec3::new(2., 1., 1.),
+                material(),
+            )),
+            curve(Vec3::zero(), Vec3::new(1., 2., 0.), Vec3::new(-1., 0., 0.)),
+            curve(
+                Quaternion::identity(),
+                tilt(Vec3::new(0., 0., 1.)),
+                tilt(Vec3::new(1., 0., 0.)),
+            ),
+            Track::new(vec![
+                Keyframe::new(0., Vec3::new(1., 1., 1.), Interpolation::Bezier)
+                    .with_handles(Vec3::new(1., 1., 1.), Vec3::new(3., 0.5, 1.)),
+                Keyframe::new(2., Vec3::new(1., 1., 1.), Interpolation::Bezier),
+            ]),
+        );
+
+        let aabb = cube.bounding_box(0.3, 1.8).unwrap();
+        for i in 0..=1000 {
+            let transform = cube.transform(0.3 + 1.5 * i as f32 / 1000.);
+            for corner in box_corners(&AABB::new(Vec3::new(1., 0., 0.), Vec3::new(2., 1., 1.))) {
+                let corner = transform.point_to_world(&corner);
+                for axis in 0..3 {
+                    assert!(aabb.minimum[axis] <= corner[axis] + 1e-4);
+                    assert!(corner[axis] <= aabb.maximum[axis] + 1e-4);
+                }
+            }
+        }
+    }
+}
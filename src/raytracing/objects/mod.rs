@@ -1,5 +1,11 @@
-use super::{aabb::BoundingBox, ray_hit::RayHitTester};
+use crate::math::vec3::Vec3;
 
+use super::{
+    aabb::BoundingBox,
+    ray_hit::{HitResult, RayHitTester},
+};
+
+pub mod animated_transform;
 pub mod bvh;
 pub mod cube;
 pub mod moving_sphere;
@@ -9,6 +15,7 @@ pub mod translate;
 pub mod world;
 pub mod yaw_rotation;
 
+pub use animated_transform::AnimatedTransform;
 pub use bvh::BvhNode;
 pub use cube::Cube;
 pub use moving_sphere::MovingSphere;
@@ -20,3 +27,14 @@ pub use translate::Translate;
 pub use world::HittableList;
 
 pub trait HittableObject: RayHitTester + BoundingBox {}
+
+/// Object with surface that can be sampled by direction, e.g. emitter used as
+/// [area light](crate::raytracing::light::AreaLight)
+pub trait SampleableObject: HittableObject {
+    /// Random direction from `origin` towards the surface
+    fn sample_direction(&self, origin: &Vec3) -> Vec3;
+
+    /// Probability density over solid angle of [sampling](Self::sample_direction)
+    /// direction from `origin` to the hit
+    fn direction_pdf(&self, origin: &Vec3, hit_result: &HitResult) -> f32;
+}
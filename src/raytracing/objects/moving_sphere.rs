@@ -82,16 +82,14 @@ impl RayHitTester for MovingSphere {
             None
         }
         // Find the nearest root that lies in the acceptable range.
+        // The farther one is visible through cutouts of the nearest
         else {
             let sqrtd = discriminant.sqrt();
-            let mut root = (-half_b - sqrtd) / a;
-            if root < min_distance || max_distance < root {
-                root = (-half_b + sqrtd) / a;
-                if root < min_distance || max_distance < root {
-                    return None;
-                }
-            }
-            Some(HitResult::new(self, ray, root, self.material.clone()))
+            [(-half_b - sqrtd) / a, (-half_b + sqrtd) / a]
+                .into_iter()
+                .filter(|root| min_distance <= *root && *root <= max_distance)
+                .map(|root| HitResult::new(self, ray, root, self.material.clone()))
+                .find(|hit_result| hit_result.material.is_opaque(hit_result))
         }
     }
 }
@@ -129,9 +127,24 @@ impl UvMapper for MovingSphere {
 
         let theta = f32::acos(-normal.y());
         let phi = f32::atan2(-normal.z(), normal.x()) + PI;
-        UvCoords {
-            u: phi / (2. * PI),
-            v: theta / PI,
-        }
+        UvCoords::new(phi / (2. * PI), theta / PI)
+    }
+
+    fn get_uv_derivatives(&self, normal: &Vec3) -> (Vec3, Vec3) {
+        // Location is `center + radius * normal`, where
+        // normal = (sin(θ)cos(φ), -cos(θ), -sin(θ)sin(φ)), θ = πv, φ = 2πu - π
+        let sin_theta = f32::sqrt(1. - normal.y() * normal.y()).max(1e-6);
+        let dpdu = (2. * PI * self.radius) * Vec3::new(normal.z(), 0., -normal.x());
+        let dpdv = (PI * self.radius)
+            * Vec3::new(
+                -normal.x() * normal.y() / sin_theta,
+                sin_theta,
+                -normal.z() * normal.y() / sin_theta,
+            );
+        (dpdu, dpdv)
+    }
+
+    fn uv_extent(&self) -> f32 {
+        PI * self.radius
     }
 }
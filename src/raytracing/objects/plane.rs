@@ -1,5 +1,7 @@
 use std::sync::Arc;
 
+use rand::{thread_rng, Rng};
+
 use crate::{
     math::vec3::Vec3,
     raytracing::{
@@ -10,7 +12,7 @@ use crate::{
     },
 };
 
-use super::HittableObject;
+use super::{HittableObject, SampleableObject};
 
 pub struct PlaneZ {
     pub x_start: f32,
@@ -52,10 +54,16 @@ impl PlaneZ {
         }
     }
 
-    fn get_uv(&self, x: &f32, y: &f32) -> UvCoords {
+    /// `footprint` - width of the ray at hit location
+    fn get_uv(&self, x: &f32, y: &f32, footprint: f32) -> UvCoords {
         let u = (x - self.x_start) / (self.x_end - self.x_start);
         let v = (y - self.y_start) / (self.y_end - self.y_start);
-        UvCoords { u, v }
+        let extent = f32::min(self.x_end - self.x_start, self.y_end - self.y_start);
+        UvCoords {
+            u,
+            v,
+            footprint: footprint / extent,
+        }
     }
 }
 
@@ -83,19 +91,39 @@ impl RayHitTester for PlaneZ {
         if !front_face {
             normal = -normal;
         }
-        Some(HitResult {
+        let hit_result = HitResult {
             location,
             normal,
             distance: depth,
             front_face,
             material: self.material.clone(),
-            uv: self.get_uv(&x, &y),
-        })
+            uv: self.get_uv(&x, &y, ray.footprint(depth)),
+            dpdu: Vec3::new(self.x_end - self.x_start, 0., 0.),
+            dpdv: Vec3::new(0., self.y_end - self.y_start, 0.),
+        };
+        hit_result
+            .material
+            .is_opaque(&hit_result)
+            .then_some(hit_result)
     }
 }
 
 impl HittableObject for PlaneZ {}
 
+impl SampleableObject for PlaneZ {
+    fn sample_direction(&self, origin: &Vec3) -> Vec3 {
+        let mut rng = thread_rng();
+        let x = rng.gen_range(self.x_start..=self.x_end);
+        let y = rng.gen_range(self.y_start..=self.y_end);
+        Vec3::new(x, y, self.z) - *origin
+    }
+
+    fn direction_pdf(&self, origin: &Vec3, hit_result: &HitResult) -> f32 {
+        let area = (self.x_end - self.x_start) * (self.y_end - self.y_start);
+        plane_direction_pdf(origin, hit_result, area)
+    }
+}
+
 impl BoundingBox for PlaneZ {
     fn bounding_box(
         &self,
@@ -120,10 +148,16 @@ impl PlaneX {
         }
     }
 
-    fn get_uv(&self, y: &f32, z: &f32) -> UvCoords {
+    /// `footprint` - width of the ray at hit location
+    fn get_uv(&self, y: &f32, z: &f32, footprint: f32) -> UvCoords {
         let u = (y - self.y_start) / (self.y_end - self.y_start);
         let v = (z - self.z_start) / (self.z_end - self.z_start);
-        UvCoords { u, v }
+        let extent = f32::min(self.y_end - self.y_start, self.z_end - self.z_start);
+        UvCoords {
+            u,
+            v,
+            footprint: footprint / extent,
+        }
     }
 }
 
@@ -151,19 +185,39 @@ impl RayHitTester for PlaneX {
         if !front_face {
             normal = -normal;
         }
-        Some(HitResult {
+        let hit_result = HitResult {
             location,
             normal,
             distance: depth,
             front_face,
             material: self.material.clone(),
-            uv: self.get_uv(&y, &z),
-        })
+            uv: self.get_uv(&y, &z, ray.footprint(depth)),
+            dpdu: Vec3::new(0., self.y_end - self.y_start, 0.),
+            dpdv: Vec3::new(0., 0., self.z_end - self.z_start),
+        };
+        hit_result
+            .material
+            .is_opaque(&hit_result)
+            .then_some(hit_result)
     }
 }
 
 impl HittableObject for PlaneX {}
 
+impl SampleableObject for PlaneX {
+    fn sample_direction(&self, origin: &Vec3) -> Vec3 {
+        let mut rng = thread_rng();
+        let y = rng.gen_range(self.y_start..=self.y_end);
+        let z = rng.gen_range(self.z_start..=self.z_end);
+        Vec3::new(self.x, y, z) - *origin
+    }
+
+    fn direction_pdf(&self, origin: &Vec3, hit_result: &HitResult) -> f32 {
+        let area = (self.y_end - self.y_start) * (self.z_end - self.z_start);
+        plane_direction_pdf(origin, hit_result, area)
+    }
+}
+
 impl BoundingBox for PlaneX {
     fn bounding_box(
         &self,
@@ -188,10 +242,16 @@ impl PlaneY {
         }
     }
 
-    fn get_uv(&self, x: &f32, z: &f32) -> UvCoords {
+    /// `footprint` - width of the ray at hit location
+    fn get_uv(&self, x: &f32, z: &f32, footprint: f32) -> UvCoords {
         let u = (x - self.x_start) / (self.x_end - self.x_start);
         let v = (z - self.z_start) / (self.z_end - self.z_start);
-        UvCoords { u, v }
+        let extent = f32::min(self.x_end - self.x_start, self.z_end - self.z_start);
+        UvCoords {
+            u,
+            v,
+            footprint: footprint / extent,
+        }
     }
 }
 
@@ -219,19 +279,39 @@ impl RayHitTester for PlaneY {
         if !front_face {
             normal = -normal;
         }
-        Some(HitResult {
+        let hit_result = HitResult {
             location,
             normal,
             distance: depth,
             front_face,
             material: self.material.clone(),
-            uv: self.get_uv(&x, &z),
-        })
+            uv: self.get_uv(&x, &z, ray.footprint(depth)),
+            dpdu: Vec3::new(self.x_end - self.x_start, 0., 0.),
+            dpdv: Vec3::new(0., 0., self.z_end - self.z_start),
+        };
+        hit_result
+            .material
+            .is_opaque(&hit_result)
+            .then_some(hit_result)
     }
 }
 
 impl HittableObject for PlaneY {}
 
+impl SampleableObject for PlaneY {
+    fn sample_direction(&self, origin: &Vec3) -> Vec3 {
+        let mut rng = thread_rng();
+        let x = rng.gen_range(self.x_start..=self.x_end);
+        let z = rng.gen_range(self.z_start..=self.z_end);
+        Vec3::new(x, self.y, z) - *origin
+    }
+
+    fn direction_pdf(&self, origin: &Vec3, hit_result: &HitResult) -> f32 {
+        let area = (self.x_end - self.x_start) * (self.z_end - self.z_start);
+        plane_direction_pdf(origin, hit_result, area)
+    }
+}
+
 impl BoundingBox for PlaneY {
     fn bounding_box(
         &self,
@@ -242,3 +322,13 @@ impl BoundingBox for PlaneY {
     }
 }
 // -------------------------------
+
+/// Uniform area density converted to solid angle from `origin`
+fn plane_direction_pdf(origin: &Vec3, hit_result: &HitResult, area: f32) -> f32 {
+    let to_hit = hit_result.location - *origin;
+    let cos_theta = hit_result.normal.dot(&to_hit.norm()).abs();
+    if cos_theta == 0. || area == 0. {
+        return 0.;
+    }
+    to_hit.length_squared() / (cos_theta * area)
+}
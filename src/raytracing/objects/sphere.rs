@@ -1,7 +1,7 @@
 use std::{f32::consts::PI, sync::Arc};
 
 use crate::{
-    math::vec3::Vec3,
+    math::{onb::Onb, random_in_cone, random_in_unit_sphere, vec3::Vec3},
     raytracing::{
         aabb::{BoundingBox, BoundingBoxError, AABB},
         material::Material,
@@ -11,7 +11,10 @@ use crate::{
     },
 };
 
-use super::HittableObject;
+use super::{HittableObject, SampleableObject};
+
+/// Relative distance from the silhouette where sampled directions are clamped
+const SILHOUETTE_MARGIN: f32 = 1e-3;
 
 pub struct Sphere {
     pub center: Vec3,
@@ -27,6 +30,12 @@ impl Sphere {
             material,
         }
     }
+
+    /// Cosine of the half angle of the cone covering the sphere
+    fn cos_theta_max(&self, distance_squared: f32) -> f32 {
+        let sin2_theta_max = self.radius * self.radius / distance_squared;
+        f32::sqrt(f32::max(0., 1. - sin2_theta_max))
+    }
 }
 
 impl HittableObject for Sphere {}
@@ -63,17 +72,57 @@ impl RayHitTester for Sphere {
             None
         }
         // Find the nearest root that lies in the acceptable range.
+        // The farther one is visible through cutouts of the nearest
         else {
             let sqrtd = discriminant.sqrt();
-            let mut root = (-half_b - sqrtd) / a;
-            if root < min_distance || max_distance < root {
-                root = (-half_b + sqrtd) / a;
-                if root < min_distance || max_distance < root {
-                    return None;
-                }
+            [(-half_b - sqrtd) / a, (-half_b + sqrtd) / a]
+                .into_iter()
+                .filter(|root| min_distance <= *root && *root <= max_distance)
+                .map(|root| HitResult::new(self, ray, root, self.material.clone()))
+                .find(|hit_result| hit_result.material.is_opaque(hit_result))
+        }
+    }
+}
+
+impl SampleableObject for Sphere {
+    fn sample_direction(&self, origin: &Vec3) -> Vec3 {
+        let to_center = self.center - *origin;
+        let distance_squared = to_center.length_squared();
+        if distance_squared <= self.radius * self.radius {
+            // Uniform point on the surface around the origin inside
+            let point = self.center + self.radius * random_in_unit_sphere().norm();
+            return point - *origin;
+        }
+        // Cone of directions covering the sphere. Directions at the very edge are pulled
+        // inside the silhouette, so rounding in the hit test doesn't make them miss
+        let cos_theta_max = self.cos_theta_max(distance_squared);
+        let mut local = random_in_cone(cos_theta_max);
+        let sin_theta_max =
+            (1. - SILHOUETTE_MARGIN) * f32::sqrt(self.radius * self.radius / distance_squared);
+        let sin_theta = f32::hypot(local.x(), local.y());
+        if sin_theta > sin_theta_max {
+            let scale = sin_theta_max / sin_theta;
+            local = Vec3::new(
+                scale * local.x(),
+                scale * local.y(),
+                f32::sqrt(1. - sin_theta_max * sin_theta_max),
+            );
+        }
+        Onb::from_w(&to_center).to_world(&local)
+    }
+
+    fn direction_pdf(&self, origin: &Vec3, hit_result: &HitResult) -> f32 {
+        let distance_squared = (self.center - *origin).length_squared();
+        if distance_squared <= self.radius * self.radius {
+            let to_hit = hit_result.location - *origin;
+            let cos_theta = hit_result.normal.dot(&to_hit.norm()).abs();
+            if cos_theta == 0. {
+                return 0.;
             }
-            Some(HitResult::new(self, ray, root, self.material.clone()))
+            let area = 4. * PI * self.radius * self.radius;
+            return to_hit.length_squared() / (cos_theta * area);
         }
+        1. / (2. * PI * (1. - self.cos_theta_max(distance_squared)))
     }
 }
 
@@ -103,9 +152,86 @@ impl UvMapper for Sphere {
 
         let theta = f32::acos(-normal.y());
         let phi = f32::atan2(-normal.z(), normal.x()) + PI;
-        UvCoords {
-            u: phi / (2. * PI),
-            v: theta / PI,
-        }
+        UvCoords::new(phi / (2. * PI), theta / PI)
+    }
+
+    fn get_uv_derivatives(&self, normal: &Vec3) -> (Vec3, Vec3) {
+        // Location is `center + radius * normal`, where
+        // normal = (sin(θ)cos(φ), -cos(θ), -sin(θ)sin(φ)), θ = πv, φ = 2πu - π
+        let sin_theta = f32::sqrt(1. - normal.y() * normal.y()).max(1e-6);
+        let dpdu = (2. * PI * self.radius) * Vec3::new(normal.z(), 0., -normal.x());
+        let dpdv = (PI * self.radius)
+            * Vec3::new(
+                -normal.x() * normal.y() / sin_theta,
+                sin_theta,
+                -normal.z() * normal.y() / sin_theta,
+            );
+        (dpdu, dpdv)
+    }
+
+    fn uv_extent(&self) -> f32 {
+        PI * self.radius
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{f32::consts::PI, sync::Arc};
+
+    use crate::{
+        math::vec3::Vec3,
+        raytracing::{
+            material::{MatCutout, MatDielectric, Material},
+            ray::Ray,
+            ray_hit::RayHitTester,
+            texture::{SolidColorTexture, Texture, UvMapper},
+        },
+    };
+
+    use super::Sphere;
+
+    #[test]
+    fn uv_derivatives_test() {
+        let sphere = Sphere::new(
+            Vec3::zero(),
+            2.,
+            Arc::new(Material::Dielectric(MatDielectric::new(1.5))),
+        );
+        let location = |u: f32, v: f32| {
+            let (sin_theta, cos_theta) = f32::sin_cos(PI * v);
+            let (sin_phi, cos_phi) = f32::sin_cos(2. * PI * u - PI);
+            2. * Vec3::new(sin_theta * cos_phi, -cos_theta, -sin_theta * sin_phi)
+        };
+
+        let normal = Vec3::new(0.3, 0.5, -0.6).norm();
+        let uv = sphere.get_uv_coords(&normal);
+        assert!((&location(uv.u, uv.v) / 2. - normal).length() < 1e-5);
+
+        let (dpdu, dpdv) = sphere.get_uv_derivatives(&normal);
+        let delta = 1e-3;
+        let numeric_dpdu = &(location(uv.u + delta, uv.v) - location(uv.u, uv.v)) / delta;
+        let numeric_dpdv = &(location(uv.u, uv.v + delta) - location(uv.u, uv.v)) / delta;
+        assert!((dpdu - numeric_dpdu).length() < 0.02 * dpdu.length());
+        assert!((dpdv - numeric_dpdv).length() < 0.02 * dpdv.length());
+    }
+
+    #[test]
+    fn cutout_test() {
+        let cutout = |opacity: f32| {
+            let base = Arc::new(Material::Dielectric(MatDielectric::new(1.5)));
+            let opacity = Arc::new(Texture::SolidColor(SolidColorTexture::new(
+                opacity, opacity, opacity,
+            )));
+            Sphere::new(
+                Vec3::zero(),
+                1.,
+                Arc::new(Material::Cutout(MatCutout::new(base, opacity))),
+            )
+        };
+        let ray = Ray::new(Vec3::new(0., 0., -5.), Vec3::new(0., 0., 1.), 0.);
+
+        let hit = cutout(1.).hit(&ray, 0.001, f32::INFINITY).unwrap();
+        assert!((hit.distance - 4.).abs() < 1e-5);
+        assert!(cutout(0.).hit(&ray, 0.001, f32::INFINITY).is_none());
     }
 }
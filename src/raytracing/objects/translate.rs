@@ -37,7 +37,9 @@ impl BoundingBox for Translate {
 
 impl RayHitTester for Translate {
     fn hit(&self, ray: &Ray, min_distance: f32, max_distance: f32) -> Option<HitResult> {
-        let moved_ray = Ray::new(ray.origin - self.offset, ray.direction, ray.time);
+        let moved_ray = Ray::new(ray.origin - self.offset, ray.direction, ray.time)
+            .with_spread(ray.spread)
+            .with_width(ray.width);
         self.instance
             .hit(&moved_ray, min_distance, max_distance)
             .map(|mut hit| {
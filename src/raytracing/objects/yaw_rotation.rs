@@ -86,13 +86,24 @@ impl RayHitTester for YawRotation {
 
         new_direction.set_x(self.cos_theta * direction.x() - self.sin_theta * direction.z());
         new_direction.set_z(self.sin_theta * direction.x() + self.cos_theta * direction.z());
-        let ray_rotated = Ray::new(new_origin, new_direction, ray.time);
+        let ray_rotated = Ray::new(new_origin, new_direction, ray.time)
+            .with_spread(ray.spread)
+            .with_width(ray.width);
 
         self.instance
             .hit(&ray_rotated, min_distance, max_distance)
             .map(|mut hit| {
                 let location = hit.location;
                 let normal = hit.normal;
+                let rotate = |v: &Vec3| {
+                    Vec3::new(
+                        self.cos_theta * v.x() + self.sin_theta * v.z(),
+                        v.y(),
+                        (-self.sin_theta) * v.x() + self.cos_theta * v.z(),
+                    )
+                };
+                hit.dpdu = rotate(&hit.dpdu);
+                hit.dpdv = rotate(&hit.dpdv);
 
                 hit.location
                     .set_x(self.cos_theta * location.x() + self.sin_theta * location.z());
@@ -4,6 +4,11 @@ pub struct Ray {
     pub origin: Vec3,
     pub direction: Vec3,
     pub time: f32,
+    /// Angle of the cone traced by the ray, i.e. growth of its width per unit of distance.
+    /// Used to estimate texture footprint, 0 for infinitely thin rays
+    pub spread: f32,
+    /// Width of the ray at its origin, e.g. pixel size of orthographic camera
+    pub width: f32,
 }
 
 impl Ray {
@@ -12,11 +17,28 @@ impl Ray {
             origin,
             direction,
             time,
+            spread: 0.,
+            width: 0.,
         }
     }
 
+    pub fn with_spread(mut self, spread: f32) -> Self {
+        self.spread = spread;
+        self
+    }
+
+    pub fn with_width(mut self, width: f32) -> Self {
+        self.width = width;
+        self
+    }
+
     /// Move forward by 't' from 'origin', along the ray specified by 'direction'  
     pub fn at(&self, t: f32) -> Vec3 {
         self.origin + (t * self.direction)
     }
+
+    /// Width of the ray cone at distance 't'
+    pub fn footprint(&self, t: f32) -> f32 {
+        self.width + self.spread * t * self.direction.length()
+    }
 }
@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use crate::math::vec3::Vec3;
+use crate::math::{onb::Onb, vec3::Vec3};
 
 use super::{
     material::Material,
@@ -8,6 +8,7 @@ use super::{
     texture::{UvCoords, UvMapper},
 };
 
+#[derive(Clone)]
 pub struct HitResult {
     pub location: Vec3,
     pub normal: Vec3,
@@ -16,6 +17,9 @@ pub struct HitResult {
     pub material: Arc<Material>,
     // texture coords on surface
     pub uv: UvCoords,
+    /// Derivatives of the location by texture coordinates, tangent frame of the surface
+    pub dpdu: Vec3,
+    pub dpdv: Vec3,
 }
 
 impl HitResult {
@@ -27,11 +31,15 @@ impl HitResult {
     ) -> Self {
         let location = ray.at(distance);
         let mut normal = object.get_normal(&location, ray);
+        // Texture coordinates are defined by the outward normal
+        let mut uv = object.get_uv_coords(&normal);
+        uv.footprint = ray.footprint(distance) / object.uv_extent();
+        let (dpdu, dpdv) = object.get_uv_derivatives(&normal);
+
         let front_face = ray.direction.dot(&normal) < 0.;
         if !front_face {
             normal = -normal;
         }
-        let uv = object.get_uv_coords(&normal);
         Self {
             location,
             normal,
@@ -39,7 +47,19 @@ impl HitResult {
             front_face,
             material,
             uv,
+            dpdu,
+            dpdv,
+        }
+    }
+
+    /// Shading frame with `u` axis along `dpdu` and `v` axis on the side of `dpdv`.
+    /// Falls back to an arbitrary tangent where the derivatives vanish, e.g. at sphere poles
+    pub fn tangent_frame(&self) -> Onb {
+        let mut frame = Onb::from_wu(&self.normal, &self.dpdu);
+        if frame.v.dot(&self.dpdv) < 0. {
+            frame.v = -frame.v;
         }
+        frame
     }
 }
 
@@ -1,21 +1,41 @@
 use rand::{thread_rng, Rng};
 use rayon::prelude::{IndexedParallelIterator, IntoParallelRefMutIterator, ParallelIterator};
 
-use crate::{math::vec3::Vec3, utils::progress_watcher::ProgressObserver};
+use crate::{
+    math::{
+        power_heuristic, random_in_unit_sphere,
+        spectrum::{path_color, SampledWavelengths},
+        vec3::Vec3,
+    },
+    utils::progress_watcher::ProgressObserver,
+};
 
-use super::{camera::Camera, objects::HittableObject, ray::Ray};
+use super::{
+    background::{Background, PhysicalSky},
+    camera::Camera,
+    light::{Light, SunLight},
+    medium::{Medium, MediumStack},
+    objects::HittableObject,
+    ray::Ray,
+    ray_hit::HitResult,
+};
 
 pub struct Renderer {
-    pub camera: Camera,
+    pub camera: Box<dyn Camera + Send + Sync>,
     pub samples_per_pixel: usize,
     pub max_ray_bounces: usize,
-    pub background: Vec3,
+    pub background: Background,
+    /// Trace wavelengths instead of RGB colors, needed for dispersion. Metals and thin
+    /// films reflect by their spectra, other colors are uplifted to spectra at hits
+    pub spectral: bool,
+    /// Lights sampled from every diffuse hit, area lights have to be also added to `objects`
+    pub lights: Vec<Light>,
     pub objects: Box<dyn HittableObject + Send + Sync>,
 }
 
 impl Renderer {
     pub fn init(
-        camera: Camera,
+        camera: Box<dyn Camera + Send + Sync>,
         samples_per_pixel: usize,
         max_ray_bounces: usize,
         objects: Box<dyn HittableObject + Send + Sync>,
@@ -25,10 +45,18 @@ impl Renderer {
             samples_per_pixel,
             max_ray_bounces,
             objects,
-            background: Vec3::new(0., 0., 0.),
+            background: Background::SolidColor(Vec3::new(0., 0., 0.)),
+            lights: Vec::new(),
+            spectral: false,
         }
     }
 
+    /// Use the `sky` as background and add its sun to the [lights](Renderer::lights)
+    pub fn set_sky(&mut self, sky: PhysicalSky) {
+        self.lights.push(Light::Sun(SunLight::from_sky(&sky)));
+        self.background = Background::Sky(sky);
+    }
+
     /// Render scene using
     pub fn render(&self, width: usize, height: usize, show_progress: bool) -> Vec<Vec3> {
         let mut pixels = vec![Vec3::zero(); width * height];
@@ -38,20 +66,25 @@ impl Renderer {
         } else {
             None
         };
+        let spread = self.camera.pixel_spread(height);
+        let ray_width = self.camera.pixel_width(height);
         pixels.par_iter_mut().enumerate().for_each(|(p_ix, pixel)| {
             let mut rng = thread_rng();
             for _ in 0..self.samples_per_pixel {
-                let x = ((p_ix % width) as f32 + rng.gen::<f32>()) / (width - 1) as f32;
-                let y = ((height - p_ix / width) as f32 + rng.gen::<f32>()) / (height - 1) as f32;
+                let x = ((p_ix % width) as f32 + rng.gen::<f32>()) / width as f32;
+                let y = ((height - 1 - p_ix / width) as f32 + rng.gen::<f32>()) / height as f32;
 
-                let ray = self.camera.get_ray(x, y);
-                *pixel += self.render_pixel(&ray, self.max_ray_bounces);
+                if let Some(ray) = self.camera.get_ray(x, y) {
+                    let ray = ray.with_spread(spread).with_width(ray_width);
+                    *pixel += self.render_pixel(&ray, self.max_ray_bounces);
+                }
             }
 
             let scale = 1. / self.samples_per_pixel as f32;
-            pixel.set_x(f32::sqrt(pixel.x() * scale));
-            pixel.set_y(f32::sqrt(pixel.y() * scale));
-            pixel.set_z(f32::sqrt(pixel.z() * scale));
+            // Spectral colors can be out of the RGB gamut
+            pixel.set_x(f32::sqrt(pixel.x().max(0.) * scale));
+            pixel.set_y(f32::sqrt(pixel.y().max(0.) * scale));
+            pixel.set_z(f32::sqrt(pixel.z().max(0.) * scale));
 
             // Update progress
             if show_progress {
@@ -63,25 +96,282 @@ impl Renderer {
     }
 
     fn render_pixel(&self, ray: &Ray, depth: usize) -> Vec3 {
+        let media = MediumStack::default();
+        if !self.spectral {
+            return self.trace(ray, depth, None, &media, None);
+        }
+        let mut wavelengths = SampledWavelengths::sample_visible(thread_rng().gen());
+        let radiance = self.trace(ray, depth, None, &media, Some(&mut wavelengths));
+        wavelengths.to_rgb(&radiance)
+    }
+
+    /// Radiance coming along the `ray`.
+    ///
+    /// `scatter_pdf` - probability density of the `ray` direction chosen by the previous
+    /// bounce, `None` for camera rays and specular bounces
+    ///
+    /// `media` - transparent objects the `ray` is inside of
+    ///
+    /// `wavelengths` - wavelengths carried by the path in spectral mode. Returned radiance
+    /// is then the values of the spectrum at them
+    fn trace(
+        &self,
+        ray: &Ray,
+        depth: usize,
+        scatter_pdf: Option<f32>,
+        media: &MediumStack,
+        wavelengths: Option<&mut SampledWavelengths>,
+    ) -> Vec3 {
         if depth == 0 {
             return Vec3::zero();
         }
 
+        let medium = match wavelengths.as_deref() {
+            Some(wavelengths) => media.current().sampled(wavelengths),
+            None => media.current(),
+        };
+        if medium.is_scattering() {
+            return match self.random_walk(ray, &medium) {
+                Some((ray, hit, weight, scattered)) => {
+                    // Direction after scattering wasn't chosen by surface
+                    let scatter_pdf = if scattered { None } else { scatter_pdf };
+                    weight * self.shade(&ray, &hit, depth, scatter_pdf, media, wavelengths)
+                }
+                None => Vec3::zero(),
+            };
+        }
+
         if let Some(hit) = self.objects.hit(ray, 0.001, f32::INFINITY) {
-            let emitted = hit.material.emitted(&hit.uv, &hit.location);
-            if let Some(scatter_result) = hit.material.scatter(ray, &hit) {
-                emitted
-                    + scatter_result.attenuation * self.render_pixel(&scatter_result.ray, depth - 1)
-            } else {
-                emitted
+            // Light is absorbed on the way through the medium
+            let transmittance = medium.transmittance(hit.distance * ray.direction.length());
+            transmittance * self.shade(ray, &hit, depth, scatter_pdf, media, wavelengths)
+        } else {
+            self.miss(ray, depth, scatter_pdf, wavelengths.as_deref())
+        }
+    }
+
+    /// Follow the `ray` scattering inside of the `medium` until it reaches a surface.
+    ///
+    /// Returns the last segment of the path with the surface hit, weight of the path
+    /// and whether the ray was scattered
+    fn random_walk(&self, ray: &Ray, medium: &Medium) -> Option<(Ray, HitResult, Vec3, bool)> {
+        const MAX_STEPS: usize = 256;
+
+        let mut rng = thread_rng();
+        // Whole walk is sampled by one channel, weighted over all of them
+        let channel = rng.gen_range(0..3);
+        let mut ray = Ray::new(ray.origin, ray.direction.norm(), ray.time);
+        let mut throughput = Vec3::new(1., 1., 1.);
+        let mut pdf = Vec3::new(1., 1., 1.);
+        for step in 0..MAX_STEPS {
+            let hit = self.objects.hit(&ray, 0.001, f32::INFINITY)?;
+            let flight = medium.sample_distance(hit.distance, channel, rng.gen());
+            // Keep density of the sampled channel at one to avoid underflow
+            let scale = pdf[channel] * flight.pdf[channel];
+            if scale == 0. {
+                return None;
+            }
+            throughput = &(throughput * flight.throughput) / scale;
+            pdf = &(pdf * flight.pdf) / scale;
+
+            match flight.distance {
+                // Isotropic scattering
+                Some(distance) => {
+                    let direction = random_in_unit_sphere().norm();
+                    ray = Ray::new(ray.at(distance), direction, ray.time);
+                }
+                None => {
+                    let weight = &throughput / ((pdf.x() + pdf.y() + pdf.z()) / 3.);
+                    return Some((ray, hit, weight, step > 0));
+                }
             }
+        }
+        None
+    }
+
+    /// Radiance leaving the hit surface towards the `ray` origin
+    fn shade(
+        &self,
+        ray: &Ray,
+        hit: &HitResult,
+        depth: usize,
+        scatter_pdf: Option<f32>,
+        media: &MediumStack,
+        mut wavelengths: Option<&mut SampledWavelengths>,
+    ) -> Vec3 {
+        // Media on both sides of the surface
+        let (exterior, interior) = match hit.material.medium() {
+            Some((id, medium)) => {
+                let (exterior, interior) = if hit.front_face {
+                    (media.clone(), media.entered(id, medium))
+                } else {
+                    (media.exited(id), media.clone())
+                };
+                // Boundary inside of a medium with higher priority doesn't exist
+                if media.is_hidden(id, &medium) {
+                    let crossed = if hit.front_face { interior } else { exterior };
+                    let ray = Ray::new(hit.location, ray.direction, ray.time)
+                        .with_spread(ray.spread)
+                        .with_width(ray.footprint(hit.distance));
+                    return self.trace(&ray, depth, scatter_pdf, &crossed, wavelengths);
+                }
+                (exterior, interior)
+            }
+            None => (media.clone(), media.clone()),
+        };
+
+        let emitted = self.emitted(ray, hit, scatter_pdf, wavelengths.as_deref());
+        let exterior_index = exterior.current().refraction_index;
+        let scatter_result =
+            hit.material
+                .scatter_from(ray, hit, exterior_index, wavelengths.as_deref_mut());
+        if let Some(scatter_result) = scatter_result {
+            // Normal faces the incoming ray, so the scattered ray crossing the surface
+            // points against it
+            let crossed = scatter_result.ray.direction.dot(&hit.normal) < 0.;
+            let media = if crossed == hit.front_face {
+                &interior
+            } else {
+                &exterior
+            };
+            emitted
+                + self.sample_background(ray, hit, wavelengths.as_deref())
+                + self.sample_lights(ray, hit, wavelengths.as_deref())
+                + scatter_result.attenuation
+                    * self.trace(
+                        &scatter_result.ray,
+                        depth - 1,
+                        scatter_result.pdf,
+                        media,
+                        wavelengths,
+                    )
         } else {
-            self.background
+            emitted
         }
+    }
+
+    /// Light emitted by the hit surface. Emitters registered as
+    /// [area lights](super::light::AreaLight) are also sampled explicitly, so their
+    /// contribution is weighted
+    fn emitted(
+        &self,
+        ray: &Ray,
+        hit: &HitResult,
+        scatter_pdf: Option<f32>,
+        wavelengths: Option<&SampledWavelengths>,
+    ) -> Vec3 {
+        let emitted = path_color(wavelengths, &hit.material.emitted(hit));
+        let pdf = match scatter_pdf {
+            Some(pdf) if emitted.length_squared() > 0. => pdf,
+            _ => return emitted,
+        };
+
+        let distance = hit.distance * ray.direction.length();
+        let light_pdf: f32 = self
+            .lights
+            .iter()
+            .map(|light| light.pdf(ray, distance))
+            .sum();
+        if light_pdf == 0. {
+            return emitted;
+        }
+        power_heuristic(pdf, light_pdf) * emitted
+    }
 
-        // let unit_direction = ray.direction.norm();
-        // let t = 0.5 * (unit_direction.y() + 1.0);
-        // // Blend between white and blue
-        // (1.0 - t) * Vec3::new(1., 1., 1.) + t * Vec3::new(0.5, 0.7, 1.0)
+    fn miss(
+        &self,
+        ray: &Ray,
+        depth: usize,
+        scatter_pdf: Option<f32>,
+        wavelengths: Option<&SampledWavelengths>,
+    ) -> Vec3 {
+        if depth == self.max_ray_bounces && !self.background.visible_to_camera() {
+            return Vec3::zero();
+        }
+
+        let radiance = path_color(wavelengths, &self.background.radiance(&ray.direction));
+        let mut radiance = match scatter_pdf {
+            // Direction could be also chosen by background sampling
+            Some(pdf) => power_heuristic(pdf, self.background.pdf(&ray.direction)) * radiance,
+            None => radiance,
+        };
+        for light in &self.lights {
+            let emitted = path_color(wavelengths, &light.escaped_radiance(ray));
+            radiance += match scatter_pdf {
+                // Direction could be also chosen by light sampling
+                Some(pdf) => power_heuristic(pdf, light.pdf(ray, f32::INFINITY)) * emitted,
+                None => emitted,
+            };
+        }
+        radiance
+    }
+
+    /// Light from explicitly sampled background direction
+    fn sample_background(
+        &self,
+        ray: &Ray,
+        hit: &HitResult,
+        wavelengths: Option<&SampledWavelengths>,
+    ) -> Vec3 {
+        let sample = match self.background.sample() {
+            Some(sample) => sample,
+            None => return Vec3::zero(),
+        };
+        let eval = match hit
+            .material
+            .evaluate_from(ray, hit, &sample.direction, wavelengths)
+        {
+            Some(eval) if eval.pdf > 0. => eval,
+            _ => return Vec3::zero(),
+        };
+
+        let shadow_ray = Ray::new(hit.location, sample.direction, ray.time);
+        if self
+            .objects
+            .hit(&shadow_ray, 0.001, f32::INFINITY)
+            .is_some()
+        {
+            return Vec3::zero();
+        }
+        let weight = power_heuristic(sample.pdf, eval.pdf) / sample.pdf;
+        weight * (eval.value * path_color(wavelengths, &sample.radiance))
+    }
+
+    /// Direct light from [lights](Renderer::lights)
+    fn sample_lights(
+        &self,
+        ray: &Ray,
+        hit: &HitResult,
+        wavelengths: Option<&SampledWavelengths>,
+    ) -> Vec3 {
+        let mut radiance = Vec3::zero();
+        for light in &self.lights {
+            let sample = match light.sample(&hit.location, ray.time) {
+                Some(sample) => sample,
+                None => continue,
+            };
+            let eval = match hit
+                .material
+                .evaluate_from(ray, hit, &sample.direction, wavelengths)
+            {
+                Some(eval) => eval,
+                None => continue,
+            };
+
+            let shadow_ray = Ray::new(hit.location, sample.direction, ray.time);
+            if self
+                .objects
+                .hit(&shadow_ray, 0.001, sample.distance * (1. - 1e-4))
+                .is_none()
+            {
+                let weight = match sample.pdf {
+                    // Direction could be also chosen by scattering
+                    Some(pdf) => power_heuristic(pdf, eval.pdf) / pdf,
+                    None => 1.,
+                };
+                radiance += weight * (eval.value * path_color(wavelengths, &sample.radiance));
+            }
+        }
+        radiance
     }
 }
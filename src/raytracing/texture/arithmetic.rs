@@ -0,0 +1,100 @@
+use std::sync::Arc;
+
+use crate::math::vec3::Vec3;
+
+use super::{Texture, TextureFunc, UvCoords};
+
+pub enum ArithmeticOperation {
+    Add,
+    Multiply,
+}
+
+/// Per channel operation on two textures
+pub struct ArithmeticTexture {
+    pub left: Arc<Texture>,
+    pub right: Arc<Texture>,
+    pub operation: ArithmeticOperation,
+}
+
+impl ArithmeticTexture {
+    pub fn new(left: Arc<Texture>, right: Arc<Texture>, operation: ArithmeticOperation) -> Self {
+        Self {
+            left,
+            right,
+            operation,
+        }
+    }
+}
+
+impl TextureFunc for ArithmeticTexture {
+    fn value(&self, uv_coords: &UvCoords, point: &Vec3) -> Vec3 {
+        let left = self.left.value(uv_coords, point);
+        let right = self.right.value(uv_coords, point);
+        match self.operation {
+            ArithmeticOperation::Add => left + right,
+            ArithmeticOperation::Multiply => left * right,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use crate::{
+        math::vec3::Vec3,
+        raytracing::texture::{SolidColorTexture, Texture, TextureFunc, UvCoords},
+    };
+
+    use super::{ArithmeticOperation, ArithmeticTexture};
+
+    fn solid(red: f32, green: f32, blue: f32) -> Arc<Texture> {
+        Arc::new(Texture::SolidColor(SolidColorTexture::new(
+            red, green, blue,
+        )))
+    }
+
+    #[test]
+    fn operation_test() {
+        let uv = UvCoords::new(0.5, 0.5);
+        let color = solid(0.2, 0.4, 0.8);
+
+        let sum = ArithmeticTexture::new(
+            color.clone(),
+            solid(0.1, 0.2, 0.3),
+            ArithmeticOperation::Add,
+        );
+        let value = sum.value(&uv, &Vec3::zero());
+        assert!(
+            (value - Vec3::new(0.3, 0.6, 1.1)).length() < 1e-6,
+            "{value:?}"
+        );
+
+        // Gray texture scales the color, colored one filters each channel
+        let scaled = ArithmeticTexture::new(
+            color.clone(),
+            solid(0.5, 0.5, 0.5),
+            ArithmeticOperation::Multiply,
+        );
+        let value = scaled.value(&uv, &Vec3::zero());
+        assert!(
+            (value - Vec3::new(0.1, 0.2, 0.4)).length() < 1e-6,
+            "{value:?}"
+        );
+        let filtered =
+            ArithmeticTexture::new(color, solid(1., 0.5, 0.), ArithmeticOperation::Multiply);
+        let value = filtered.value(&uv, &Vec3::zero());
+        assert!(
+            (value - Vec3::new(0.2, 0.2, 0.)).length() < 1e-6,
+            "{value:?}"
+        );
+
+        // Scalar of gray operands is the result of the operation on their values
+        let product = ArithmeticTexture::new(
+            solid(0.5, 0.5, 0.5),
+            solid(0.3, 0.3, 0.3),
+            ArithmeticOperation::Multiply,
+        );
+        assert!((product.scalar(&uv, &Vec3::zero()) - 0.15).abs() < 1e-5);
+    }
+}
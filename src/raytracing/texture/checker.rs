@@ -1,35 +1,65 @@
 use std::sync::Arc;
 
-use super::{solid_color::SolidColorTexture, TextureFunc};
+use crate::math::vec3::Vec3;
+
+use super::{Texture, TextureFunc, UvCoords};
+
+/// Space in which checker pattern is evaluated
+pub enum CheckerMode {
+    /// 3D pattern from hit point
+    Solid,
+    /// 2D pattern from texture coordinates
+    Uv,
+}
 
 pub struct CheckerTexture {
-    pub odd: Arc<SolidColorTexture>,
-    pub even: Arc<SolidColorTexture>,
+    pub odd: Arc<Texture>,
+    pub even: Arc<Texture>,
+    /// Pattern frequency
+    pub scale: f32,
+    pub mode: CheckerMode,
 }
 
 impl CheckerTexture {
-    pub fn new(odd_color: Arc<SolidColorTexture>, even_color: Arc<SolidColorTexture>) -> Self {
+    pub fn new(odd: Arc<Texture>, even: Arc<Texture>) -> Self {
+        Self {
+            odd,
+            even,
+            scale: 10.,
+            mode: CheckerMode::Solid,
+        }
+    }
+
+    /// Checker with 2D pattern and `scale` squares per unit of texture coordinates
+    pub fn uv(odd: Arc<Texture>, even: Arc<Texture>, scale: f32) -> Self {
         Self {
-            odd: odd_color,
-            even: even_color,
+            odd,
+            even,
+            scale,
+            mode: CheckerMode::Uv,
+        }
+    }
+
+    fn is_odd(&self, uv_coords: &UvCoords, point: &Vec3) -> bool {
+        match self.mode {
+            CheckerMode::Solid => {
+                let sines = f32::sin(self.scale * point.x())
+                    * f32::sin(self.scale * point.y())
+                    * f32::sin(self.scale * point.z());
+                sines < 0.0
+            }
+            CheckerMode::Uv => {
+                let u = (self.scale * uv_coords.u).floor() as i32;
+                let v = (self.scale * uv_coords.v).floor() as i32;
+                (u + v) % 2 != 0
+            }
         }
     }
 }
 
 impl TextureFunc for CheckerTexture {
-    fn value(
-        &self,
-        uv_coords: &super::UvCoords,
-        point: &crate::math::vec3::Vec3,
-    ) -> crate::math::vec3::Vec3 {
-        // auto sines = sin(10*p.x())*sin(10*p.y())*sin(10*p.z());
-        // if (sines < 0)
-        //     return odd->value(u, v, p);
-        // else
-        //     return even->value(u, v, p);
-        let sines =
-            f32::sin(10.0 * point.x()) * f32::sin(10.0 * point.y()) * f32::sin(10.0 * point.z());
-        if sines < 0.0 {
+    fn value(&self, uv_coords: &UvCoords, point: &Vec3) -> Vec3 {
+        if self.is_odd(uv_coords, point) {
             self.odd.value(uv_coords, point)
         } else {
             self.even.value(uv_coords, point)
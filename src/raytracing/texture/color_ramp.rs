@@ -0,0 +1,99 @@
+use std::sync::Arc;
+
+use crate::math::vec3::Vec3;
+
+use super::{Texture, TextureFunc, UvCoords};
+
+/// Maps scalar value in range [0, 1] to color by linear interpolation between stops
+#[derive(Clone)]
+pub struct ColorRamp {
+    stops: Vec<(f32, Vec3)>,
+}
+
+impl ColorRamp {
+    /// Create ramp from `(position, color)` stops. Order of stops is not important
+    pub fn new(mut stops: Vec<(f32, Vec3)>) -> Self {
+        stops.sort_unstable_by(|left, right| left.0.total_cmp(&right.0));
+        Self { stops }
+    }
+
+    /// Ramp between two colors
+    pub fn linear(start: Vec3, end: Vec3) -> Self {
+        Self::new(vec![(0., start), (1., end)])
+    }
+
+    /// Black to white ramp
+    pub fn grayscale() -> Self {
+        Self::linear(Vec3::zero(), Vec3::new(1., 1., 1.))
+    }
+
+    pub fn sample(&self, value: f32) -> Vec3 {
+        let (first, last) = match (self.stops.first(), self.stops.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return Vec3::zero(),
+        };
+        if value <= first.0 {
+            return first.1;
+        }
+        if value >= last.0 {
+            return last.1;
+        }
+
+        let next = self
+            .stops
+            .iter()
+            .position(|stop| stop.0 > value)
+            .unwrap_or(self.stops.len() - 1);
+        let (start, end) = (&self.stops[next - 1], &self.stops[next]);
+        let t = (value - start.0) / (end.0 - start.0);
+        (1. - t) * start.1 + t * end.1
+    }
+}
+
+impl Default for ColorRamp {
+    fn default() -> Self {
+        Self::grayscale()
+    }
+}
+
+/// Remaps scalar value of inner texture with color ramp
+pub struct RampTexture {
+    pub input: Arc<Texture>,
+    pub ramp: ColorRamp,
+}
+
+impl RampTexture {
+    pub fn new(input: Arc<Texture>, ramp: ColorRamp) -> Self {
+        Self { input, ramp }
+    }
+}
+
+impl TextureFunc for RampTexture {
+    fn value(&self, uv_coords: &UvCoords, point: &Vec3) -> Vec3 {
+        self.ramp.sample(self.input.scalar(uv_coords, point))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::math::vec3::Vec3;
+
+    use super::ColorRamp;
+
+    #[test]
+    fn sample_test() {
+        let ramp = ColorRamp::new(vec![
+            (1., Vec3::new(0., 0., 1.)),
+            (0., Vec3::new(1., 0., 0.)),
+            (0.5, Vec3::new(0., 1., 0.)),
+        ]);
+        assert_eq!(ramp.sample(-1.).x(), 1.);
+        assert_eq!(ramp.sample(2.).z(), 1.);
+        assert_eq!(ramp.sample(0.5).y(), 1.);
+
+        let color = ramp.sample(0.75);
+        assert_eq!(color.x(), 0.);
+        assert_eq!(color.y(), 0.5);
+        assert_eq!(color.z(), 0.5);
+    }
+}
@@ -1,59 +1,332 @@
-use std::{
-    io::{self},
-    path::Path,
-};
+use std::path::Path;
 
-use image::RgbImage;
+use image::{DynamicImage, ImageError};
+use thiserror::Error;
 
 use crate::math::vec3::Vec3;
 
-use super::TextureFunc;
+use super::{TextureFunc, UvCoords};
+
+#[derive(Error, Debug)]
+pub enum ImageTextureError {
+    #[error("Failed to load texture image: {0}")]
+    Load(#[from] ImageError),
+    #[error("Texture image is empty")]
+    Empty,
+}
+
+/// Encoding of the stored pixel values
+#[derive(Clone, Copy)]
+pub enum ColorSpace {
+    /// Gamma encoded colors, e.g. photos and painted textures
+    Srgb,
+    /// Data stored as is, e.g. normal or roughness maps
+    Linear,
+}
+
+/// Texture coordinates handling outside of [0, 1] range
+#[derive(Clone, Copy)]
+pub enum WrapMode {
+    Repeat,
+    Mirror,
+    Clamp,
+}
+
+/// Reconstruction filter between texels
+#[derive(Clone, Copy)]
+pub enum TextureFilter {
+    Nearest,
+    Bilinear,
+    /// Catmull-Rom spline over 4x4 texels
+    Bicubic,
+}
 
 pub struct ImageTexture {
-    image_buffer: RgbImage,
+    /// Mipmap pyramid of linear colors, the first level is the original image
+    levels: Vec<MipLevel>,
+    pub wrap: WrapMode,
+    pub filter: TextureFilter,
+    /// Select detail level by ray footprint
+    pub mipmapping: bool,
+}
+
+struct MipLevel {
+    width: usize,
+    height: usize,
+    pixels: Vec<Vec3>,
 }
 
 impl ImageTexture {
-    pub fn new<P: AsRef<Path>>(filepath: P) -> Result<Self, io::Error> {
-        let texture = image::open(filepath).unwrap().into_rgb8();
+    /// Load sRGB encoded image
+    pub fn new<P: AsRef<Path>>(filepath: P) -> Result<Self, ImageTextureError> {
+        Self::load(filepath, ColorSpace::Srgb)
+    }
+
+    pub fn load<P: AsRef<Path>>(
+        filepath: P,
+        color_space: ColorSpace,
+    ) -> Result<Self, ImageTextureError> {
+        Self::from_image(image::open(filepath)?, color_space)
+    }
+
+    pub fn from_image(
+        image: DynamicImage,
+        color_space: ColorSpace,
+    ) -> Result<Self, ImageTextureError> {
+        let image = image.into_rgb32f();
+        if image.width() == 0 || image.height() == 0 {
+            return Err(ImageTextureError::Empty);
+        }
+
+        let decode = |value: f32| match color_space {
+            ColorSpace::Srgb => srgb_to_linear(value),
+            ColorSpace::Linear => value,
+        };
+        let base = MipLevel {
+            width: image.width() as usize,
+            height: image.height() as usize,
+            pixels: image
+                .pixels()
+                .map(|pixel| Vec3::new(decode(pixel[0]), decode(pixel[1]), decode(pixel[2])))
+                .collect(),
+        };
+
+        let mut levels = vec![base];
+        while let Some(level) = levels.last().and_then(MipLevel::downsample) {
+            levels.push(level);
+        }
+
         Ok(Self {
-            image_buffer: texture,
+            levels,
+            wrap: WrapMode::Repeat,
+            filter: TextureFilter::Bilinear,
+            mipmapping: true,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.levels[0].width
+    }
+
+    pub fn height(&self) -> usize {
+        self.levels[0].height
+    }
+
+    /// Fractional mipmap level for given footprint in texture coordinates
+    fn detail_level(&self, footprint: f32) -> f32 {
+        if !self.mipmapping || footprint <= 0. {
+            return 0.;
+        }
+        let texels = footprint * self.width().max(self.height()) as f32;
+        f32::clamp(texels.log2(), 0., (self.levels.len() - 1) as f32)
+    }
+
+    fn sample(&self, level: usize, u: f32, v: f32) -> Vec3 {
+        let level = &self.levels[level];
+        // Texel centers are located at half-integer coordinates
+        let x = u * level.width as f32 - 0.5;
+        let y = v * level.height as f32 - 0.5;
+
+        match self.filter {
+            TextureFilter::Nearest => level.texel(x.round() as i64, y.round() as i64, self.wrap),
+            TextureFilter::Bilinear => {
+                let (x0, y0) = (x.floor(), y.floor());
+                let (tx, ty) = (x - x0, y - y0);
+                let (x0, y0) = (x0 as i64, y0 as i64);
+                let top = (1. - tx) * level.texel(x0, y0, self.wrap)
+                    + tx * level.texel(x0 + 1, y0, self.wrap);
+                let bottom = (1. - tx) * level.texel(x0, y0 + 1, self.wrap)
+                    + tx * level.texel(x0 + 1, y0 + 1, self.wrap);
+                (1. - ty) * top + ty * bottom
+            }
+            TextureFilter::Bicubic => {
+                let (x0, y0) = (x.floor(), y.floor());
+                let weights_x = catmull_rom_weights(x - x0);
+                let weights_y = catmull_rom_weights(y - y0);
+                let (x0, y0) = (x0 as i64, y0 as i64);
+
+                let mut color = Vec3::zero();
+                for (j, weight_y) in weights_y.iter().enumerate() {
+                    for (i, weight_x) in weights_x.iter().enumerate() {
+                        let texel = level.texel(x0 + i as i64 - 1, y0 + j as i64 - 1, self.wrap);
+                        color += (weight_x * weight_y) * texel;
+                    }
+                }
+                // Spline may overshoot on sharp edges
+                Vec3::new(color.x().max(0.), color.y().max(0.), color.z().max(0.))
+            }
+        }
+    }
+}
+
+impl MipLevel {
+    fn texel(&self, x: i64, y: i64, wrap: WrapMode) -> Vec3 {
+        let x = wrap_index(x, self.width, wrap);
+        let y = wrap_index(y, self.height, wrap);
+        self.pixels[y * self.width + x]
+    }
+
+    /// Next level of the pyramid with half of the size. Even sizes use 2 texel box filter,
+    /// odd ones 3 texel filter weighted by texel overlap, so every texel contributes
+    fn downsample(&self) -> Option<Self> {
+        if self.width == 1 && self.height == 1 {
+            return None;
+        }
+        let width = (self.width / 2).max(1);
+        let height = (self.height / 2).max(1);
+
+        let mut pixels = Vec::with_capacity(width * height);
+        for y in 0..height {
+            let taps_y = downsample_taps(self.height, y);
+            for x in 0..width {
+                let taps_x = downsample_taps(self.width, x);
+                let mut color = Vec3::zero();
+                for (source_y, weight_y) in taps_y {
+                    for (source_x, weight_x) in taps_x {
+                        color +=
+                            (weight_x * weight_y) * self.texel(source_x, source_y, WrapMode::Clamp);
+                    }
+                }
+                pixels.push(color);
+            }
+        }
+        Some(Self {
+            width,
+            height,
+            pixels,
         })
     }
 }
 
+/// Source texels and their weights for texel at `index` of the level downsampled
+/// from `size`, see "Non-Power-of-Two Mipmapping" by Nvidia
+fn downsample_taps(size: usize, index: usize) -> [(i64, f32); 3] {
+    let source = 2 * index as i64;
+    if size == 1 {
+        [(0, 1.), (0, 0.), (0, 0.)]
+    } else if size.is_multiple_of(2) {
+        [(source, 0.5), (source + 1, 0.5), (source + 1, 0.)]
+    } else {
+        let (half, index, size) = ((size / 2) as f32, index as f32, size as f32);
+        [
+            (source, (half - index) / size),
+            (source + 1, half / size),
+            (source + 2, (index + 1.) / size),
+        ]
+    }
+}
+
 impl TextureFunc for ImageTexture {
-    fn value(
-        &self,
-        uv_coords: &super::UvCoords,
-        _: &crate::math::vec3::Vec3,
-    ) -> crate::math::vec3::Vec3 {
-        // Clamp input texture coordinates to [0,1] x [1,0]
-        let u = f32::clamp(uv_coords.u, 0.0, 1.0);
+    fn value(&self, uv_coords: &UvCoords, _: &Vec3) -> Vec3 {
         // Flip V to image
-        let v = 1.0 - f32::clamp(uv_coords.v, 0.0, 1.0);
-
-        let mut x = (u * self.image_buffer.width() as f32) as u32;
-        let mut y = (v * self.image_buffer.height() as f32) as u32;
+        let (u, v) = (uv_coords.u, 1.0 - uv_coords.v);
 
-        if x >= self.image_buffer.width() {
-            x = self.image_buffer.width() - 1;
+        // Blend between two closest levels
+        let level = self.detail_level(uv_coords.footprint);
+        let lower = level.floor() as usize;
+        let t = level - lower as f32;
+        if t == 0. {
+            self.sample(lower, u, v)
+        } else {
+            (1. - t) * self.sample(lower, u, v) + t * self.sample(lower + 1, u, v)
         }
+    }
+}
 
-        // Clamp integer mapping, since actual coordinates should be less than 1.0
-        if y >= self.image_buffer.height() {
-            y = self.image_buffer.height() - 1;
+fn wrap_index(index: i64, size: usize, wrap: WrapMode) -> usize {
+    let size = size as i64;
+    let index = match wrap {
+        WrapMode::Repeat => index.rem_euclid(size),
+        WrapMode::Mirror => {
+            let period = index.rem_euclid(2 * size);
+            if period < size {
+                period
+            } else {
+                2 * size - 1 - period
+            }
         }
+        WrapMode::Clamp => index.clamp(0, size - 1),
+    };
+    index as usize
+}
+
+fn catmull_rom_weights(t: f32) -> [f32; 4] {
+    let t2 = t * t;
+    let t3 = t2 * t;
+    [
+        0.5 * (-t3 + 2. * t2 - t),
+        0.5 * (3. * t3 - 5. * t2 + 2.),
+        0.5 * (-3. * t3 + 4. * t2 + t),
+        0.5 * (t3 - t2),
+    ]
+}
+
+/// Decode gamma encoded sRGB channel value
+pub fn srgb_to_linear(value: f32) -> f32 {
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use image::{DynamicImage, Rgb, RgbImage};
 
-        const COLOR_SCALE: f32 = 1.0 / 255.0;
-        let pixel = self.image_buffer.get_pixel(x, y).0;
-        // auto pixel = data + j*bytes_per_scanline + i*bytes_per_pixel;
+    use crate::{math::vec3::Vec3, raytracing::texture::UvCoords};
+
+    use super::{wrap_index, ColorSpace, ImageTexture, MipLevel, TextureFunc, WrapMode};
+
+    #[test]
+    fn wrap_index_test() {
+        assert_eq!(wrap_index(-1, 4, WrapMode::Repeat), 3);
+        assert_eq!(wrap_index(5, 4, WrapMode::Repeat), 1);
+        assert_eq!(wrap_index(-1, 4, WrapMode::Mirror), 0);
+        assert_eq!(wrap_index(5, 4, WrapMode::Mirror), 2);
+        assert_eq!(wrap_index(-1, 4, WrapMode::Clamp), 0);
+        assert_eq!(wrap_index(5, 4, WrapMode::Clamp), 3);
+    }
+
+    #[test]
+    fn mipmap_test() {
+        let image = RgbImage::from_fn(4, 2, |x, _| {
+            if x % 2 == 0 {
+                Rgb([0, 0, 0])
+            } else {
+                Rgb([255, 255, 255])
+            }
+        });
+        let texture =
+            ImageTexture::from_image(DynamicImage::ImageRgb8(image), ColorSpace::Linear).unwrap();
+        assert_eq!(texture.levels.len(), 3);
+
+        let mut uv = UvCoords::new(0.125, 0.5);
+        assert_eq!(texture.value(&uv, &Vec3::zero()).x(), 0.);
+
+        // Footprint wider than the image averages all texels
+        uv.footprint = 4.;
+        assert!((texture.value(&uv, &Vec3::zero()).x() - 0.5).abs() < 1e-6);
+    }
+    #[test]
+    fn odd_size_mipmap_test() {
+        let level = MipLevel {
+            width: 5,
+            height: 1,
+            pixels: [0., 1., 2., 3., 10.]
+                .iter()
+                .map(|&value| Vec3::new(value, value, value))
+                .collect(),
+        };
+        // Edge texel is not dropped and the average is preserved
+        let next = level.downsample().unwrap();
+        assert_eq!((next.width, next.height), (2, 1));
+        let average = (next.pixels[0].x() + next.pixels[1].x()) / 2.;
+        assert!((average - 16. / 5.).abs() < 1e-5, "{average}");
+        assert!((next.pixels[1].x() - 5.6).abs() < 1e-5);
 
-        Vec3::new(
-            pixel[0] as f32 * COLOR_SCALE,
-            pixel[1] as f32 * COLOR_SCALE,
-            pixel[2] as f32 * COLOR_SCALE,
-        )
-        // return color(color_scale*pixel[0], color_scale*pixel[1], color_scale*pixel[2]);
+        let last = next.downsample().unwrap();
+        assert!((last.pixels[0].x() - 16. / 5.).abs() < 1e-5);
+        assert!(last.downsample().is_none());
     }
 }
@@ -0,0 +1,81 @@
+use std::sync::Arc;
+
+use crate::math::vec3::Vec3;
+
+use super::{Texture, TextureFunc, UvCoords};
+
+/// Linear interpolation between two textures.
+///
+/// Each channel of `mask` is a weight of `second` texture in the same channel
+pub struct MixTexture {
+    pub first: Arc<Texture>,
+    pub second: Arc<Texture>,
+    pub mask: Arc<Texture>,
+}
+
+impl MixTexture {
+    pub fn new(first: Arc<Texture>, second: Arc<Texture>, mask: Arc<Texture>) -> Self {
+        Self {
+            first,
+            second,
+            mask,
+        }
+    }
+}
+
+impl TextureFunc for MixTexture {
+    fn value(&self, uv_coords: &UvCoords, point: &Vec3) -> Vec3 {
+        let mask = self.mask.value(uv_coords, point);
+        let inverse = Vec3::new(1. - mask.x(), 1. - mask.y(), 1. - mask.z());
+        inverse * self.first.value(uv_coords, point) + mask * self.second.value(uv_coords, point)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use crate::{
+        math::vec3::Vec3,
+        raytracing::texture::{SolidColorTexture, Texture, TextureFunc, UvCoords},
+    };
+
+    use super::MixTexture;
+
+    fn solid(red: f32, green: f32, blue: f32) -> Arc<Texture> {
+        Arc::new(Texture::SolidColor(SolidColorTexture::new(
+            red, green, blue,
+        )))
+    }
+
+    #[test]
+    fn mask_test() {
+        let uv = UvCoords::new(0.5, 0.5);
+        let first = solid(1., 0., 0.);
+        let second = solid(0., 0., 1.);
+
+        // Gray mask is a single weight for all channels
+        let mix = MixTexture::new(first.clone(), second.clone(), solid(0.25, 0.25, 0.25));
+        let color = mix.value(&uv, &Vec3::zero());
+        assert!(
+            (color - Vec3::new(0.75, 0., 0.25)).length() < 1e-6,
+            "{color:?}"
+        );
+
+        // Colored mask weights each channel separately
+        let mix = MixTexture::new(solid(0.2, 0.4, 0.6), solid(1., 1., 1.), solid(1., 0., 0.5));
+        let color = mix.value(&uv, &Vec3::zero());
+        assert!(
+            (color - Vec3::new(1., 0.4, 0.8)).length() < 1e-6,
+            "{color:?}"
+        );
+
+        // Masks outside of [0, 1] extrapolate
+        let mix = MixTexture::new(first, second, solid(2., 2., 2.));
+        let color = mix.value(&uv, &Vec3::zero());
+        assert!(
+            (color - Vec3::new(-1., 0., 2.)).length() < 1e-6,
+            "{color:?}"
+        );
+    }
+}
@@ -1,15 +1,40 @@
+pub mod arithmetic;
 pub mod checker;
+pub mod color_ramp;
 pub mod image;
+pub mod mix;
+pub mod noise;
 pub mod solid_color;
+pub mod uv_transform;
+pub mod voronoi;
 
-use crate::math::vec3::Vec3;
+use crate::math::{colorimetry::luminance, vec3::Vec3};
 
-pub use self::{checker::CheckerTexture, image::ImageTexture, solid_color::SolidColorTexture};
+pub use self::{
+    arithmetic::{ArithmeticOperation, ArithmeticTexture},
+    checker::{CheckerMode, CheckerTexture},
+    color_ramp::{ColorRamp, RampTexture},
+    image::{ColorSpace, ImageTexture, ImageTextureError, TextureFilter, WrapMode},
+    mix::MixTexture,
+    noise::{MarbleTexture, NoiseTexture, TurbulenceTexture, WoodTexture},
+    solid_color::SolidColorTexture,
+    uv_transform::UvTransformTexture,
+    voronoi::{VoronoiMode, VoronoiTexture},
+};
 
 pub enum Texture {
     SolidColor(SolidColorTexture),
     Checker(CheckerTexture),
     Image(ImageTexture),
+    Noise(NoiseTexture),
+    Turbulence(TurbulenceTexture),
+    Marble(MarbleTexture),
+    Wood(WoodTexture),
+    Voronoi(VoronoiTexture),
+    Mix(MixTexture),
+    Arithmetic(ArithmeticTexture),
+    Ramp(RampTexture),
+    UvTransform(UvTransformTexture),
 }
 
 /// Common functionality for textures
@@ -19,18 +44,42 @@ pub trait TextureFunc {
     /// `UvCoords` - texture coordinates on surfacee
     /// `point` - point on shape
     fn value(&self, uv_coords: &UvCoords, point: &Vec3) -> Vec3;
+
+    /// Get texture value as a single number, i.e. luminance of the color
+    fn scalar(&self, uv_coords: &UvCoords, point: &Vec3) -> f32 {
+        luminance(&self.value(uv_coords, point))
+    }
 }
 
 pub trait UvMapper {
     fn get_uv_coords(&self, normal: &Vec3) -> UvCoords;
+
+    /// Derivatives of the surface location by `u` and `v` texture coordinates
+    fn get_uv_derivatives(&self, normal: &Vec3) -> (Vec3, Vec3);
+
+    /// Approximate distance on the surface covered by unit of texture coordinates
+    fn uv_extent(&self) -> f32;
 }
 
 /// Texture coordinates
+#[derive(Clone, Copy)]
 pub struct UvCoords {
     // `x` axis coord
     pub u: f32,
     // `y` axis coord
     pub v: f32,
+    /// Filter width in texture coordinates. 0 selects the most detailed texture level
+    pub footprint: f32,
+}
+
+impl UvCoords {
+    pub fn new(u: f32, v: f32) -> Self {
+        Self {
+            u,
+            v,
+            footprint: 0.,
+        }
+    }
 }
 
 impl TextureFunc for Texture {
@@ -39,6 +88,15 @@ impl TextureFunc for Texture {
             Texture::SolidColor(tex) => tex.value(uv_coords, point),
             Texture::Checker(tex) => tex.value(uv_coords, point),
             Texture::Image(tex) => tex.value(uv_coords, point),
+            Texture::Noise(tex) => tex.value(uv_coords, point),
+            Texture::Turbulence(tex) => tex.value(uv_coords, point),
+            Texture::Marble(tex) => tex.value(uv_coords, point),
+            Texture::Wood(tex) => tex.value(uv_coords, point),
+            Texture::Voronoi(tex) => tex.value(uv_coords, point),
+            Texture::Mix(tex) => tex.value(uv_coords, point),
+            Texture::Arithmetic(tex) => tex.value(uv_coords, point),
+            Texture::Ramp(tex) => tex.value(uv_coords, point),
+            Texture::UvTransform(tex) => tex.value(uv_coords, point),
         }
     }
 }
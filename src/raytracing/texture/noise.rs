@@ -0,0 +1,121 @@
+use crate::math::{perlin::Perlin, vec3::Vec3};
+
+use super::{ColorRamp, TextureFunc, UvCoords};
+
+/// Smooth Perlin noise
+pub struct NoiseTexture {
+    pub noise: Perlin,
+    /// Noise frequency
+    pub scale: f32,
+    pub ramp: ColorRamp,
+}
+
+/// Perlin noise summed over several octaves
+pub struct TurbulenceTexture {
+    pub noise: Perlin,
+    /// Noise frequency
+    pub scale: f32,
+    pub octaves: usize,
+    pub ramp: ColorRamp,
+}
+
+/// Sine stripes along `z` axis distorted by turbulence
+pub struct MarbleTexture {
+    pub noise: Perlin,
+    /// Stripes frequency
+    pub scale: f32,
+    pub octaves: usize,
+    /// Amount of stripes distortion
+    pub turbulence: f32,
+    pub ramp: ColorRamp,
+}
+
+/// Concentric rings around `y` axis distorted by turbulence
+pub struct WoodTexture {
+    pub noise: Perlin,
+    /// Noise frequency
+    pub scale: f32,
+    pub octaves: usize,
+    /// Number of rings per unit of distance from the axis
+    pub rings: f32,
+    /// Amount of rings distortion
+    pub turbulence: f32,
+    pub ramp: ColorRamp,
+}
+
+impl NoiseTexture {
+    pub fn new(scale: f32) -> Self {
+        Self {
+            noise: Perlin::new(),
+            scale,
+            ramp: ColorRamp::default(),
+        }
+    }
+}
+
+impl TurbulenceTexture {
+    pub fn new(scale: f32, octaves: usize) -> Self {
+        Self {
+            noise: Perlin::new(),
+            scale,
+            octaves,
+            ramp: ColorRamp::default(),
+        }
+    }
+}
+
+impl MarbleTexture {
+    pub fn new(scale: f32) -> Self {
+        Self {
+            noise: Perlin::new(),
+            scale,
+            octaves: 7,
+            turbulence: 10.,
+            ramp: ColorRamp::default(),
+        }
+    }
+}
+
+impl WoodTexture {
+    pub fn new(scale: f32, rings: f32) -> Self {
+        Self {
+            noise: Perlin::new(),
+            scale,
+            octaves: 4,
+            rings,
+            turbulence: 1.,
+            ramp: ColorRamp::linear(Vec3::new(0.35, 0.2, 0.08), Vec3::new(0.75, 0.55, 0.3)),
+        }
+    }
+}
+
+impl TextureFunc for NoiseTexture {
+    fn value(&self, _: &UvCoords, point: &Vec3) -> Vec3 {
+        let noise = self.noise.noise(&(self.scale * point));
+        self.ramp.sample(0.5 * (1. + noise))
+    }
+}
+
+impl TextureFunc for TurbulenceTexture {
+    fn value(&self, _: &UvCoords, point: &Vec3) -> Vec3 {
+        let turbulence = self.noise.turbulence(&(self.scale * point), self.octaves);
+        self.ramp.sample(turbulence)
+    }
+}
+
+impl TextureFunc for MarbleTexture {
+    fn value(&self, _: &UvCoords, point: &Vec3) -> Vec3 {
+        let turbulence = self.noise.turbulence(point, self.octaves);
+        let phase = self.scale * point.z() + self.turbulence * turbulence;
+        self.ramp.sample(0.5 * (1. + f32::sin(phase)))
+    }
+}
+
+impl TextureFunc for WoodTexture {
+    fn value(&self, _: &UvCoords, point: &Vec3) -> Vec3 {
+        let distance = f32::hypot(point.x(), point.z());
+        let turbulence = self.noise.turbulence(&(self.scale * point), self.octaves);
+        let rings = self.rings * distance + self.turbulence * turbulence;
+        self.ramp.sample(rings - rings.floor())
+    }
+}
@@ -0,0 +1,111 @@
+use std::sync::Arc;
+
+use crate::math::{degrees_to_radians, vec3::Vec3};
+
+use super::{Texture, TextureFunc, UvCoords};
+
+/// Transforms texture coordinates before sampling inner texture.
+///
+/// Coordinates are scaled and rotated around the texture center, then shifted by `offset`
+pub struct UvTransformTexture {
+    pub texture: Arc<Texture>,
+    pub scale: (f32, f32),
+    pub offset: (f32, f32),
+    sin_theta: f32,
+    cos_theta: f32,
+}
+
+impl UvTransformTexture {
+    /// `rotation` - angle in degrees
+    pub fn new(
+        texture: Arc<Texture>,
+        scale: (f32, f32),
+        offset: (f32, f32),
+        rotation: f32,
+    ) -> Self {
+        let (sin_theta, cos_theta) = f32::sin_cos(degrees_to_radians(rotation));
+        Self {
+            texture,
+            scale,
+            offset,
+            sin_theta,
+            cos_theta,
+        }
+    }
+
+    fn transform(&self, uv_coords: &UvCoords) -> UvCoords {
+        let u = (uv_coords.u - 0.5) * self.scale.0;
+        let v = (uv_coords.v - 0.5) * self.scale.1;
+        UvCoords {
+            u: self.cos_theta * u - self.sin_theta * v + 0.5 + self.offset.0,
+            v: self.sin_theta * u + self.cos_theta * v + 0.5 + self.offset.1,
+            footprint: uv_coords.footprint * f32::max(self.scale.0.abs(), self.scale.1.abs()),
+        }
+    }
+}
+
+impl TextureFunc for UvTransformTexture {
+    fn value(&self, uv_coords: &UvCoords, point: &Vec3) -> Vec3 {
+        self.texture.value(&self.transform(uv_coords), point)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use image::{DynamicImage, Rgb, RgbImage};
+
+    use crate::{
+        math::vec3::Vec3,
+        raytracing::texture::{
+            ColorSpace, ImageTexture, Texture, TextureFilter, TextureFunc, UvCoords, WrapMode,
+        },
+    };
+
+    use super::UvTransformTexture;
+
+    /// Black left half and white right half
+    fn halves(wrap: WrapMode) -> Arc<Texture> {
+        let image = RgbImage::from_fn(2, 1, |x, _| Rgb([255 * x as u8; 3]));
+        let mut texture =
+            ImageTexture::from_image(DynamicImage::ImageRgb8(image), ColorSpace::Linear).unwrap();
+        texture.wrap = wrap;
+        texture.filter = TextureFilter::Nearest;
+        Arc::new(Texture::Image(texture))
+    }
+
+    #[test]
+    fn transform_test() {
+        let value = |texture: &UvTransformTexture, u: f32| {
+            texture.value(&UvCoords::new(u, 0.5), &Vec3::zero()).x()
+        };
+        let shifted = |offset: f32, wrap: WrapMode| {
+            UvTransformTexture::new(halves(wrap), (1., 1.), (offset, 0.), 0.)
+        };
+
+        assert_eq!(value(&shifted(0., WrapMode::Repeat), 0.25), 0.);
+        assert_eq!(value(&shifted(0.5, WrapMode::Repeat), 0.25), 1.);
+        // Coordinates shifted out of the image are wrapped by the inner texture
+        assert_eq!(value(&shifted(1., WrapMode::Repeat), 0.25), 0.);
+        assert_eq!(value(&shifted(1., WrapMode::Clamp), 0.25), 1.);
+        assert_eq!(value(&shifted(-1., WrapMode::Clamp), 0.75), 0.);
+
+        // Rotation and scale are around the texture center
+        let rotated = UvTransformTexture::new(halves(WrapMode::Clamp), (1., 1.), (0., 0.), 180.);
+        assert_eq!(value(&rotated, 0.25), 1.);
+        let mirrored = UvTransformTexture::new(halves(WrapMode::Clamp), (-1., 1.), (0., 0.), 0.);
+        assert_eq!(value(&mirrored, 0.25), 1.);
+        let scaled = UvTransformTexture::new(halves(WrapMode::Repeat), (3., 1.), (0., 0.), 0.);
+        assert_eq!(value(&scaled, 0.5 + 0.2 / 3.), 1.);
+        assert_eq!(value(&scaled, 0.5 + 0.7 / 3.), 0.);
+
+        // Filter width grows with the larger scale
+        let uv = UvCoords {
+            footprint: 0.1,
+            ..UvCoords::new(0.5, 0.5)
+        };
+        let scaled = UvTransformTexture::new(halves(WrapMode::Repeat), (2., -3.), (0., 0.), 0.);
+        assert!((scaled.transform(&uv).footprint - 0.3).abs() < 1e-6);
+    }
+}
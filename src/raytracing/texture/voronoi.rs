@@ -0,0 +1,45 @@
+use crate::math::{vec3::Vec3, worley::Worley};
+
+use super::{ColorRamp, TextureFunc, UvCoords};
+
+/// Value of Worley noise used for coloring
+pub enum VoronoiMode {
+    /// Distance to the closest feature point
+    Distance,
+    /// Difference between the two closest feature points, highlights cell borders
+    Edges,
+    /// Flat random value per cell
+    Cells,
+}
+
+/// Worley/Voronoi cellular texture
+pub struct VoronoiTexture {
+    pub noise: Worley,
+    /// Cells frequency
+    pub scale: f32,
+    pub mode: VoronoiMode,
+    pub ramp: ColorRamp,
+}
+
+impl VoronoiTexture {
+    pub fn new(scale: f32, mode: VoronoiMode) -> Self {
+        Self {
+            noise: Worley::new(),
+            scale,
+            mode,
+            ramp: ColorRamp::default(),
+        }
+    }
+}
+
+impl TextureFunc for VoronoiTexture {
+    fn value(&self, _: &UvCoords, point: &Vec3) -> Vec3 {
+        let sample = self.noise.sample(&(self.scale * point));
+        let value = match self.mode {
+            VoronoiMode::Distance => sample.f1,
+            VoronoiMode::Edges => sample.f2 - sample.f1,
+            VoronoiMode::Cells => sample.cell_value,
+        };
+        self.ramp.sample(value)
+    }
+}